@@ -55,7 +55,7 @@ impl MpdAddress<'static> {
         (cfg_addr, cfg_pw)
     }
 
-    fn resolve_config(
+    pub(super) fn resolve_config(
         addr: String,
         pw: Option<String>,
     ) -> (MpdAddress<'static>, Option<MpdPassword<'static>>) {
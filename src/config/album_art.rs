@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 use strum::Display;
 
@@ -15,6 +17,12 @@ pub struct AlbumArtConfigFile {
     pub vertical_align: VerticalAlignFile,
     #[serde(default)]
     pub horizontal_align: HorizontalAlignFile,
+    #[serde(default = "super::defaults::album_art_cover_names")]
+    pub cover_names: Vec<String>,
+    #[serde(default = "super::defaults::default_true")]
+    pub browser_preview: bool,
+    #[serde(default = "super::defaults::album_art_cache_max_age_days")]
+    pub cache_max_age_days: u32,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -24,6 +32,10 @@ pub struct AlbumArtConfig {
     pub disabled_protocols: Vec<&'static str>,
     pub vertical_align: VerticalAlign,
     pub horizontal_align: HorizontalAlign,
+    pub cover_names: Vec<&'static str>,
+    pub browser_preview: bool,
+    /// Cached covers older than this are fetched again, kept forever if none
+    pub cache_max_age: Option<Duration>,
 }
 
 #[derive(Default, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
@@ -63,6 +75,7 @@ pub enum ImageMethodFile {
     UeberzugX11,
     Iterm2,
     Sixel,
+    Block,
     None,
     #[default]
     Auto,
@@ -75,6 +88,7 @@ pub enum ImageMethod {
     UeberzugX11,
     Iterm2,
     Sixel,
+    Block,
     None,
     #[default]
     Unsupported,
@@ -96,6 +110,14 @@ impl From<AlbumArtConfigFile> for AlbumArtConfig {
                 .collect(),
             vertical_align: value.vertical_align.into(),
             horizontal_align: value.horizontal_align.into(),
+            browser_preview: value.browser_preview,
+            cache_max_age: (value.cache_max_age_days > 0)
+                .then(|| Duration::from_secs(u64::from(value.cache_max_age_days) * 24 * 60 * 60)),
+            cover_names: value
+                .cover_names
+                .into_iter()
+                .map(|name| name.leak() as &'static _)
+                .collect(),
         }
     }
 }
@@ -15,6 +15,9 @@ pub struct Args {
     #[arg(short, long)]
     /// Override the MPD password
     pub password: Option<String>,
+    #[arg(long, conflicts_with_all = ["address", "password"])]
+    /// Connect to MPD using the profile with this name from the config file
+    pub profile: Option<String>,
 }
 
 #[derive(Subcommand, Clone, Debug, PartialEq)]
@@ -25,6 +28,8 @@ pub enum Command {
         /// If provided, print the current config instead of the default one.
         #[arg(short, long, default_value = "false")]
         current: bool,
+        #[command(subcommand)]
+        cmd: Option<ConfigCmd>,
     },
     /// Prints the default theme. Can be used to bootstrap your theme file.
     Theme {
@@ -138,10 +143,31 @@ pub enum Command {
         // Id of the output to disable
         id: u32,
     },
+    /// Enable MPD output and disable all of the other ones
+    SoloOutput {
+        // Id of the output to enable
+        id: u32,
+    },
+    /// Set runtime attribute of MPD output, for example 'dop'
+    SetOutput {
+        // Id of the output to change
+        id: u32,
+        /// Name of the attribute
+        name: String,
+        /// New value of the attribute
+        value: String,
+    },
     /// List MPD decoder plugins
     Decoders,
+    /// Prints database statistics like the number of songs and MPD's uptime
+    Stats,
     /// Prints various information like the playback status
-    Status,
+    Status {
+        /// Format the output using a template instead of printing JSON, for
+        /// example '{State} {Elapsed}/{Duration}'
+        #[arg(short, long)]
+        format: Option<String>,
+    },
     /// Prints info about the current song.
     /// If --path specified, prints information about the song at the given path
     /// instead. If --path is specified multiple times, prints an array
@@ -149,6 +175,116 @@ pub enum Command {
     Song {
         #[arg(short, long)]
         path: Option<Vec<String>>,
+        /// Format the output using a template instead of printing JSON, for
+        /// example '{Artist} - {Title}'
+        #[arg(short, long)]
+        format: Option<String>,
+    },
+    /// Prints the current queue. Use one of the subcommands to manipulate it
+    /// instead.
+    Queue {
+        /// Format the output using a template instead of printing JSON, for
+        /// example '{Artist} - {Title}'
+        #[arg(short, long)]
+        format: Option<String>,
+        #[command(subcommand)]
+        cmd: Option<QueueCmd>,
+    },
+    /// Search the database for songs matching all of the given filters. The
+    /// search is not case sensitive.
+    Search {
+        /// Filters in the form of 'tag=value', for example 'artist=Queen'
+        #[arg(required = true, value_parser = parse_filter)]
+        filters: Vec<(String, String)>,
+        /// How the filter values are matched against the tags
+        #[arg(short, long)]
+        #[clap(value_enum, default_value_t = FilterKind::Contains)]
+        kind: FilterKind,
+        /// Format the output using a template instead of printing JSON, for
+        /// example '{Artist} - {Title}'
+        #[arg(short, long)]
+        format: Option<String>,
+        /// Add the found songs to the queue instead of printing them
+        #[arg(long, default_value = "false")]
+        add: bool,
+        /// Add the found songs to the queue and play the first one
+        #[arg(long, default_value = "false")]
+        play: bool,
+    },
+    /// Find songs in the database matching all of the given filters. The
+    /// search is case sensitive.
+    Find {
+        /// Filters in the form of 'tag=value', for example 'artist=Queen'
+        #[arg(required = true, value_parser = parse_filter)]
+        filters: Vec<(String, String)>,
+        /// How the filter values are matched against the tags
+        #[arg(short, long)]
+        #[clap(value_enum, default_value_t = FilterKind::Exact)]
+        kind: FilterKind,
+        /// Format the output using a template instead of printing JSON, for
+        /// example '{Artist} - {Title}'
+        #[arg(short, long)]
+        format: Option<String>,
+        /// Add the found songs to the queue instead of printing them
+        #[arg(long, default_value = "false")]
+        add: bool,
+        /// Add the found songs to the queue and play the first one
+        #[arg(long, default_value = "false")]
+        play: bool,
+    },
+    /// Lists unique values of a tag, optionally only for songs matching the
+    /// given filters
+    List {
+        /// Tag whose values should be listed, for example 'album'
+        tag: String,
+        /// Filters in the form of 'tag=value', for example 'artist=Queen'
+        #[arg(value_parser = parse_filter)]
+        filters: Vec<(String, String)>,
+        /// How the filter values are matched against the tags
+        #[arg(short, long)]
+        #[clap(value_enum, default_value_t = FilterKind::Exact)]
+        kind: FilterKind,
+        /// Add songs with the listed tag values to the queue instead of
+        /// printing them
+        #[arg(long, default_value = "false")]
+        add: bool,
+        /// Add songs with the listed tag values to the queue and play the
+        /// first one
+        #[arg(long, default_value = "false")]
+        play: bool,
+    },
+    /// Lists the contents of a directory in the music database. Defaults to
+    /// the database root.
+    Ls {
+        /// Path to a directory, relative to music directory root
+        path: Option<String>,
+        /// Format the output using a template instead of printing JSON, for
+        /// example '{Artist} - {Title}'
+        #[arg(short, long)]
+        format: Option<String>,
+        /// Add the directory contents to the queue instead of printing them
+        #[arg(long, default_value = "false")]
+        add: bool,
+        /// Add the directory contents to the queue and play the first song
+        #[arg(long, default_value = "false")]
+        play: bool,
+    },
+    /// Prints a line whenever something changes in MPD, until interrupted.
+    /// Each line contains the changed subsystem, the playback status and the
+    /// current song. A line without the subsystem is printed on start and
+    /// after reconnecting to MPD.
+    Watch {
+        /// Comma separated subsystems to watch, for example 'player,mixer'.
+        /// Possible values: `player`, `mixer`, `playlist`, `options`,
+        /// `database`, `update`, `stored_playlist`, `output`, `partition`,
+        /// `sticker`, `subscription`, `message`, `neighbor`, `mount`. Watches
+        /// all of them if not specified.
+        #[arg(short, long, value_delimiter = ',')]
+        events: Vec<String>,
+        /// Format the output using a template instead of printing JSON, for
+        /// example '{Artist} - {Title}'
+        #[arg(short, long)]
+        format: Option<String>,
     },
     /// Mounts supported storage to MPD
     Mount { name: String, path: String },
@@ -156,6 +292,8 @@ pub enum Command {
     Unmount { name: String },
     /// List currently mounted storages
     ListMounts,
+    /// List storages discovered on the network by MPD's neighbor plugins
+    ListNeighbors,
     /// Manipulate and query song stickers
     Sticker {
         #[command(subcommand)]
@@ -172,6 +310,19 @@ pub enum Command {
     },
 }
 
+#[derive(Subcommand, Clone, Debug, PartialEq)]
+#[clap(rename_all = "lower")]
+pub enum ConfigCmd {
+    /// Check the config file and its theme for errors. Every problem is
+    /// printed together with its location and exit code is non-zero if any
+    /// were found.
+    Validate {
+        /// If provided, only the theme file is checked
+        #[arg(short, long, default_value = "false")]
+        theme: bool,
+    },
+}
+
 #[derive(Subcommand, Clone, Debug, PartialEq)]
 #[clap(rename_all = "lower")]
 pub enum NotifyCmd {
@@ -199,6 +350,52 @@ pub enum Level {
     Warn,
 }
 
+#[derive(Subcommand, Clone, Debug, PartialEq)]
+#[clap(rename_all = "lower")]
+pub enum QueueCmd {
+    /// Remove songs from the queue
+    Remove {
+        /// Position of a song or a range in the form of 'start:end', end is
+        /// exclusive
+        range: String,
+    },
+    /// Move songs to a different position in the queue
+    Move {
+        /// Position of a song or a range in the form of 'start:end', end is
+        /// exclusive
+        range: String,
+        /// Target position, relative to the current song if prefixed by + or -
+        #[arg(allow_negative_numbers(true))]
+        to: String,
+    },
+    /// Shuffle the queue
+    Shuffle {
+        /// If supplied, only songs in this range in the form of 'start:end'
+        /// are shuffled
+        range: Option<String>,
+    },
+    /// Save the queue as a stored playlist
+    Save {
+        /// Name of the playlist
+        name: String,
+        /// What to do when a playlist with the given name already exists.
+        /// Requires MPD 0.24.0 or newer.
+        #[arg(short, long)]
+        #[clap(value_enum)]
+        mode: Option<SaveMode>,
+    },
+}
+
+#[derive(Parser, ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub enum SaveMode {
+    /// Fail if the playlist already exists
+    Create,
+    /// Append the queue to the existing playlist
+    Append,
+    /// Replace the existing playlist with the queue
+    Replace,
+}
+
 #[derive(Subcommand, Clone, Debug, PartialEq)]
 #[clap(rename_all = "lower")]
 pub enum StickerCmd {
@@ -244,6 +441,18 @@ pub enum StickerCmd {
     },
 }
 
+#[derive(Parser, ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub enum FilterKind {
+    /// Tag must be equal to the value
+    Exact,
+    /// Tag must contain the value
+    Contains,
+    /// Tag must start with the value
+    StartsWith,
+    /// Tag must match the value as a regular expression
+    Regex,
+}
+
 #[derive(Parser, ValueEnum, Copy, Clone, Debug, PartialEq)]
 pub enum OnOff {
     /// Enable
@@ -262,6 +471,15 @@ pub enum OnOffOneshot {
     Oneshot,
 }
 
+fn parse_filter(input: &str) -> Result<(String, String), String> {
+    match input.split_once('=') {
+        Some((tag, value)) if !tag.trim().is_empty() => {
+            Ok((tag.trim().to_owned(), value.to_owned()))
+        }
+        _ => Err(format!("Invalid filter '{input}', expected 'tag=value'")),
+    }
+}
+
 fn get_default_config_path() -> PathBuf {
     let mut path = PathBuf::new();
     if let Ok(dir) = std::env::var("XDG_CONFIG_HOME") {
@@ -3,7 +3,14 @@ use std::path::PathBuf;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
-use super::{Config, ConfigFile, MpdAddress, address::MpdPassword, utils::tilde_expand};
+use super::{
+    Config,
+    ConfigFile,
+    MpdAddress,
+    address::MpdPassword,
+    profiles::{Profile, ProfileFile},
+    utils::tilde_expand,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CliConfigFile {
@@ -15,6 +22,8 @@ pub struct CliConfigFile {
     cache_dir: Option<String>,
     #[serde(default)]
     lyrics_dir: Option<String>,
+    #[serde(default)]
+    profiles: Vec<ProfileFile>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -32,6 +41,7 @@ impl From<ConfigFile> for CliConfigFile {
             password: value.password,
             cache_dir: value.cache_dir,
             lyrics_dir: value.lyrics_dir,
+            profiles: value.profiles,
         }
     }
 }
@@ -60,11 +70,12 @@ impl CliConfigFile {
         self,
         address_cli: Option<String>,
         password_cli: Option<String>,
-    ) -> CliConfig {
+        profile: Option<&str>,
+    ) -> Result<CliConfig> {
         let (address, password) =
             MpdAddress::resolve(address_cli, password_cli, self.address, self.password);
 
-        CliConfig {
+        let config = CliConfig {
             cache_dir: self
                 .cache_dir
                 .map(|v| if v.ends_with('/') { v } else { format!("{v}/") }.leak() as &'static _),
@@ -74,6 +85,19 @@ impl CliConfigFile {
             }),
             address,
             password,
-        }
+        };
+
+        let Some(profile) = profile else {
+            return Ok(config);
+        };
+        let profiles = self.profiles.into_iter().map(Profile::from).collect::<Vec<_>>();
+        let profile = Profile::find(&profiles, profile)?;
+
+        Ok(CliConfig {
+            address: profile.address,
+            password: profile.password,
+            cache_dir: profile.cache_dir.or(config.cache_dir),
+            lyrics_dir: profile.lyrics_dir.or(config.lyrics_dir),
+        })
     }
 }
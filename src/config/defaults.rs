@@ -23,6 +23,34 @@ pub fn default_scrolloff() -> usize {
     0
 }
 
+pub fn default_search_history_size() -> usize {
+    50
+}
+
+pub fn default_lyrics_fetch_url() -> String {
+    "https://lrclib.net/api/get".to_string()
+}
+
+pub fn default_info_timeout_ms() -> u64 {
+    5000
+}
+
+pub fn default_warn_timeout_ms() -> u64 {
+    5000
+}
+
+pub fn default_error_timeout_ms() -> u64 {
+    10_000
+}
+
+pub fn default_notification_history_size() -> usize {
+    100
+}
+
+pub fn default_desktop_notification_levels() -> Vec<crate::shared::events::Level> {
+    vec![crate::shared::events::Level::Error]
+}
+
 pub fn default_read_timeout() -> u64 {
     10_000
 }
@@ -52,6 +80,21 @@ pub fn disabled_album_art_protos() -> Vec<String> {
     ["http://", "https://"].into_iter().map(|p| p.to_owned()).collect()
 }
 
+pub fn album_art_cover_names() -> Vec<String> {
+    ["cover.jpg", "cover.png", "folder.jpg", "folder.png", "front.jpg", "front.png"]
+        .into_iter()
+        .map(|name| name.to_owned())
+        .collect()
+}
+
+pub fn album_art_cache_max_age_days() -> u32 {
+    30
+}
+
+pub fn lyrics_not_found_max_age_days() -> u32 {
+    7
+}
+
 pub fn default_playing_label() -> String {
     "Playing".to_string()
 }
@@ -13,6 +13,9 @@ pub enum GlobalAction {
     ShowCurrentSongInfo,
     ShowOutputs,
     ShowDecoders,
+    ShowStorage,
+    ShowNotifications,
+    CycleLyricsTranslation,
     NextTrack,
     PreviousTrack,
     Stop,
@@ -29,6 +32,10 @@ pub enum GlobalAction {
     NextTab,
     PreviousTab,
     SwitchToTab(TabName),
+    UpdateDatabase,
+    RescanDatabase,
+    ReloadConfig,
+    SwitchServer,
     Command { command: &'static str, description: Option<&'static str> },
     ExternalCommand { command: &'static [&'static str], description: Option<&'static str> },
 }
@@ -42,6 +49,9 @@ pub enum GlobalActionFile {
     ShowCurrentSongInfo,
     ShowOutputs,
     ShowDecoders,
+    ShowStorage,
+    ShowNotifications,
+    CycleLyricsTranslation,
     NextTrack,
     PreviousTrack,
     Stop,
@@ -64,6 +74,10 @@ pub enum GlobalActionFile {
     PlaylistsTab,
     SearchTab,
     CommandMode,
+    UpdateDatabase,
+    RescanDatabase,
+    ReloadConfig,
+    SwitchServer,
     Command { command: String, description: Option<String> },
     ExternalCommand { command: Vec<String>, description: Option<String> },
 }
@@ -74,6 +88,9 @@ impl From<GlobalActionFile> for GlobalAction {
             GlobalActionFile::Quit => GlobalAction::Quit,
             GlobalActionFile::ShowOutputs => GlobalAction::ShowOutputs,
             GlobalActionFile::ShowDecoders => GlobalAction::ShowDecoders,
+            GlobalActionFile::ShowStorage => GlobalAction::ShowStorage,
+            GlobalActionFile::ShowNotifications => GlobalAction::ShowNotifications,
+            GlobalActionFile::CycleLyricsTranslation => GlobalAction::CycleLyricsTranslation,
             GlobalActionFile::ShowCurrentSongInfo => GlobalAction::ShowCurrentSongInfo,
             GlobalActionFile::CommandMode => GlobalAction::CommandMode,
             GlobalActionFile::Command { command, description } => GlobalAction::Command {
@@ -102,6 +119,10 @@ impl From<GlobalActionFile> for GlobalAction {
             GlobalActionFile::AlbumsTab => GlobalAction::SwitchToTab("Albums".into()),
             GlobalActionFile::PlaylistsTab => GlobalAction::SwitchToTab("Playlists".into()),
             GlobalActionFile::SearchTab => GlobalAction::SwitchToTab("Search".into()),
+            GlobalActionFile::UpdateDatabase => GlobalAction::UpdateDatabase,
+            GlobalActionFile::RescanDatabase => GlobalAction::RescanDatabase,
+            GlobalActionFile::ReloadConfig => GlobalAction::ReloadConfig,
+            GlobalActionFile::SwitchServer => GlobalAction::SwitchServer,
             GlobalActionFile::ExternalCommand { command, description } => {
                 GlobalAction::ExternalCommand {
                     command: command
@@ -122,6 +143,11 @@ impl ToDescription for GlobalAction {
             GlobalAction::Quit => "Exit rmpc",
             GlobalAction::ShowOutputs => "Show MPD outputs config",
             GlobalAction::ShowDecoders => "Show MPD decoder plugins",
+            GlobalAction::ShowStorage => "Show mounted storages and discovered neighbors",
+            GlobalAction::ShowNotifications => "Show history of status bar notifications",
+            GlobalAction::CycleLyricsTranslation => {
+                "Cycle the translation shown under the lyrics between the available languages"
+            }
             GlobalAction::ShowCurrentSongInfo => {
                 "Show metadata of the currently playing song in a modal popup"
             }
@@ -151,6 +177,14 @@ impl ToDescription for GlobalAction {
             GlobalAction::SwitchToTab(TabName("Search")) => "Switch directly to Search tab",
             GlobalAction::SwitchToTab(name) => format!("Switch directly to {name} tab").leak(),
             GlobalAction::ShowHelp => "Show keybinds",
+            GlobalAction::UpdateDatabase => "Update the whole MPD database",
+            GlobalAction::RescanDatabase => {
+                "Rescan the whole MPD database after confirmation, also rereading unchanged files"
+            }
+            GlobalAction::ReloadConfig => {
+                "Reload the config file and theme, keeping the current config if they are invalid"
+            }
+            GlobalAction::SwitchServer => "Connect to the MPD server of another profile",
             GlobalAction::CommandMode => "Enter command mode",
             GlobalAction::Command { description: None, .. } => "Execute a command",
             GlobalAction::Command { description: Some(desc), .. } => desc,
@@ -205,20 +239,36 @@ impl From<ArtistsActionsFile> for ArtistsActions {
 // Directories actions
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash, Clone)]
-pub enum DirectoriesActionsFile {}
+pub enum DirectoriesActionsFile {
+    Update,
+    Rescan,
+}
 
 #[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
-pub enum DirectoriesActions {}
+pub enum DirectoriesActions {
+    Update,
+    Rescan,
+}
 
 impl ToDescription for DirectoriesActions {
     fn to_description(&self) -> &'static str {
-        ""
+        match self {
+            DirectoriesActions::Update => {
+                "Update the directory or song under cursor in MPD database"
+            }
+            DirectoriesActions::Rescan => {
+                "Rescan the directory or song under cursor, also rereading files which did not change"
+            }
+        }
     }
 }
 
 impl From<DirectoriesActionsFile> for DirectoriesActions {
-    fn from(_value: DirectoriesActionsFile) -> Self {
-        unreachable!()
+    fn from(value: DirectoriesActionsFile) -> Self {
+        match value {
+            DirectoriesActionsFile::Update => DirectoriesActions::Update,
+            DirectoriesActionsFile::Rescan => DirectoriesActions::Rescan,
+        }
     }
 }
 
@@ -312,6 +362,50 @@ impl ToDescription for QueueActions {
     }
 }
 
+// Lyrics actions
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash, Clone)]
+pub enum LyricsActionsFile {
+    IncreaseOffset,
+    DecreaseOffset,
+    ToggleSync,
+    SyncLine,
+}
+
+#[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum LyricsActions {
+    IncreaseOffset,
+    DecreaseOffset,
+    ToggleSync,
+    SyncLine,
+}
+
+impl From<LyricsActionsFile> for LyricsActions {
+    fn from(value: LyricsActionsFile) -> Self {
+        match value {
+            LyricsActionsFile::IncreaseOffset => LyricsActions::IncreaseOffset,
+            LyricsActionsFile::DecreaseOffset => LyricsActions::DecreaseOffset,
+            LyricsActionsFile::ToggleSync => LyricsActions::ToggleSync,
+            LyricsActionsFile::SyncLine => LyricsActions::SyncLine,
+        }
+    }
+}
+
+impl ToDescription for LyricsActions {
+    fn to_description(&self) -> &str {
+        match self {
+            LyricsActions::IncreaseOffset => "Show lyrics 100ms earlier and save the offset",
+            LyricsActions::DecreaseOffset => "Show lyrics 100ms later and save the offset",
+            LyricsActions::ToggleSync => {
+                "Start or finish tap to sync mode, finishing saves the synced lines as a new lrc file"
+            }
+            LyricsActions::SyncLine => {
+                "In tap to sync mode, start the next line at the current time"
+            }
+        }
+    }
+}
+
 // Common actions
 
 #[derive(
@@ -407,7 +501,7 @@ impl ToDescription for CommonAction {
             CommonAction::Delete => {
                 "Delete. For example a playlist, song from a playlist or wipe the current queue"
             }
-            CommonAction::Rename => "Rename. Currently only for playlists",
+            CommonAction::Rename => "Rename a playlist or edit tags of a song in song info",
             CommonAction::Close => {
                 "Close/Stop whatever action is currently going on. Cancel filter, close a modal, etc."
             }
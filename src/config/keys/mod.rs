@@ -10,6 +10,7 @@ pub use actions::{
     CommonAction,
     DirectoriesActions,
     GlobalAction,
+    LyricsActions,
     PlaylistsActions,
     QueueActions,
     SearchActions,
@@ -20,6 +21,7 @@ use actions::{
     CommonActionFile,
     DirectoriesActionsFile,
     GlobalActionFile,
+    LyricsActionsFile,
     PlaylistsActionsFile,
     QueueActionsFile,
 };
@@ -42,6 +44,7 @@ pub struct KeyConfig {
     #[cfg(debug_assertions)]
     pub logs: HashMap<Key, LogsActions>,
     pub queue: HashMap<Key, QueueActions>,
+    pub lyrics: HashMap<Key, LyricsActions>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -52,7 +55,8 @@ pub struct KeyConfigFile {
     pub navigation: HashMap<Key, CommonActionFile>,
     // pub albums: HashMap<AlbumsActions, Vec<Key>>,
     // pub artists: HashMap<ArtistsActions, Vec<Key>>,
-    // pub directories: HashMap<DirectoriesActions, Vec<Key>>,
+    #[serde(default)]
+    pub directories: HashMap<Key, DirectoriesActionsFile>,
     // pub playlists: HashMap<PlaylistsActions, Vec<Key>>,
     // pub search: HashMap<SearchActions, Vec<Key>>,
     #[cfg(debug_assertions)]
@@ -60,6 +64,8 @@ pub struct KeyConfigFile {
     pub logs: HashMap<Key, LogsActionsFile>,
     #[serde(default)]
     pub queue: HashMap<Key, QueueActionsFile>,
+    #[serde(default)]
+    pub lyrics: HashMap<Key, LyricsActionsFile>,
 }
 
 impl Default for KeyConfigFile {
@@ -77,6 +83,7 @@ impl Default for KeyConfigFile {
         #[cfg(debug_assertions)]
         use LogsActionsFile as L;
         use QueueActionsFile as Q;
+        use LyricsActionsFile as Ly;
         Self {
             global: HashMap::from([
                 (Key { key: K::Char('q'), modifiers: M::NONE  }, G::Quit),
@@ -85,6 +92,9 @@ impl Default for KeyConfigFile {
                 (Key { key: K::Char('I'), modifiers: M::SHIFT }, G::ShowCurrentSongInfo),
                 (Key { key: K::Char('O'), modifiers: M::SHIFT }, G::ShowOutputs),
                 (Key { key: K::Char('P'), modifiers: M::SHIFT }, G::ShowDecoders),
+                (Key { key: K::Char('M'), modifiers: M::SHIFT }, G::ShowStorage),
+                (Key { key: K::Char('L'), modifiers: M::SHIFT }, G::ShowNotifications),
+                (Key { key: K::Char('T'), modifiers: M::SHIFT }, G::CycleLyricsTranslation),
                 (Key { key: K::Char('>'), modifiers: M::NONE  }, G::NextTrack),
                 (Key { key: K::Char('<'), modifiers: M::NONE  }, G::PreviousTrack),
                 (Key { key: K::Char('s'), modifiers: M::NONE  }, G::Stop),
@@ -106,6 +116,10 @@ impl Default for KeyConfigFile {
                 (Key { key: K::Char('5'), modifiers: M::NONE  }, G::SwitchToTab("Albums".to_string())),
                 (Key { key: K::Char('6'), modifiers: M::NONE  }, G::SwitchToTab("Playlists".to_string())),
                 (Key { key: K::Char('7'), modifiers: M::NONE  }, G::SwitchToTab("Search".to_string())),
+                (Key { key: K::Char('u'), modifiers: M::NONE  }, G::UpdateDatabase),
+                (Key { key: K::Char('U'), modifiers: M::SHIFT }, G::RescanDatabase),
+                (Key { key: K::Char('R'), modifiers: M::SHIFT }, G::ReloadConfig),
+                (Key { key: K::Char('X'), modifiers: M::SHIFT }, G::SwitchServer),
             ]),
             navigation: HashMap::from([
                 (Key { key: K::Char('k'), modifiers: M::NONE    }, C::Up),
@@ -144,8 +158,10 @@ impl Default for KeyConfigFile {
             // ]),
             // artists: HashMap::from([
             // ]),
-            // directories: HashMap::from([
-            // ]),
+            directories: HashMap::from([
+                (Key { key: K::Char('u'), modifiers: M::NONE    }, D::Update),
+                (Key { key: K::Char('U'), modifiers: M::SHIFT   }, D::Rescan),
+            ]),
             // playlists: HashMap::from([
             // ]),
             #[cfg(debug_assertions)]
@@ -162,6 +178,12 @@ impl Default for KeyConfigFile {
                 (Key { key: K::Char('i'), modifiers: M::NONE    }, Q::ShowInfo),
                 (Key { key: K::Char('C'), modifiers: M::SHIFT   }, Q::JumpToCurrent),
             ]),
+            lyrics: HashMap::from([
+                (Key { key: K::Char('+'), modifiers: M::NONE    }, Ly::IncreaseOffset),
+                (Key { key: K::Char('-'), modifiers: M::NONE    }, Ly::DecreaseOffset),
+                (Key { key: K::Char('S'), modifiers: M::SHIFT   }, Ly::ToggleSync),
+                (Key { key: K::Char('t'), modifiers: M::NONE    }, Ly::SyncLine),
+            ]),
         }
     }
 }
@@ -173,16 +195,16 @@ impl From<KeyConfigFile> for KeyConfig {
             navigation: value.navigation.into_iter().map(|(k, v)| (k, v.into())).collect(),
             // albums: invert_map(value.albums),
             // artists: invert_map(value.artists),
-            // directories: invert_map(value.directories),
             // playlists: invert_map(value.playlists),
             albums: HashMap::new(),
             artists: HashMap::new(),
-            directories: HashMap::new(),
+            directories: value.directories.into_iter().map(|(k, v)| (k, v.into())).collect(),
             playlists: HashMap::new(),
             search: HashMap::new(),
             #[cfg(debug_assertions)]
             logs: value.logs.into_iter().map(|(k, v)| (k, v.into())).collect(),
             queue: value.queue.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            lyrics: value.lyrics.into_iter().map(|(k, v)| (k, v.into())).collect(),
         }
     }
 }
@@ -210,9 +232,17 @@ mod tests {
     use crate::config::keys::LogsActionsFile;
     use crate::config::keys::{
         CommonAction,
+        DirectoriesActions,
         GlobalAction,
+        LyricsActions,
         QueueActions,
-        actions::{CommonActionFile, GlobalActionFile, QueueActionsFile},
+        actions::{
+            CommonActionFile,
+            DirectoriesActionsFile,
+            GlobalActionFile,
+            LyricsActionsFile,
+            QueueActionsFile,
+        },
     };
 
     #[test]
@@ -225,9 +255,10 @@ mod tests {
             logs: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, LogsActionsFile::Clear)]),
             queue: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, QueueActionsFile::Play),
                                   (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT, }, QueueActionsFile::Save)]),
+            lyrics: HashMap::from([(Key { key: KeyCode::Char('t'), modifiers: KeyModifiers::NONE, }, LyricsActionsFile::SyncLine)]),
+            directories: HashMap::from([(Key { key: KeyCode::Char('u'), modifiers: KeyModifiers::NONE, }, DirectoriesActionsFile::Update)]),
             // albums: HashMap::from([]),
             // artists: HashMap::from([]),
-            // directories: HashMap::from([]),
             // playlists: HashMap::from([]),
             navigation: HashMap::from([
                 (Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, CommonActionFile::Up),
@@ -240,9 +271,10 @@ mod tests {
             logs: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, LogsActions::Clear)]),
             queue: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, QueueActions::Play),
                                   (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT, }, QueueActions::Save)]),
+            lyrics: HashMap::from([(Key { key: KeyCode::Char('t'), modifiers: KeyModifiers::NONE, }, LyricsActions::SyncLine)]),
             albums: HashMap::from([]),
             artists: HashMap::from([]),
-            directories: HashMap::from([]),
+            directories: HashMap::from([(Key { key: KeyCode::Char('u'), modifiers: KeyModifiers::NONE, }, DirectoriesActions::Update)]),
             playlists: HashMap::from([]),
             search: HashMap::from([]),
             navigation: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL }, CommonAction::Up),
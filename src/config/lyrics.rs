@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct LyricsConfigFile {
+    #[serde(default)]
+    pub fetch: bool,
+    #[serde(default = "super::defaults::default_lyrics_fetch_url")]
+    pub fetch_url: String,
+    #[serde(default = "super::defaults::lyrics_not_found_max_age_days")]
+    pub not_found_max_age_days: u32,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct LyricsConfig {
+    /// Fetch lyrics for songs without a lrc file from an LRCLIB compatible
+    /// API
+    pub fetch: bool,
+    pub fetch_url: &'static str,
+    /// Songs the API had no lyrics for are searched again once this much
+    /// time passed, never if none
+    pub not_found_max_age: Option<Duration>,
+}
+
+impl Default for LyricsConfigFile {
+    fn default() -> Self {
+        Self {
+            fetch: false,
+            fetch_url: super::defaults::default_lyrics_fetch_url(),
+            not_found_max_age_days: super::defaults::lyrics_not_found_max_age_days(),
+        }
+    }
+}
+
+impl From<LyricsConfigFile> for LyricsConfig {
+    fn from(value: LyricsConfigFile) -> Self {
+        Self {
+            fetch: value.fetch,
+            fetch_url: value.fetch_url.leak(),
+            not_found_max_age: (value.not_found_max_age_days > 0).then(|| {
+                Duration::from_secs(u64::from(value.not_found_max_age_days) * 24 * 60 * 60)
+            }),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    hash::{DefaultHasher, Hash, Hasher},
     path::{Path, PathBuf},
     str::FromStr,
     time::Duration,
@@ -9,8 +10,11 @@ use album_art::{AlbumArtConfig, AlbumArtConfigFile, ImageMethod, ImageMethodFile
 use anyhow::{Context, Result};
 use artists::{Artists, ArtistsFile};
 use clap::Parser;
-use cli::{Args, OnOff, OnOffOneshot};
+use cli::{Args, FilterKind, OnOff, OnOffOneshot, SaveMode};
 use itertools::Itertools;
+use lyrics::{LyricsConfig, LyricsConfigFile};
+use notifications::{NotificationsConfig, NotificationsConfigFile};
+use profiles::{Profile, ProfileFile};
 use rustix::path::Arg;
 use search::SearchFile;
 use serde::{Deserialize, Serialize};
@@ -24,12 +28,16 @@ pub mod cli;
 pub mod cli_config;
 mod defaults;
 pub mod keys;
+pub mod lyrics;
+pub mod notifications;
+pub mod profiles;
 mod search;
 pub mod tabs;
 pub mod theme;
+pub mod validate;
 
 pub use address::MpdAddress;
-pub use search::Search;
+pub use search::{SavedSearch, Search, SearchQuery};
 
 use self::{
     keys::{KeyConfig, KeyConfigFile},
@@ -41,31 +49,44 @@ use crate::{
 };
 
 #[derive(Debug, Default, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Config {
     pub address: MpdAddress<'static>,
     pub password: Option<MpdPassword<'static>>,
     pub cache_dir: Option<&'static str>,
     pub lyrics_dir: Option<&'static str>,
+    pub profiles: &'static [Profile],
+    /// Name of the profile whose MPD server rmpc is connected to, [`None`]
+    /// when the top level address is used
+    pub active_profile: Option<&'static str>,
     pub volume_step: u8,
     pub max_fps: u32,
     pub scrolloff: usize,
     pub wrap_navigation: bool,
     pub keybinds: KeyConfig,
     pub enable_mouse: bool,
+    pub watch_config: bool,
     pub status_update_interval_ms: Option<u64>,
     pub select_current_song_on_change: bool,
     pub mpd_read_timeout: Duration,
     pub mpd_write_timeout: Duration,
     pub theme: UiConfig,
     pub album_art: AlbumArtConfig,
+    pub lyrics: LyricsConfig,
+    pub notifications: NotificationsConfig,
     pub on_song_change: Option<&'static [&'static str]>,
+    pub tag_editor: Option<&'static [&'static str]>,
     pub search: Search,
     pub artists: Artists,
     pub tabs: Tabs,
     pub active_panes: &'static [PaneTypeDiscriminants],
+    /// Hash of the config and theme files this config was created from, used
+    /// to skip reloads which would not change anything
+    pub file_hash: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ConfigFile {
     #[serde(default = "defaults::mpd_address")]
     pub address: String,
@@ -76,6 +97,8 @@ pub struct ConfigFile {
     #[serde(default)]
     lyrics_dir: Option<String>,
     #[serde(default)]
+    profiles: Vec<ProfileFile>,
+    #[serde(default)]
     pub theme: Option<String>,
     #[serde(default = "defaults::default_volume_step")]
     volume_step: u8,
@@ -95,6 +118,8 @@ pub struct ConfigFile {
     mpd_write_timeout_ms: u64,
     #[serde(default = "defaults::default_false")]
     enable_mouse: bool,
+    #[serde(default = "defaults::default_false")]
+    watch_config: bool,
     #[serde(default)]
     keybinds: KeyConfigFile,
     #[serde(default)]
@@ -104,13 +129,22 @@ pub struct ConfigFile {
     #[serde(default)]
     pub album_art: AlbumArtConfigFile,
     #[serde(default)]
+    lyrics: LyricsConfigFile,
+    #[serde(default)]
+    notifications: NotificationsConfigFile,
+    #[serde(default)]
     on_song_change: Option<Vec<String>>,
     #[serde(default)]
+    tag_editor: Option<Vec<String>>,
+    #[serde(default)]
     search: SearchFile,
     #[serde(default)]
     artists: ArtistsFile,
     #[serde(default)]
     tabs: TabsFile,
+    /// Hash of the file this config was read from
+    #[serde(skip)]
+    source_hash: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
@@ -139,29 +173,38 @@ impl Default for ConfigFile {
             theme: None,
             cache_dir: None,
             lyrics_dir: None,
+            profiles: Vec::new(),
             image_method: None,
             select_current_song_on_change: false,
             album_art_max_size_px: Size::default(),
             album_art: AlbumArtConfigFile {
                 disabled_protocols: defaults::disabled_album_art_protos(),
+                cover_names: defaults::album_art_cover_names(),
+                browser_preview: true,
+                cache_max_age_days: defaults::album_art_cache_max_age_days(),
                 ..Default::default()
             },
+            lyrics: LyricsConfigFile::default(),
+            notifications: NotificationsConfigFile::default(),
             on_song_change: None,
+            tag_editor: None,
             search: SearchFile::default(),
             tabs: TabsFile::default(),
             enable_mouse: true,
+            watch_config: false,
             wrap_navigation: false,
             password: None,
             artists: ArtistsFile::default(),
+            source_hash: None,
         }
     }
 }
 
 impl ConfigFile {
-    pub fn read(path: &PathBuf) -> Result<Self> {
-        let file = std::fs::File::open(path)?;
-        let read = std::io::BufReader::new(file);
-        let config: ConfigFile = ron::de::from_reader(read)?;
+    pub fn read(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)?;
+        let mut config: ConfigFile = ron::de::from_str(&source)?;
+        config.source_hash = Some(hash_source(&source));
 
         Ok(config)
     }
@@ -172,18 +215,14 @@ impl ConfigFile {
         })
     }
 
-    fn read_theme(&self, config_dir: &Path) -> Result<UiConfigFile> {
-        self.theme_path(config_dir).map_or_else(
-            || Ok(UiConfigFile::default()),
-            |path| {
-                let file = std::fs::File::open(&path).with_context(|| {
+    fn read_theme(&self, config_dir: &Path) -> Result<Option<String>> {
+        self.theme_path(config_dir)
+            .map(|path| {
+                std::fs::read_to_string(&path).with_context(|| {
                     format!("Failed to open theme file {:?}", path.to_string_lossy())
-                })?;
-                let read = std::io::BufReader::new(file);
-                let theme: UiConfigFile = ron::de::from_reader(read)?;
-                Ok(theme)
-            },
-        )
+                })
+            })
+            .transpose()
     }
 
     pub fn into_config(
@@ -193,9 +232,25 @@ impl ConfigFile {
         password_cli: Option<String>,
         is_cli: bool,
     ) -> Result<Config> {
-        let theme: UiConfig = config_path
+        let theme_source = config_path
             .map(|d| self.read_theme(d.parent().expect("Config path to be defined correctly")))
             .transpose()?
+            .flatten();
+
+        self.into_config_with_theme(theme_source.as_deref(), address_cli, password_cli, is_cli)
+    }
+
+    fn into_config_with_theme(
+        self,
+        theme_source: Option<&str>,
+        address_cli: Option<String>,
+        password_cli: Option<String>,
+        is_cli: bool,
+    ) -> Result<Config> {
+        let file_hash = self.source_hash.map(|hash| file_hash(hash, theme_source));
+        let theme: UiConfig = theme_source
+            .map(ron::de::from_str::<UiConfigFile>)
+            .transpose()?
             .unwrap_or_default()
             .try_into()?;
 
@@ -223,6 +278,8 @@ impl ConfigFile {
                 let v = tilde_expand(&v);
                 if v.ends_with('/') { v.into_owned() } else { format!("{v}/") }.leak() as &'static _
             }),
+            profiles: self.profiles.into_iter().map(Profile::from).collect_vec().leak(),
+            active_profile: None,
             tabs,
             active_panes,
             address,
@@ -235,17 +292,27 @@ impl ConfigFile {
             mpd_read_timeout: Duration::from_millis(self.mpd_read_timeout_ms),
             mpd_write_timeout: Duration::from_millis(self.mpd_write_timeout_ms),
             enable_mouse: self.enable_mouse,
+            watch_config: self.watch_config,
             keybinds: self.keybinds.into(),
             select_current_song_on_change: self.select_current_song_on_change,
             search: self.search.into(),
             artists: self.artists.into(),
             album_art: self.album_art.into(),
+            lyrics: self.lyrics.into(),
+            notifications: self.notifications.into(),
             on_song_change: self.on_song_change.map(|arr| {
                 arr.into_iter()
                     .map(|v| tilde_expand(&v).into_owned().leak() as &'static str)
                     .collect_vec()
                     .leak() as &'static [_]
             }),
+            tag_editor: self.tag_editor.map(|arr| {
+                arr.into_iter()
+                    .map(|v| tilde_expand(&v).into_owned().leak() as &'static str)
+                    .collect_vec()
+                    .leak() as &'static [_]
+            }),
+            file_hash,
         };
 
         if is_cli {
@@ -271,6 +338,7 @@ impl ConfigFile {
             }
             ImageMethodFile::UeberzugX11 => ImageMethod::Unsupported,
             ImageMethodFile::Sixel => ImageMethod::Sixel,
+            ImageMethodFile::Block => ImageMethod::Block,
             ImageMethodFile::None => ImageMethod::None,
             ImageMethodFile::Auto => match image::determine_image_support(is_tmux)? {
                 ImageProtocol::Kitty => ImageMethod::Kitty,
@@ -278,7 +346,7 @@ impl ConfigFile {
                 ImageProtocol::UeberzugX11 => ImageMethod::UeberzugX11,
                 ImageProtocol::Iterm2 => ImageMethod::Iterm2,
                 ImageProtocol::Sixel => ImageMethod::Sixel,
-                ImageProtocol::None => ImageMethod::Unsupported,
+                ImageProtocol::Block | ImageProtocol::None => ImageMethod::Block,
             },
         };
 
@@ -293,7 +361,8 @@ impl ConfigFile {
             | ImageMethod::UeberzugWayland
             | ImageMethod::UeberzugX11
             | ImageMethod::Iterm2
-            | ImageMethod::Sixel => {
+            | ImageMethod::Sixel
+            | ImageMethod::Block => {
                 log::debug!(resolved:? = config.album_art.method, requested:? = album_art_method, is_tmux; "Image method resolved");
             }
         }
@@ -302,6 +371,68 @@ impl ConfigFile {
     }
 }
 
+impl Config {
+    /// Reads the config file and its theme again for the running instance.
+    /// Values which cannot change without a restart, like the MPD address or
+    /// the image method, are kept from the current config. Returns [`None`]
+    /// if neither of the files changed.
+    pub fn reload(&self, config_path: &Path) -> Result<Option<Config>> {
+        let file = ConfigFile::read(config_path)
+            .with_context(|| format!("Failed to read config file '{}'", config_path.display()))?;
+        let config_dir = config_path.parent().expect("Config path to be defined correctly");
+        let theme_source = file.read_theme(config_dir)?;
+        let file_hash = file.source_hash.map(|hash| file_hash(hash, theme_source.as_deref()));
+        if file_hash.is_some() && self.file_hash == file_hash {
+            return Ok(None);
+        }
+
+        let mut config = file.into_config_with_theme(theme_source.as_deref(), None, None, true)?;
+        validate_tabs(&config.theme.layout, &config.tabs)?;
+
+        config.address = self.address;
+        config.password = self.password;
+        config.cache_dir = self.cache_dir;
+        config.lyrics_dir = self.lyrics_dir;
+        config.active_profile = self.active_profile;
+        config.mpd_read_timeout = self.mpd_read_timeout;
+        config.mpd_write_timeout = self.mpd_write_timeout;
+        config.enable_mouse = self.enable_mouse;
+        config.watch_config = self.watch_config;
+        config.album_art.method = self.album_art.method;
+
+        Ok(Some(config))
+    }
+
+    /// Connects to the MPD server of the given profile instead of the current
+    /// one. Cache and lyrics directories not set in the profile are kept.
+    pub fn with_profile(mut self, name: &str) -> Result<Config> {
+        let profile = Profile::find(self.profiles, name)?;
+
+        self.address = profile.address;
+        self.password = profile.password;
+        self.cache_dir = profile.cache_dir.or(self.cache_dir);
+        self.lyrics_dir = profile.lyrics_dir.or(self.lyrics_dir);
+        self.active_profile = Some(profile.name);
+
+        Ok(self)
+    }
+}
+
+/// Configs are leaked for the lifetime of rmpc, so the files are hashed to
+/// avoid leaking a new one when nothing has changed
+fn file_hash(config_hash: u64, theme_source: Option<&str>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    config_hash.hash(&mut hasher);
+    theme_source.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn hash_source(source: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
 impl FromStr for Args {
     type Err = anyhow::Error;
 
@@ -329,6 +460,27 @@ impl From<OnOffOneshot> for crate::mpd::commands::status::OnOffOneshot {
     }
 }
 
+impl From<FilterKind> for crate::mpd::mpd_client::FilterKind {
+    fn from(value: FilterKind) -> Self {
+        match value {
+            FilterKind::Exact => crate::mpd::mpd_client::FilterKind::Exact,
+            FilterKind::Contains => crate::mpd::mpd_client::FilterKind::Contains,
+            FilterKind::StartsWith => crate::mpd::mpd_client::FilterKind::StartsWith,
+            FilterKind::Regex => crate::mpd::mpd_client::FilterKind::Regex,
+        }
+    }
+}
+
+impl From<SaveMode> for crate::mpd::mpd_client::SaveMode {
+    fn from(value: SaveMode) -> Self {
+        match value {
+            SaveMode::Create => crate::mpd::mpd_client::SaveMode::Create,
+            SaveMode::Append => crate::mpd::mpd_client::SaveMode::Append,
+            SaveMode::Replace => crate::mpd::mpd_client::SaveMode::Replace,
+        }
+    }
+}
+
 pub trait Leak {
     fn leak(self) -> &'static Self;
 }
@@ -408,11 +560,64 @@ pub mod utils {
 #[allow(clippy::unwrap_used)]
 mod tests {
 
+    use rstest::rstest;
     use walkdir::WalkDir;
 
     #[cfg(debug_assertions)]
     use crate::config::keys::KeyConfigFile;
-    use crate::config::{ConfigFile, theme::UiConfigFile};
+    use crate::config::{
+        ConfigFile,
+        MpdAddress,
+        address::MpdPassword,
+        cli::{Args, Command, FilterKind, QueueCmd},
+        profiles::ProfileFile,
+        theme::UiConfigFile,
+    };
+    use crate::tests::fixtures::{TempDir, temp_dir};
+
+    #[rstest]
+    fn reload_keeps_values_requiring_restart(#[from(temp_dir)] dir: TempDir) {
+        let path = dir.join("config.ron");
+        let current = ConfigFile::default()
+            .into_config(None, Some("127.0.0.1:6601".to_owned()), None, true)
+            .unwrap();
+
+        std::fs::write(&path, "(volume_step: 10, address: \"127.0.0.1:7000\")").unwrap();
+        let reloaded = current.reload(&path).unwrap().unwrap();
+        let unchanged = reloaded.reload(&path).unwrap();
+        std::fs::write(&path, "(volume_step: ").unwrap();
+        let invalid = current.reload(&path);
+
+        assert_eq!(reloaded.volume_step, 10);
+        assert!(unchanged.is_none());
+        assert_eq!(reloaded.address, current.address);
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn profile_overrides_address_and_keeps_unset_dirs() {
+        let file = ConfigFile {
+            lyrics_dir: Some("/lyrics".to_owned()),
+            profiles: vec![ProfileFile {
+                name: "nas".to_owned(),
+                address: "192.168.1.10:6600".to_owned(),
+                password: Some("secret".to_owned()),
+                cache_dir: Some("/cache/nas".to_owned()),
+                lyrics_dir: None,
+            }],
+            ..ConfigFile::default()
+        };
+        let config = file.into_config(None, None, None, true).unwrap();
+
+        let switched = config.clone().with_profile("nas").unwrap();
+
+        assert_eq!(switched.address, MpdAddress::IpAndPort("192.168.1.10:6600"));
+        assert_eq!(switched.password, Some(MpdPassword("secret")));
+        assert_eq!(switched.cache_dir, Some("/cache/nas/"));
+        assert_eq!(switched.lyrics_dir, Some("/lyrics/"));
+        assert_eq!(switched.active_profile, Some("nas"));
+        assert!(config.with_profile("car").is_err());
+    }
 
     #[test]
     #[cfg(debug_assertions)]
@@ -473,4 +678,55 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn parses_cli_filters() {
+        let args: Args = "search artist=Queen title=a=b --kind starts-with".parse().unwrap();
+
+        assert_eq!(
+            args.command,
+            Some(Command::Search {
+                filters: vec![
+                    ("artist".to_owned(), "Queen".to_owned()),
+                    ("title".to_owned(), "a=b".to_owned())
+                ],
+                kind: FilterKind::StartsWith,
+                format: None,
+                add: false,
+                play: false,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_cli_filter() {
+        assert!("find artist".parse::<Args>().is_err());
+        assert!("find =Queen".parse::<Args>().is_err());
+    }
+
+    #[test]
+    fn parses_cli_queue_move_with_relative_target() {
+        let args: Args = "queue move 1:3 -0".parse().unwrap();
+
+        assert_eq!(
+            args.command,
+            Some(Command::Queue {
+                format: None,
+                cmd: Some(QueueCmd::Move { range: "1:3".to_owned(), to: "-0".to_owned() })
+            })
+        );
+    }
+
+    #[test]
+    fn parses_cli_watch_events() {
+        let args: Args = "watch --events player,mixer -e stored_playlist".parse().unwrap();
+
+        assert_eq!(
+            args.command,
+            Some(Command::Watch {
+                events: vec!["player".to_owned(), "mixer".to_owned(), "stored_playlist".to_owned()],
+                format: None,
+            })
+        );
+    }
 }
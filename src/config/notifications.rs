@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::utils::tilde_expand;
+use crate::shared::events::Level;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct NotificationsConfigFile {
+    #[serde(default = "super::defaults::default_info_timeout_ms")]
+    pub info_timeout_ms: u64,
+    #[serde(default = "super::defaults::default_warn_timeout_ms")]
+    pub warn_timeout_ms: u64,
+    #[serde(default = "super::defaults::default_error_timeout_ms")]
+    pub error_timeout_ms: u64,
+    #[serde(default = "super::defaults::default_notification_history_size")]
+    pub history_size: usize,
+    #[serde(default)]
+    pub desktop_command: Option<Vec<String>>,
+    #[serde(default = "super::defaults::default_desktop_notification_levels")]
+    pub desktop_levels: Vec<Level>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NotificationsConfig {
+    pub info_timeout: Duration,
+    pub warn_timeout: Duration,
+    pub error_timeout: Duration,
+    /// Maximum number of messages kept in the notification history
+    pub history_size: usize,
+    /// Command executed for messages of `desktop_levels`, for example
+    /// `notify-send`. The message is passed in the `MESSAGE` and `LEVEL`
+    /// environment variables.
+    pub desktop_command: Option<&'static [&'static str]>,
+    pub desktop_levels: &'static [Level],
+}
+
+impl NotificationsConfig {
+    /// How long a message of the given level stays in the status bar
+    pub fn timeout(&self, level: Level) -> Duration {
+        match level {
+            Level::Error => self.error_timeout,
+            Level::Warn => self.warn_timeout,
+            Level::Info | Level::Debug | Level::Trace => self.info_timeout,
+        }
+    }
+}
+
+impl Default for NotificationsConfigFile {
+    fn default() -> Self {
+        Self {
+            info_timeout_ms: super::defaults::default_info_timeout_ms(),
+            warn_timeout_ms: super::defaults::default_warn_timeout_ms(),
+            error_timeout_ms: super::defaults::default_error_timeout_ms(),
+            history_size: super::defaults::default_notification_history_size(),
+            desktop_command: None,
+            desktop_levels: super::defaults::default_desktop_notification_levels(),
+        }
+    }
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        NotificationsConfigFile::default().into()
+    }
+}
+
+impl From<NotificationsConfigFile> for NotificationsConfig {
+    fn from(value: NotificationsConfigFile) -> Self {
+        Self {
+            info_timeout: Duration::from_millis(value.info_timeout_ms),
+            warn_timeout: Duration::from_millis(value.warn_timeout_ms),
+            error_timeout: Duration::from_millis(value.error_timeout_ms),
+            history_size: value.history_size,
+            desktop_command: value.desktop_command.map(|arr| {
+                arr.into_iter()
+                    .map(|v| tilde_expand(&v).into_owned().leak() as &'static str)
+                    .collect_vec()
+                    .leak() as &'static [_]
+            }),
+            desktop_levels: value.desktop_levels.leak(),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use anyhow::{Result, anyhow};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::{MpdAddress, address::MpdPassword, utils::tilde_expand};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProfileFile {
+    pub name: String,
+    #[serde(default = "super::defaults::mpd_address")]
+    pub address: String,
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default)]
+    pub cache_dir: Option<String>,
+    #[serde(default)]
+    pub lyrics_dir: Option<String>,
+}
+
+/// Named MPD server rmpc can connect to. Cache and lyrics directories fall
+/// back to the top level values of the config when not set.
+#[derive(Debug, Clone)]
+pub struct Profile {
+    pub name: &'static str,
+    pub address: MpdAddress<'static>,
+    pub password: Option<MpdPassword<'static>>,
+    pub cache_dir: Option<&'static str>,
+    pub lyrics_dir: Option<&'static str>,
+}
+
+impl Profile {
+    pub fn find<'a>(profiles: &'a [Profile], name: &str) -> Result<&'a Profile> {
+        profiles.iter().find(|profile| profile.name == name).ok_or_else(|| {
+            if profiles.is_empty() {
+                anyhow!("Profile '{name}' not found, no profiles are configured")
+            } else {
+                anyhow!(
+                    "Profile '{name}' not found. Available profiles: {}",
+                    profiles.iter().map(|profile| profile.name).join(", ")
+                )
+            }
+        })
+    }
+}
+
+impl From<ProfileFile> for Profile {
+    fn from(value: ProfileFile) -> Self {
+        let (address, password) = MpdAddress::resolve_config(value.address, value.password);
+
+        Self {
+            name: value.name.leak(),
+            address,
+            password,
+            cache_dir: value
+                .cache_dir
+                .map(|v| if v.ends_with('/') { v } else { format!("{v}/") }.leak() as &'static _),
+            lyrics_dir: value.lyrics_dir.map(|v| {
+                let v = tilde_expand(&v);
+                if v.ends_with('/') { v.into_owned() } else { format!("{v}/") }.leak() as &'static _
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Profile, ProfileFile};
+    use crate::config::MpdAddress;
+
+    fn profiles() -> Vec<Profile> {
+        ["desktop", "nas"]
+            .into_iter()
+            .map(|name| {
+                ProfileFile {
+                    name: name.to_owned(),
+                    address: format!("{name}:6600"),
+                    password: None,
+                    cache_dir: Some("/tmp/cache".to_owned()),
+                    lyrics_dir: None,
+                }
+                .into()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_profile_by_name() {
+        let profiles = profiles();
+
+        let profile = Profile::find(&profiles, "nas").expect("profile to exist");
+
+        assert_eq!(profile.address, MpdAddress::IpAndPort("nas:6600"));
+        assert_eq!(profile.cache_dir, Some("/tmp/cache/"));
+    }
+
+    #[test]
+    fn lists_available_profiles_when_not_found() {
+        let err = Profile::find(&profiles(), "car").expect_err("profile not to exist");
+
+        assert_eq!(err.to_string(), "Profile 'car' not found. Available profiles: desktop, nas");
+    }
+}
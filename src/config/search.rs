@@ -1,6 +1,9 @@
+use std::collections::BTreeMap;
+
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
+use super::defaults;
 use crate::mpd::mpd_client::FilterKind;
 
 #[derive(Debug, Default, Clone)]
@@ -8,6 +11,8 @@ pub struct Search {
     pub case_sensitive: bool,
     pub mode: FilterKind,
     pub tags: &'static [SearchableTag],
+    pub history_size: usize,
+    pub saved: &'static [SavedSearch],
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -15,6 +20,36 @@ pub struct SearchFile {
     case_sensitive: bool,
     mode: FilterKindFile,
     tags: Vec<SearchableTagFile>,
+    #[serde(default = "defaults::default_search_history_size")]
+    history_size: usize,
+    #[serde(default)]
+    saved: Vec<SavedSearchFile>,
+}
+
+/// Values of all of the search pane's inputs
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Searched value for each tag, tags with empty value are omitted
+    pub tags: BTreeMap<String, String>,
+    pub mode: FilterKind,
+    pub case_sensitive: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: SearchQuery,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SavedSearchFile {
+    name: String,
+    #[serde(default)]
+    tags: BTreeMap<String, String>,
+    #[serde(default)]
+    mode: Option<FilterKindFile>,
+    #[serde(default)]
+    case_sensitive: Option<bool>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -31,9 +66,24 @@ pub struct SearchableTagFile {
 
 impl From<SearchFile> for Search {
     fn from(value: SearchFile) -> Self {
+        let mode: FilterKind = value.mode.into();
         Self {
             case_sensitive: value.case_sensitive,
-            mode: value.mode.into(),
+            mode,
+            history_size: value.history_size,
+            saved: value
+                .saved
+                .into_iter()
+                .map(|saved| SavedSearch {
+                    name: saved.name,
+                    query: SearchQuery {
+                        tags: saved.tags,
+                        mode: saved.mode.map_or(mode, Into::into),
+                        case_sensitive: saved.case_sensitive.unwrap_or(value.case_sensitive),
+                    },
+                })
+                .collect_vec()
+                .leak(),
             tags: if value.tags.is_empty() {
                 vec![SearchableTag { label: "Any Tag", value: "any" }]
             } else {
@@ -69,6 +119,8 @@ impl Default for SearchFile {
                 SearchableTagFile { value: "genre".to_string(), label: "Genre".to_string() },
             ]
             .to_vec(),
+            history_size: defaults::default_search_history_size(),
+            saved: Vec::new(),
         }
     }
 }
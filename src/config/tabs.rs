@@ -1,7 +1,7 @@
 #![allow(deprecated)] // TODO remove after cleanup
 use std::collections::HashMap;
 
-use anyhow::{Result, ensure};
+use anyhow::{Result, anyhow, ensure};
 use derive_more::{Deref, Display, Into};
 use itertools::Itertools;
 use ratatui::{layout::Direction, widgets::Borders};
@@ -38,10 +38,12 @@ pub enum PaneTypeFile {
     Artists,
     Albums,
     AlbumArtists,
+    AlbumGrid,
     Playlists,
     Search,
     AlbumArt,
     Lyrics,
+    Stats,
     ProgressBar,
     Header,
     Tabs,
@@ -65,10 +67,12 @@ pub enum PaneType {
     Artists,
     AlbumArtists,
     Albums,
+    AlbumGrid,
     Playlists,
     Search,
     AlbumArt,
     Lyrics,
+    Stats,
     ProgressBar,
     Header,
     Tabs,
@@ -85,9 +89,8 @@ pub const PANES_ALLOWED_IN_BOTH_TAB_AND_LAYOUT: [PaneTypeDiscriminants; 1] =
     [PaneTypeDiscriminants::Property];
 
 #[cfg(debug_assertions)]
-pub const UNFOSUSABLE_TABS: [PaneTypeDiscriminants; 8] = [
+pub const UNFOSUSABLE_TABS: [PaneTypeDiscriminants; 7] = [
     PaneTypeDiscriminants::AlbumArt,
-    PaneTypeDiscriminants::Lyrics,
     PaneTypeDiscriminants::ProgressBar,
     PaneTypeDiscriminants::Header,
     PaneTypeDiscriminants::Tabs,
@@ -97,9 +100,8 @@ pub const UNFOSUSABLE_TABS: [PaneTypeDiscriminants; 8] = [
 ];
 
 #[cfg(not(debug_assertions))]
-pub const UNFOSUSABLE_TABS: [PaneTypeDiscriminants; 7] = [
+pub const UNFOSUSABLE_TABS: [PaneTypeDiscriminants; 6] = [
     PaneTypeDiscriminants::AlbumArt,
-    PaneTypeDiscriminants::Lyrics,
     PaneTypeDiscriminants::ProgressBar,
     PaneTypeDiscriminants::Header,
     PaneTypeDiscriminants::Tabs,
@@ -113,9 +115,11 @@ impl Pane {
     }
 }
 
-impl From<&PaneTypeFile> for PaneType {
-    fn from(value: &PaneTypeFile) -> Self {
-        match value {
+impl TryFrom<&PaneTypeFile> for PaneType {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &PaneTypeFile) -> Result<Self> {
+        Ok(match value {
             PaneTypeFile::Queue => PaneType::Queue,
             #[cfg(debug_assertions)]
             PaneTypeFile::Logs => PaneType::Logs,
@@ -123,10 +127,12 @@ impl From<&PaneTypeFile> for PaneType {
             PaneTypeFile::Artists => PaneType::Artists,
             PaneTypeFile::AlbumArtists => PaneType::AlbumArtists,
             PaneTypeFile::Albums => PaneType::Albums,
+            PaneTypeFile::AlbumGrid => PaneType::AlbumGrid,
             PaneTypeFile::Playlists => PaneType::Playlists,
             PaneTypeFile::Search => PaneType::Search,
             PaneTypeFile::AlbumArt => PaneType::AlbumArt,
             PaneTypeFile::Lyrics => PaneType::Lyrics,
+            PaneTypeFile::Stats => PaneType::Stats,
             PaneTypeFile::ProgressBar => PaneType::ProgressBar,
             PaneTypeFile::Header => PaneType::Header,
             PaneTypeFile::Tabs => PaneType::Tabs,
@@ -136,12 +142,12 @@ impl From<&PaneTypeFile> for PaneType {
             PaneTypeFile::Property { content: properties, align } => PaneType::Property {
                 content: properties
                     .iter()
-                    .map(|prop| prop.try_into().expect(""))
-                    .collect_vec()
+                    .map(|prop| prop.try_into())
+                    .collect::<Result<Vec<_>>>()?
                     .leak(),
                 align: (*align).into(),
             },
-        }
+        })
     }
 }
 
@@ -179,6 +185,21 @@ pub enum BorderTypeFile {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub(super) struct TabsFile(Vec<TabFile>);
 
+impl TabsFile {
+    /// Converts every tab on its own and returns the name of each tab which
+    /// failed together with its error. [`None`] refers to the tabs as a whole.
+    pub(super) fn problems(&self) -> Vec<(Option<&str>, anyhow::Error)> {
+        if self.0.is_empty() {
+            return vec![(None, anyhow!("At least one tab is required"))];
+        }
+
+        self.0
+            .iter()
+            .filter_map(|tab| tab.pane.convert().err().map(|err| (Some(tab.name.as_str()), err)))
+            .collect()
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct Tabs {
     pub names: &'static [TabName],
@@ -322,7 +343,7 @@ impl PaneOrSplitFile {
     pub fn convert_recursive(&self, b: Borders) -> Result<SizedPaneOrSplit> {
         Ok(match self {
             PaneOrSplitFile::Pane(pane_type_file) => SizedPaneOrSplit::Pane(Pane {
-                pane: pane_type_file.into(),
+                pane: pane_type_file.try_into()?,
                 borders: b,
                 id: id::new(),
             }),
@@ -85,6 +85,28 @@ impl Default for HeaderConfigFile {
                             }),
                             default: None,
                         },
+                        PropertyFile {
+                            kind: PropertyKindFileOrText::Group(vec![
+                                PropertyFile {
+                                    kind: PropertyKindFileOrText::Text(" ".to_string()),
+                                    style: None,
+                                    default: None,
+                                },
+                                PropertyFile {
+                                    kind: PropertyKindFileOrText::Property(
+                                        PropertyKindFile::Status(StatusPropertyFile::UpdatingDb),
+                                    ),
+                                    style: Some(StyleFile {
+                                        fg: Some("blue".to_string()),
+                                        bg: None,
+                                        modifiers: None,
+                                    }),
+                                    default: None,
+                                },
+                            ]),
+                            style: None,
+                            default: None,
+                        },
                     ],
                     center: vec![PropertyFile {
                         kind: PropertyKindFileOrText::Property(PropertyKindFile::Song(
@@ -1,5 +1,5 @@
 use ::serde::{Deserialize, Serialize};
-use anyhow::Result;
+use anyhow::{Context, Result, anyhow};
 use properties::{SongFormat, SongFormatFile};
 use ratatui::style::{Color, Style};
 
@@ -17,6 +17,7 @@ pub mod properties;
 mod queue_table;
 mod scrollbar;
 mod style;
+pub mod template;
 
 pub use style::{ConfigColor, StyleFile};
 
@@ -38,6 +39,7 @@ pub struct UiConfig {
     pub borders_style: Style,
     pub highlighted_item_style: Style,
     pub current_item_style: Style,
+    pub lyrics_current_word_style: Style,
     pub highlight_border_style: Style,
     pub column_widths: [u16; 3],
     pub browser_song_format: SongFormat,
@@ -72,6 +74,7 @@ pub struct UiConfigFile {
     pub(super) borders_style: Option<StyleFile>,
     pub(super) highlighted_item_style: Option<StyleFile>,
     pub(super) current_item_style: Option<StyleFile>,
+    pub(super) lyrics_current_word_style: Option<StyleFile>,
     pub(super) highlight_border_style: Option<StyleFile>,
     pub(super) show_song_table_header: bool,
     pub(super) song_table_format: QueueTableColumnsFile,
@@ -108,6 +111,11 @@ impl Default for UiConfigFile {
                 bg: Some("blue".to_string()),
                 modifiers: Some(Modifiers::Bold),
             }),
+            lyrics_current_word_style: Some(StyleFile {
+                fg: Some("black".to_string()),
+                bg: Some("blue".to_string()),
+                modifiers: Some(Modifiers::Bold),
+            }),
             highlight_border_style: Some(StyleFile {
                 fg: Some("blue".to_string()),
                 bg: None,
@@ -182,58 +190,180 @@ impl From<SymbolsFile> for SymbolsConfig {
 impl TryFrom<UiConfigFile> for UiConfig {
     type Error = anyhow::Error;
 
-    #[allow(clippy::similar_names)]
     fn try_from(value: UiConfigFile) -> Result<Self, Self::Error> {
-        let bg_color = StringColor(value.background_color).to_color()?;
-        let header_bg_color = StringColor(value.header_background_color).to_color()?.or(bg_color);
+        value.convert().map_err(|problems| {
+            problems.into_iter().next().map_or_else(|| anyhow!("Invalid theme"), |(_, err)| err)
+        })
+    }
+}
+
+/// Widths of the previous, current and preview columns of the browsers
+fn column_widths(widths: &[u16]) -> Result<[u16; 3]> {
+    widths
+        .try_into()
+        .map_err(|_| anyhow!("Expected exactly 3 browser column widths, found {}", widths.len()))
+}
+
+/// Errors of the theme fields which failed to convert, fields of nested
+/// structs are reported as `parent.field`
+type FieldProblems = Vec<(&'static str, anyhow::Error)>;
+
+fn check<T>(problems: &mut FieldProblems, field: &'static str, result: Result<T>) -> Option<T> {
+    result.map_err(|err| problems.push((field, err))).ok()
+}
+
+impl UiConfigFile {
+    /// Returns the errors of all fields which failed to convert instead of
+    /// only the first one
+    pub(super) fn problems(self) -> FieldProblems {
+        self.convert().err().unwrap_or_default()
+    }
+
+    /// Converts every field on its own so the errors of all of them can be
+    /// reported at once
+    #[allow(clippy::similar_names)]
+    fn convert(self) -> Result<UiConfig, FieldProblems> {
+        let mut problems = Vec::new();
+        let p = &mut problems;
         let fallback_border_fg = Color::White;
 
-        Ok(Self {
-            layout: value.layout.convert()?,
-            background_color: bg_color,
-            draw_borders: value.draw_borders,
-            modal_background_color: StringColor(value.modal_background_color)
-                .to_color()?
-                .or(bg_color),
-            text_color: StringColor(value.text_color).to_color()?,
-            header_background_color: header_bg_color,
-            borders_style: value.borders_style.to_config_or(Some(fallback_border_fg), None)?,
-            highlighted_item_style: value
-                .highlighted_item_style
-                .to_config_or(Some(Color::Blue), None)?,
-            highlight_border_style: value
-                .highlight_border_style
-                .to_config_or(Some(Color::Blue), None)?,
-            symbols: value.symbols.into(),
-            show_song_table_header: value.show_song_table_header,
-            scrollbar: value.scrollbar.into_config(fallback_border_fg)?,
-            progress_bar: value.progress_bar.into_config()?,
-            song_table_format: TryInto::<QueueTableColumns>::try_into(value.song_table_format)?
-                .0
-                .leak(),
-            header: value.header.try_into()?,
-            column_widths: [
-                value.browser_column_widths[0],
-                value.browser_column_widths[1],
-                value.browser_column_widths[2],
-            ],
+        let bg_color = check(p, "background_color", StringColor(self.background_color).to_color());
+        let header_bg_color = check(
+            p,
+            "header_background_color",
+            StringColor(self.header_background_color).to_color(),
+        )
+        .map(|color| color.or(bg_color.flatten()));
+        let modal_bg_color =
+            check(p, "modal_background_color", StringColor(self.modal_background_color).to_color())
+                .map(|color| color.or(bg_color.flatten()));
+        let text_color = check(p, "text_color", StringColor(self.text_color).to_color());
+        let borders_style = check(
+            p,
+            "borders_style",
+            self.borders_style.to_config_or(Some(fallback_border_fg), None),
+        );
+        let highlighted_item_style = check(
+            p,
+            "highlighted_item_style",
+            self.highlighted_item_style.to_config_or(Some(Color::Blue), None),
+        );
+        let highlight_border_style = check(
+            p,
+            "highlight_border_style",
+            self.highlight_border_style.to_config_or(Some(Color::Blue), None),
+        );
+        let current_item_style = check(
+            p,
+            "current_item_style",
+            self.current_item_style.to_config_or(Some(Color::Black), Some(Color::Blue)),
+        );
+        let lyrics_current_word_style = check(
+            p,
+            "lyrics_current_word_style",
+            self.lyrics_current_word_style.to_config_or(Some(Color::Black), Some(Color::Blue)),
+        );
+        let tab_bar_active_style = check(
+            p,
+            "tab_bar.active_style",
+            self.tab_bar.active_style.to_config_or(Some(Color::Black), Some(Color::Blue)),
+        );
+        let tab_bar_inactive_style = check(
+            p,
+            "tab_bar.inactive_style",
+            self.tab_bar.inactive_style.to_config_or(None, header_bg_color.flatten()),
+        );
+        let layout = check(p, "layout", self.layout.convert());
+        let scrollbar = check(p, "scrollbar", self.scrollbar.into_config(fallback_border_fg));
+        let progress_bar = check(p, "progress_bar", self.progress_bar.into_config());
+        let song_table_format =
+            check(p, "song_table_format", QueueTableColumns::try_from(self.song_table_format));
+        let header = check(p, "header", HeaderConfig::try_from(self.header));
+        let browser_song_format =
+            check(p, "browser_song_format", SongFormat::try_from(self.browser_song_format));
+        let column_widths =
+            check(p, "browser_column_widths", column_widths(&self.browser_column_widths));
+        let default_album_art = check(
+            p,
+            "default_album_art_path",
+            self.default_album_art_path.map_or(Ok(DEFAULT_ART as &'static [u8]), |path| {
+                std::fs::read(&path)
+                    .map(|art| art.leak() as &'static [u8])
+                    .with_context(|| format!("Failed to read album art at '{path}'"))
+            }),
+        );
+
+        let (
+            Some(background_color),
+            Some(header_background_color),
+            Some(modal_background_color),
+            Some(text_color),
+            Some(borders_style),
+            Some(highlighted_item_style),
+            Some(highlight_border_style),
+            Some(current_item_style),
+            Some(lyrics_current_word_style),
+            Some(active_style),
+            Some(inactive_style),
+            Some(layout),
+            Some(scrollbar),
+            Some(progress_bar),
+            Some(song_table_format),
+            Some(header),
+            Some(browser_song_format),
+            Some(column_widths),
+            Some(default_album_art),
+        ) = (
+            bg_color,
+            header_bg_color,
+            modal_bg_color,
+            text_color,
+            borders_style,
+            highlighted_item_style,
+            highlight_border_style,
+            current_item_style,
+            lyrics_current_word_style,
+            tab_bar_active_style,
+            tab_bar_inactive_style,
+            layout,
+            scrollbar,
+            progress_bar,
+            song_table_format,
+            header,
+            browser_song_format,
+            column_widths,
+            default_album_art,
+        )
+        else {
+            return Err(problems);
+        };
+
+        Ok(UiConfig {
+            layout,
+            background_color,
+            draw_borders: self.draw_borders,
+            modal_background_color,
+            text_color,
+            header_background_color,
+            borders_style,
+            highlighted_item_style,
+            highlight_border_style,
+            symbols: self.symbols.into(),
+            show_song_table_header: self.show_song_table_header,
+            scrollbar,
+            progress_bar,
+            song_table_format: song_table_format.0.leak(),
+            header,
+            column_widths,
             tab_bar: TabBar {
-                enabled: value.tab_bar.enabled.unwrap_or(true),
-                active_style: value
-                    .tab_bar
-                    .active_style
-                    .to_config_or(Some(Color::Black), Some(Color::Blue))?,
-                inactive_style: value.tab_bar.inactive_style.to_config_or(None, header_bg_color)?,
+                enabled: self.tab_bar.enabled.unwrap_or(true),
+                active_style,
+                inactive_style,
             },
-            current_item_style: value
-                .current_item_style
-                .to_config_or(Some(Color::Black), Some(Color::Blue))?,
-            default_album_art: value
-                .default_album_art_path
-                .map_or(Ok(DEFAULT_ART as &'static [u8]), |path| -> Result<_> {
-                    Ok(std::fs::read(path)?.leak())
-                })?,
-            browser_song_format: TryInto::<SongFormat>::try_into(value.browser_song_format)?,
+            current_item_style,
+            lyrics_current_word_style,
+            default_album_art,
+            browser_song_format,
         })
     }
 }
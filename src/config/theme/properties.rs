@@ -106,6 +106,7 @@ pub enum StatusPropertyFile {
     Duration,
     Crossfade,
     Bitrate,
+    UpdatingDb,
 }
 
 #[derive(Debug, Clone, Display, Hash, Eq, PartialEq)]
@@ -151,6 +152,7 @@ pub enum StatusProperty {
     Duration,
     Crossfade,
     Bitrate,
+    UpdatingDb,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -297,6 +299,7 @@ impl TryFrom<StatusPropertyFile> for StatusProperty {
             StatusPropertyFile::Volume => StatusProperty::Volume,
             StatusPropertyFile::Bitrate => StatusProperty::Bitrate,
             StatusPropertyFile::Crossfade => StatusProperty::Crossfade,
+            StatusPropertyFile::UpdatingDb => StatusProperty::UpdatingDb,
             StatusPropertyFile::Repeat => StatusProperty::Repeat {
                 on_label: defaults::default_on_label().leak(),
                 off_label: defaults::default_off_label().leak(),
@@ -38,10 +38,11 @@ impl std::str::FromStr for PercentOrLength {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("Invalid size '{s}'");
         if s.ends_with('%') {
-            Ok(PercentOrLength::Percent(s.trim_end_matches('%').parse()?))
+            Ok(PercentOrLength::Percent(s.trim_end_matches('%').parse().with_context(invalid)?))
         } else {
-            Ok(PercentOrLength::Length(s.parse()?))
+            Ok(PercentOrLength::Length(s.parse().with_context(invalid)?))
         }
     }
 }
@@ -167,17 +167,23 @@ impl TryFrom<&[u8]> for crate::config::ConfigColor {
             b"white" => Ok(Self::White),
             s if input.len() == 7 && input.first().is_some_and(|v| v == &b'#') => {
                 let res = std::str::from_utf8(s.strip_prefix(b"#").context("")?)?;
-                let res = u32::from_str_radix(res, 16).context("")?;
+                let res = u32::from_str_radix(res, 16).with_context(|| {
+                    format!("Invalid color format '{}'", String::from_utf8_lossy(s))
+                })?;
                 Ok(Self::Hex(res))
             }
             s if s.starts_with(b"rgb(") => {
+                let invalid = || format!("Invalid color format '{}'", String::from_utf8_lossy(s));
                 let mut colors = std::str::from_utf8(
-                    s.strip_prefix(b"rgb(").context("")?.strip_suffix(b")").context("")?,
+                    s.strip_prefix(b"rgb(")
+                        .context("")?
+                        .strip_suffix(b")")
+                        .with_context(invalid)?,
                 )?
                 .splitn(3, ',');
-                let r = colors.next().context("")?.parse::<u8>().context("")?;
-                let g = colors.next().context("")?.parse::<u8>().context("")?;
-                let b = colors.next().context("")?.parse::<u8>().context("")?;
+                let r = colors.next().with_context(invalid)?.parse::<u8>().with_context(invalid)?;
+                let g = colors.next().with_context(invalid)?.parse::<u8>().with_context(invalid)?;
+                let b = colors.next().with_context(invalid)?.parse::<u8>().with_context(invalid)?;
                 Ok(Self::Rgb(r, g, b))
             }
             s => {
@@ -0,0 +1,266 @@
+use std::{
+    iter::Peekable,
+    str::{Chars, FromStr},
+};
+
+use anyhow::{Context, Result, bail};
+use itertools::Itertools;
+
+use super::properties::{
+    Property,
+    PropertyFile,
+    PropertyKind,
+    PropertyKindFile,
+    PropertyKindFileOrText,
+    SongPropertyFile,
+    StatusPropertyFile,
+};
+
+/// A list of properties parsed from a template string such as
+/// `{Artist} - {Title}`.
+///
+/// * `{Name}` - song property, status property or any other song tag, in this
+///   order. Can be prefixed with `song:`, `status:` or `sticker:` to be
+///   explicit, for example `{status:Duration}` or `{sticker:playCount}`.
+/// * `[...]` - group, displayed only if all of the properties inside it are
+///   present.
+/// * `|` - directly after a property or a group, the following property or
+///   group is used as its default, for example `{Title}|{Filename}` or
+///   `[{Artist} - {Title}]|[unknown]`.
+/// * `\` - escapes the next character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template(pub Vec<PropertyFile<PropertyKindFile>>);
+
+impl Template {
+    pub fn into_properties(self) -> Result<Vec<&'static Property<'static, PropertyKind>>> {
+        self.0.into_iter().map(TryInto::try_into).try_collect()
+    }
+}
+
+impl FromStr for Template {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Template(parse_items(&mut s.chars().peekable(), false)?))
+    }
+}
+
+fn parse_items(
+    chars: &mut Peekable<Chars>,
+    in_group: bool,
+) -> Result<Vec<PropertyFile<PropertyKindFile>>> {
+    let mut result = Vec::new();
+    let mut text = String::new();
+
+    loop {
+        match chars.next() {
+            None if in_group => bail!("Unclosed group, expected ']'"),
+            None => break,
+            Some(']') if in_group => break,
+            Some('\\') => text.push(chars.next().context("Unfinished escape at the end")?),
+            Some(c @ ('{' | '[')) => {
+                if !text.is_empty() {
+                    result.push(text_property(std::mem::take(&mut text)));
+                }
+                result.push(parse_element(c, chars)?);
+            }
+            Some(c @ ('}' | ']')) => bail!("Unexpected '{c}', use '\\{c}' to display it"),
+            Some(c) => text.push(c),
+        }
+    }
+
+    if !text.is_empty() {
+        result.push(text_property(text));
+    }
+
+    Ok(result)
+}
+
+fn parse_element(
+    opening: char,
+    chars: &mut Peekable<Chars>,
+) -> Result<PropertyFile<PropertyKindFile>> {
+    let kind = match opening {
+        '{' => {
+            let mut name = String::new();
+            loop {
+                match chars.next() {
+                    Some('}') => break,
+                    Some(c) => name.push(c),
+                    None => bail!("Unclosed property, expected '}}'"),
+                }
+            }
+            parse_property(&name)?
+        }
+        '[' => PropertyKindFileOrText::Group(parse_items(chars, true)?),
+        c => bail!("Expected '{{' or '[' but found '{c}'"),
+    };
+
+    let default = if chars.next_if_eq(&'|').is_some() {
+        let next = chars.next().context("Expected a default after '|'")?;
+        Some(Box::new(parse_element(next, chars)?))
+    } else {
+        None
+    };
+
+    Ok(PropertyFile { kind, style: None, default })
+}
+
+fn parse_property(name: &str) -> Result<PropertyKindFileOrText<PropertyKindFile>> {
+    let (prefix, name) = match name.split_once(':') {
+        Some((prefix, name)) => (Some(prefix.trim().to_lowercase()), name.trim()),
+        None => (None, name.trim()),
+    };
+
+    if name.is_empty() {
+        bail!("Empty property name");
+    }
+
+    Ok(match prefix.as_deref() {
+        Some("sticker") => PropertyKindFileOrText::Sticker(name.to_owned()),
+        Some("song") => PropertyKindFileOrText::Property(PropertyKindFile::Song(
+            song_property(name).unwrap_or_else(|| SongPropertyFile::Other(name.to_lowercase())),
+        )),
+        Some("status") => PropertyKindFileOrText::Property(PropertyKindFile::Status(
+            status_property(name).with_context(|| format!("Unknown status property '{name}'"))?,
+        )),
+        Some(prefix) => {
+            bail!("Unknown prefix '{prefix}', expected one of 'song', 'status' or 'sticker'")
+        }
+        None => PropertyKindFileOrText::Property(
+            song_property(name)
+                .map(PropertyKindFile::Song)
+                .or_else(|| status_property(name).map(PropertyKindFile::Status))
+                .unwrap_or_else(|| {
+                    PropertyKindFile::Song(SongPropertyFile::Other(name.to_lowercase()))
+                }),
+        ),
+    })
+}
+
+fn song_property(name: &str) -> Option<SongPropertyFile> {
+    Some(match name.to_lowercase().as_str() {
+        "filename" => SongPropertyFile::Filename,
+        "file" => SongPropertyFile::File,
+        "title" => SongPropertyFile::Title,
+        "artist" => SongPropertyFile::Artist,
+        "album" => SongPropertyFile::Album,
+        "duration" => SongPropertyFile::Duration,
+        "track" => SongPropertyFile::Track,
+        _ => return None,
+    })
+}
+
+fn status_property(name: &str) -> Option<StatusPropertyFile> {
+    Some(match name.to_lowercase().as_str() {
+        "volume" => StatusPropertyFile::Volume,
+        "repeat" => StatusPropertyFile::Repeat,
+        "random" => StatusPropertyFile::Random,
+        "single" => StatusPropertyFile::Single,
+        "consume" => StatusPropertyFile::Consume,
+        "state" => StatusPropertyFile::State,
+        "elapsed" => StatusPropertyFile::Elapsed,
+        "duration" => StatusPropertyFile::Duration,
+        "crossfade" => StatusPropertyFile::Crossfade,
+        "bitrate" => StatusPropertyFile::Bitrate,
+        _ => return None,
+    })
+}
+
+fn text_property(text: String) -> PropertyFile<PropertyKindFile> {
+    PropertyFile { kind: PropertyKindFileOrText::Text(text), style: None, default: None }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use test_case::test_case;
+
+    use super::Template;
+    use crate::config::theme::properties::{
+        PropertyFile,
+        PropertyKindFile,
+        PropertyKindFileOrText,
+        SongPropertyFile,
+        StatusPropertyFile,
+    };
+
+    fn prop(kind: PropertyKindFileOrText<PropertyKindFile>) -> PropertyFile<PropertyKindFile> {
+        PropertyFile { kind, style: None, default: None }
+    }
+
+    fn song(p: SongPropertyFile) -> PropertyFile<PropertyKindFile> {
+        prop(PropertyKindFileOrText::Property(PropertyKindFile::Song(p)))
+    }
+
+    fn status(p: StatusPropertyFile) -> PropertyFile<PropertyKindFile> {
+        prop(PropertyKindFileOrText::Property(PropertyKindFile::Status(p)))
+    }
+
+    fn text(t: &str) -> PropertyFile<PropertyKindFile> {
+        prop(PropertyKindFileOrText::Text(t.to_owned()))
+    }
+
+    #[test]
+    fn parses_properties_and_text() {
+        let result: Template = "{Artist} - {title} ({Elapsed})".parse().unwrap();
+
+        assert_eq!(result.0, vec![
+            song(SongPropertyFile::Artist),
+            text(" - "),
+            song(SongPropertyFile::Title),
+            text(" ("),
+            status(StatusPropertyFile::Elapsed),
+            text(")"),
+        ]);
+    }
+
+    #[test]
+    fn parses_prefixed_properties() {
+        let result: Template =
+            "{status:Duration}{song:Duration}{sticker:playCount}{Genre}".parse().unwrap();
+
+        assert_eq!(result.0, vec![
+            status(StatusPropertyFile::Duration),
+            song(SongPropertyFile::Duration),
+            prop(PropertyKindFileOrText::Sticker("playCount".to_owned())),
+            song(SongPropertyFile::Other("genre".to_owned())),
+        ]);
+    }
+
+    #[test]
+    fn parses_groups_with_defaults() {
+        let result: Template = "[{Artist} - {Title}]|{Filename}|[none]".parse().unwrap();
+
+        let mut filename = song(SongPropertyFile::Filename);
+        filename.default = Some(Box::new(prop(PropertyKindFileOrText::Group(vec![text("none")]))));
+        let mut group = prop(PropertyKindFileOrText::Group(vec![
+            song(SongPropertyFile::Artist),
+            text(" - "),
+            song(SongPropertyFile::Title),
+        ]));
+        group.default = Some(Box::new(filename));
+
+        assert_eq!(result.0, vec![group]);
+    }
+
+    #[test]
+    fn parses_escaped_characters() {
+        let result: Template = r"\{a\|b\} | \\".parse().unwrap();
+
+        assert_eq!(result.0, vec![text(r"{a|b} | \")]);
+    }
+
+    #[test_case("{Artist"; "unclosed property")]
+    #[test_case("[{Artist}"; "unclosed group")]
+    #[test_case("{Artist}]"; "unexpected closing bracket")]
+    #[test_case("{}"; "empty property")]
+    #[test_case("{status:Title}"; "unknown status property")]
+    #[test_case("{foo:Title}"; "unknown prefix")]
+    #[test_case("{Title}|"; "missing default")]
+    #[test_case("{Title}|text"; "invalid default")]
+    #[test_case("abc\\"; "unfinished escape")]
+    fn rejects_invalid_template(input: &str) {
+        assert!(input.parse::<Template>().is_err());
+    }
+}
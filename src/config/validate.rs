@@ -0,0 +1,439 @@
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use ron::error::{Position, SpannedError};
+
+use super::{
+    ConfigFile,
+    tabs::{Tabs, validate_tabs},
+    theme::UiConfigFile,
+};
+
+/// Single problem found in the config or theme file
+#[derive(Debug)]
+pub struct Problem {
+    pub path: PathBuf,
+    pub position: Option<Position>,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.position {
+            Some(Position { line, col }) => {
+                write!(f, "{}:{line}:{col}: {}", self.path.display(), self.message)?;
+            }
+            None => write!(f, "{}: {}", self.path.display(), self.message)?,
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "\n    help: {suggestion}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the config file and its theme and runs every conversion done on
+/// startup. Unlike startup, the checks do not stop at the first error so all
+/// problems are returned at once. Only the theme is checked if `only_theme`
+/// is set.
+pub fn validate(config_path: &Path, only_theme: bool) -> Result<Vec<Problem>> {
+    let config_source = std::fs::read_to_string(config_path)
+        .with_context(|| format!("Config file was not found at '{}'", config_path.display()))?;
+    let config: ConfigFile = match ron::de::from_str(&config_source) {
+        Ok(config) => config,
+        Err(err) => return Ok(vec![Problem::from_ron(config_path, &err)]),
+    };
+
+    let mut problems = Vec::new();
+    if !only_theme {
+        for (tab, err) in config.tabs.problems() {
+            let position = match tab {
+                Some(name) => locate(&config_source, &format!("\"{name}\"")),
+                None => locate_field(&config_source, "tabs"),
+            };
+            problems.push(Problem::from_error(config_path, position, &err));
+        }
+    }
+
+    let config_dir = config_path.parent().unwrap_or(Path::new("."));
+    let theme = match config.theme_path(config_dir) {
+        Some(theme_path) => {
+            let (theme, theme_problems) = validate_theme(&theme_path);
+            problems.extend(theme_problems);
+            theme
+        }
+        None if only_theme => {
+            problems.push(Problem {
+                path: config_path.to_path_buf(),
+                position: None,
+                message: "No theme file specified in the config".to_owned(),
+                suggestion: Some(
+                    "set the `theme` field to the name of a file in the themes directory"
+                        .to_owned(),
+                ),
+            });
+            None
+        }
+        None => Some(UiConfigFile::default()),
+    };
+
+    if only_theme {
+        return Ok(problems);
+    }
+
+    // Tabs can only be validated against the layout when both were converted
+    // successfully, errors of either were already reported above
+    let layout = theme.and_then(|theme| theme.layout.convert().ok());
+    let tabs: Option<Tabs> = config.tabs.try_into().ok();
+    if let (Some(layout), Some(tabs)) = (layout, tabs) {
+        if let Err(err) = validate_tabs(&layout, &tabs) {
+            let position = locate_field(&config_source, "tabs");
+            problems.push(Problem::from_error(config_path, position, &err));
+        }
+    }
+
+    Ok(problems)
+}
+
+/// Returns the parsed theme, if it could be parsed at all, together with its
+/// problems
+fn validate_theme(theme_path: &Path) -> (Option<UiConfigFile>, Vec<Problem>) {
+    let source = match std::fs::read_to_string(theme_path) {
+        Ok(source) => source,
+        Err(err) => {
+            let err = anyhow::Error::from(err).context("Failed to open theme file");
+            return (None, vec![Problem::from_error(theme_path, None, &err)]);
+        }
+    };
+
+    let parse = || ron::de::from_str::<UiConfigFile>(&source);
+    let problems = match parse() {
+        Ok(theme) => theme
+            .problems()
+            .into_iter()
+            .map(|(field, err)| Problem::from_error(theme_path, locate_field(&source, field), &err))
+            .collect_vec(),
+        Err(err) => return (None, vec![Problem::from_ron(theme_path, &err)]),
+    };
+
+    // The theme is consumed by the checks above, parsing it again is cheaper
+    // than making every theme struct cloneable
+    (parse().ok(), problems)
+}
+
+impl Problem {
+    fn from_ron(path: &Path, err: &SpannedError) -> Self {
+        let suggestion = match &err.code {
+            ron::Error::NoSuchStructField { expected, found, .. }
+            | ron::Error::NoSuchEnumVariant { expected, found, .. } => {
+                Some(match closest(found, expected) {
+                    Some(closest) => format!("did you mean `{closest}`?"),
+                    None => format!("expected one of: {}", expected.iter().join(", ")),
+                })
+            }
+            ron::Error::MissingStructField { field, .. } => {
+                Some(format!("add the `{field}` field"))
+            }
+            ron::Error::DuplicateStructField { field, .. } => {
+                Some(format!("remove one of the `{field}` fields"))
+            }
+            _ => None,
+        };
+
+        Self {
+            path: path.to_path_buf(),
+            position: Some(err.position),
+            message: err.code.to_string(),
+            suggestion,
+        }
+    }
+
+    fn from_error(path: &Path, position: Option<Position>, err: &anyhow::Error) -> Self {
+        let message = format!("{err:#}");
+        let suggestion = if message.contains("Invalid color") {
+            Some(
+                "colors are either a name like \"red\" or \"light_blue\", a hex value like \"#ff0000\", \"rgb(255,0,0)\" or a 256 color index like \"196\""
+                    .to_owned(),
+            )
+        } else if message.contains("Invalid size") {
+            Some("sizes are either a percentage like \"50%\" or a length like \"10\"".to_owned())
+        } else {
+            None
+        };
+
+        Self { path: path.to_path_buf(), position, message, suggestion }
+    }
+}
+
+/// Position of the first occurrence of `needle` outside of comments
+fn locate(source: &str, needle: &str) -> Option<Position> {
+    source.lines().enumerate().find_map(|(idx, line)| {
+        if line.trim_start().starts_with("//") {
+            return None;
+        }
+        line.find(needle)
+            .map(|col| Position { line: idx + 1, col: line[..col].chars().count() + 1 })
+    })
+}
+
+/// Position of the field at the given dotted path like `tab_bar.active_style`,
+/// every segment is looked up inside of the value of the previous one
+fn locate_field(source: &str, path: &str) -> Option<Position> {
+    let mut span = 0..source.len();
+    let mut field_start = 0;
+    for segment in path.split('.') {
+        let (start, value) = find_field(&source[span.clone()], segment)?;
+        field_start = span.start + start;
+        span = span.start + value.start..span.start + value.end;
+    }
+
+    let before = &source[..field_start];
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    Some(Position {
+        line: before.matches('\n').count() + 1,
+        col: before[line_start..].chars().count() + 1,
+    })
+}
+
+/// Finds the shallowest `field: value` in the source and returns the offset of
+/// the field name together with the range of its value
+fn find_field(source: &str, field: &str) -> Option<(usize, Range<usize>)> {
+    let tokens = tokens(source).collect_vec();
+    let mut depth = 0;
+    let mut found: Option<(usize, usize)> = None;
+    for (idx, pair) in tokens.windows(2).enumerate() {
+        match pair[0].1 {
+            Token::Open => depth += 1,
+            Token::Close => depth -= 1,
+            Token::Ident(name)
+                if name == field
+                    && pair[1].1 == Token::Colon
+                    && found.is_none_or(|(_, found_depth)| depth < found_depth) =>
+            {
+                found = Some((idx, depth));
+            }
+            _ => {}
+        }
+    }
+    let (idx, _) = found?;
+
+    // The value ends with the comma or bracket closing it
+    let mut depth = 0;
+    let end = tokens[idx + 2..]
+        .iter()
+        .find(|(_, token)| match token {
+            Token::Open => {
+                depth += 1;
+                false
+            }
+            Token::Close if depth == 0 => true,
+            Token::Close => {
+                depth -= 1;
+                false
+            }
+            Token::Comma => depth == 0,
+            _ => false,
+        })
+        .map_or(source.len(), |(offset, _)| *offset);
+
+    Some((tokens[idx].0, tokens[idx + 1].0 + 1..end))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Open,
+    Close,
+    Comma,
+    Colon,
+    Ident(&'a str),
+    Other,
+}
+
+/// Splits ron source into the tokens needed to find fields together with their
+/// offsets, whitespace, comments and the contents of strings are skipped
+fn tokens(source: &str) -> impl Iterator<Item = (usize, Token<'_>)> {
+    let mut chars = source.char_indices().peekable();
+    std::iter::from_fn(move || {
+        loop {
+            let (idx, c) = chars.next()?;
+            let token = match c {
+                '(' | '[' | '{' => Token::Open,
+                ')' | ']' | '}' => Token::Close,
+                ',' => Token::Comma,
+                ':' => Token::Colon,
+                '"' | '\'' => {
+                    while let Some((_, next)) = chars.next() {
+                        if next == '\\' {
+                            chars.next();
+                        } else if next == c {
+                            break;
+                        }
+                    }
+                    Token::Other
+                }
+                '/' if chars.next_if(|(_, next)| *next == '/').is_some() => {
+                    chars.find(|(_, next)| *next == '\n');
+                    continue;
+                }
+                '/' if chars.next_if(|(_, next)| *next == '*').is_some() => {
+                    let mut prev = ' ';
+                    chars.find(|(_, next)| {
+                        std::mem::replace(&mut prev, *next) == '*' && *next == '/'
+                    });
+                    continue;
+                }
+                c if c.is_whitespace() => continue,
+                c if c.is_alphanumeric() || c == '_' => {
+                    let mut end = idx + c.len_utf8();
+                    while let Some((next_idx, next)) =
+                        chars.next_if(|(_, next)| next.is_alphanumeric() || *next == '_')
+                    {
+                        end = next_idx + next.len_utf8();
+                    }
+                    Token::Ident(&source[idx..end])
+                }
+                _ => Token::Other,
+            };
+            return Some((idx, token));
+        }
+    })
+}
+
+/// Finds the expected value closest to the found one, but only if it is close
+/// enough to likely be a typo
+fn closest<'a>(found: &str, expected: &[&'a str]) -> Option<&'a str> {
+    expected
+        .iter()
+        .map(|candidate| (candidate, levenshtein(found, candidate)))
+        .filter(|(_, distance)| *distance <= (found.chars().count() / 3).max(2))
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| *candidate)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect_vec();
+    let mut prev = (0..=b.len()).collect_vec();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use rstest::rstest;
+    use test_case::test_case;
+
+    use super::{closest, locate_field, validate};
+    use crate::tests::fixtures::{TempDir, temp_dir};
+
+    /// Fields without a default which every theme has to contain
+    const REQUIRED_THEME_FIELDS: &str = r#"    symbols: (song: "S", dir: "D", marker: "M"),
+    progress_bar: (symbols: ["-", ">", " "]),
+    scrollbar: (symbols: ["|", "=", "^", "v"]),
+    show_song_table_header: true,
+    song_table_format: ([]),
+    header: (rows: []),
+"#;
+
+    #[test_case("volume_stpe", Some("volume_step"); "transposed letters")]
+    #[test_case("scrolof", Some("scrolloff"); "missing letters")]
+    #[test_case("something_else", None; "unrelated")]
+    fn suggests_closest_name(found: &str, expected: Option<&str>) {
+        assert_eq!(closest(found, &["volume_step", "scrolloff", "max_fps"]), expected);
+    }
+
+    #[test_case("text_color", Some((5, 5)); "top level field")]
+    #[test_case("tab_bar.active_style", Some((9, 9)); "nested field")]
+    #[test_case("tab_bar.inactive_style", None; "missing nested field")]
+    #[test_case("other.text_color", Some((4, 13)); "nested field shadowing top level one")]
+    fn locates_fields(path: &str, expected: Option<(usize, usize)>) {
+        let source = r#"(
+    // text_color: None,
+    background_color: "text_color: in a string",
+    other: (text_color: "blue", active_style: ()),
+    text_color : "red",
+    tab_bar: (
+        /* active_style: (), */
+        enabled: true,
+        active_style: (fg: "black"),
+    ),
+)"#;
+
+        let position = locate_field(source, path);
+
+        assert_eq!(position.map(|p| (p.line, p.col)), expected);
+    }
+
+    #[rstest]
+    fn reports_all_problems(#[from(temp_dir)] dir: TempDir) {
+        std::fs::create_dir_all(dir.join("themes")).unwrap();
+        let config_path = dir.join("config.ron");
+        std::fs::write(&config_path, "(theme: Some(\"broken\"))").unwrap();
+        let theme = format!(
+            r##"#![enable(implicit_some)]
+(
+    text_color: "redd",
+    tab_bar: (
+        active_style: (fg: "#zzzzzz"),
+    ),
+{REQUIRED_THEME_FIELDS})"##
+        );
+        std::fs::write(dir.join("themes").join("broken.ron"), theme).unwrap();
+
+        let problems = validate(&config_path, false).unwrap();
+
+        let positions = problems
+            .iter()
+            .map(|problem| problem.position.map(|p| (p.line, p.col)))
+            .collect::<Vec<_>>();
+        assert_eq!(positions, [Some((3, 5)), Some((5, 9))]);
+        assert!(problems.iter().all(|problem| problem.suggestion.is_some()));
+    }
+
+    #[rstest]
+    fn reports_wrong_number_of_column_widths(#[from(temp_dir)] dir: TempDir) {
+        std::fs::create_dir_all(dir.join("themes")).unwrap();
+        let config_path = dir.join("config.ron");
+        std::fs::write(&config_path, "(theme: Some(\"widths\"))").unwrap();
+        let theme = format!(
+            r"(
+    tab_bar: (),
+    browser_column_widths: [20, 80],
+{REQUIRED_THEME_FIELDS})"
+        );
+        std::fs::write(dir.join("themes").join("widths.ron"), theme).unwrap();
+
+        let problems = validate(&config_path, false).unwrap();
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].position.map(|p| (p.line, p.col)), Some((3, 5)));
+        assert_eq!(problems[0].message, "Expected exactly 3 browser column widths, found 2");
+    }
+
+    #[rstest]
+    fn suggests_variant_for_typo(#[from(temp_dir)] dir: TempDir) {
+        let config_path = dir.join("config.ron");
+        std::fs::write(&config_path, "(\n    album_art: (method: Kity),\n)").unwrap();
+
+        let problems = validate(&config_path, false).unwrap();
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].position.map(|p| p.line), Some(2));
+        assert_eq!(problems[0].suggestion.as_deref(), Some("did you mean `Kitty`?"));
+    }
+}
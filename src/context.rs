@@ -1,4 +1,4 @@
-use std::{cell::Cell, collections::HashSet, ops::AddAssign};
+use std::{cell::Cell, collections::HashSet, ops::AddAssign, path::PathBuf};
 
 use anyhow::{Result, bail};
 use bon::bon;
@@ -19,9 +19,10 @@ use crate::{
     },
     shared::{
         events::ClientRequest,
-        lrc::{Lrc, LrcIndex, get_lrc_path},
+        lrc::{Lrc, LrcIndex, Lyrics, get_lrc_path},
         macros::status_warn,
         mpd_query::MpdQuerySync,
+        notifications::Notifications,
     },
 };
 
@@ -42,6 +43,7 @@ pub struct AppContext {
     pub(crate) lrc_index: LrcIndex,
     pub(crate) rendered_frames: u64,
     pub(crate) should_fetch_stickers: bool,
+    pub(crate) notifications: Notifications,
     #[debug(skip)]
     pub(crate) scheduler: Scheduler<(Sender<AppEvent>, Sender<ClientRequest>)>,
 }
@@ -69,9 +71,13 @@ impl AppContext {
         let status = client.get_status()?;
         let queue = client.playlist_info(sticker_support_needed)?.unwrap_or_default();
 
-        if !supported_commands.contains("albumart") || !supported_commands.contains("readpicture") {
+        let mpd_album_art_supported =
+            supported_commands.contains("albumart") && supported_commands.contains("readpicture");
+        if !mpd_album_art_supported && client.config().is_none() {
             config.album_art.method = ImageMethod::None;
-            status_warn!("Album art is disabled because it is not supported by MPD");
+            status_warn!(
+                "Album art is disabled because it is not supported by MPD and the music directory is not reachable"
+            );
         }
 
         log::info!(config:? = config; "Resolved config");
@@ -89,6 +95,7 @@ impl AppContext {
             client_request_sender,
             needs_render: Cell::new(false),
             should_fetch_stickers: sticker_support_needed,
+            notifications: Notifications::default(),
             rendered_frames: 0,
         })
     }
@@ -197,6 +204,83 @@ impl AppContext {
 
         Ok(None)
     }
+
+    /// Path of the current song's lrc file, either at the same path as the song
+    /// file in the `lyrics_dir` or found by the lyrics index
+    pub(crate) fn find_lrc_path(&self) -> Result<Option<PathBuf>> {
+        let Some((_, song)) = self.find_current_song_in_queue() else {
+            return Ok(None);
+        };
+
+        let Some(lyrics_dir) = self.config.lyrics_dir else {
+            return Ok(None);
+        };
+
+        let path = get_lrc_path(lyrics_dir, &song.file)?;
+        if path.exists() {
+            return Ok(Some(path));
+        }
+
+        Ok(self.lrc_index.find_lrc_path_for_song(song).cloned())
+    }
+
+    /// Finds lyrics of the current song. Lrc files take precedence over plain
+    /// text `.txt` files at the same path and lyrics embedded in the song's
+    /// tags.
+    pub(crate) fn find_lyrics(&self) -> Result<Option<Lyrics>> {
+        if let Some(lrc) = self.find_lrc()? {
+            return Ok(Some(Lyrics::Synced(lrc)));
+        }
+
+        let Some((_, song)) = self.find_current_song_in_queue() else {
+            return Ok(None);
+        };
+
+        if let Some(lyrics_dir) = self.config.lyrics_dir {
+            let path = get_lrc_path(lyrics_dir, &song.file)?.with_extension("txt");
+            match std::fs::read_to_string(&path) {
+                Ok(text) => return Ok(Some(Lyrics::plain(&text))),
+                Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => {
+                    log::trace!(path:?; "Plain text lyrics not found");
+                }
+                Err(err) => {
+                    log::error!(err:?; "Encountered error when searching for plain text lyrics");
+                }
+            }
+        }
+
+        Ok(song.lyrics().map(|lyrics| Lyrics::parse(lyrics)))
+    }
+
+    /// Requests the lyrics of the current song to be fetched if fetching is
+    /// enabled and no lrc file was found for it
+    pub(crate) fn fetch_lyrics_if_missing(&self) -> Result<()> {
+        if !self.config.lyrics.fetch {
+            return Ok(());
+        }
+        let Some(lyrics_dir) = self.config.lyrics_dir else {
+            return Ok(());
+        };
+        let Some((_, song)) = self.find_current_song_in_queue() else {
+            return Ok(());
+        };
+        if song.file.contains("://") {
+            return Ok(());
+        }
+        // an existing file is never overwritten, even if it cannot be parsed
+        let path = get_lrc_path(lyrics_dir, &song.file)?;
+        if path.exists() || self.lrc_index.find_lrc_path_for_song(song).is_some() {
+            return Ok(());
+        }
+
+        self.work_sender.send(WorkRequest::FetchLyrics {
+            url: self.config.lyrics.fetch_url,
+            song: song.clone(),
+            path,
+            not_found_max_age: self.config.lyrics.not_found_max_age,
+        })?;
+        Ok(())
+    }
 }
 
 impl Config {
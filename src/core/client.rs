@@ -20,6 +20,10 @@ use crate::{
     },
 };
 
+/// How long to wait before trying to reconnect after the connection to MPD
+/// has been lost
+pub const RECONNECT_WAIT_TIME: std::time::Duration = std::time::Duration::from_secs(1);
+
 pub fn init(
     client_rx: Receiver<ClientRequest>,
     event_tx: Sender<AppEvent>,
@@ -163,10 +167,30 @@ fn client_task(
                                             continue;
                                         }
 
+                                        if let ClientRequest::SwitchServer(new_config) = request {
+                                            if switch_server(&mut client, new_config, event_tx) {
+                                                // Queued commands were meant for the previous server. Queries
+                                                // are kept and run against the new server so that everyone
+                                                // waiting for them still receives a reply.
+                                                let queued = buffer.len();
+                                                buffer.retain(|request| !matches!(request, ClientRequest::Command(_)));
+                                                if buffer.len() < queued {
+                                                    log::warn!(count = queued - buffer.len(); "Dropped commands queued for the previous server");
+                                                }
+                                            } else {
+                                                try_break!(client.reconnect(), "Failed to reconnect to the previous server");
+                                                try_skip!(event_tx.send(AppEvent::ServerSwitchFailed), "Failed to send server switch failed event");
+                                            }
+                                            try_break!(client.set_read_timeout(Some(config.mpd_read_timeout)), "Failed to set read timeout");
+                                            try_break!(client.set_write_timeout(Some(config.mpd_write_timeout)), "Failed to set write timeout");
+                                            client_write = try_break!(client.stream.try_clone(), "Client write clone to succeed");
+                                            continue;
+                                        }
+
                                         match handle_client_request(&mut client, request) {
                                             Ok(result) => {
                                                 try_break!(
-                                                    event_tx.send(AppEvent::WorkDone(Ok(result))),
+                                                    event_tx.send(AppEvent::WorkDone(Ok(Box::new(result)))),
                                                     "Failed to send work done success event"
                                                 );
                                             }
@@ -209,7 +233,7 @@ fn client_task(
                 client_return_tx.send(client).expect("To be able to return the client");
             }
 
-            let wait_time = std::time::Duration::from_secs(1);
+            let wait_time = RECONNECT_WAIT_TIME;
             log::debug!(wait_time:?; "Lost connection to MPD, waiting before trying again");
             try_skip!(
                 event_tx.send(AppEvent::LostConnection),
@@ -288,23 +312,76 @@ fn check_connection(
     config: &Config,
 ) -> bool {
     if first_loop {
-        true
-    } else if client.reconnect().is_ok() {
-        // empty the work queue after reconnect as they might no longer be
-        // relevant
-        let _ = client_rx.try_iter().collect::<Vec<_>>();
-        try_skip!(event_tx.send(AppEvent::Reconnected), "Failed to send reconnected event");
-        if let Err(err) = client.set_read_timeout(Some(config.mpd_read_timeout)) {
-            log::error!(error:? = err; "Failed to set read timeout");
-            return false;
+        return true;
+    }
+
+    // Requests queued while disconnected are dropped as they might no longer
+    // be relevant. Switching to another server is the only way out when the
+    // current one stays unreachable so the latest switch is tried first.
+    let switch_to = client_rx
+        .try_iter()
+        .filter_map(|request| match request {
+            ClientRequest::SwitchServer(config) => Some(config),
+            _ => None,
+        })
+        .last();
+    let is_connected = match switch_to {
+        Some(new_config) if switch_server(client, new_config, event_tx) => true,
+        Some(_) if client.reconnect().is_ok() => {
+            try_skip!(
+                event_tx.send(AppEvent::ServerSwitchFailed),
+                "Failed to send server switch failed event"
+            );
+            true
+        }
+        _ if client.reconnect().is_ok() => {
+            try_skip!(event_tx.send(AppEvent::Reconnected), "Failed to send reconnected event");
+            true
+        }
+        _ => false,
+    };
+
+    if !is_connected {
+        return false;
+    }
+    if let Err(err) = client.set_read_timeout(Some(config.mpd_read_timeout)) {
+        log::error!(error:? = err; "Failed to set read timeout");
+        return false;
+    }
+    if let Err(err) = client.set_write_timeout(Some(config.mpd_write_timeout)) {
+        log::error!(error:? = err; "Failed to set write timeout");
+        return false;
+    }
+    true
+}
+
+/// Connects to the MPD server of the given config. The previous server is
+/// kept as the one to reconnect to and an error is reported if the
+/// connection fails.
+fn switch_server(
+    client: &mut Client<'_>,
+    config: &'static Config,
+    event_tx: &Sender<AppEvent>,
+) -> bool {
+    log::debug!(address:? = config.address; "Switching MPD server");
+    let (address, password) = client.set_address(config.address, config.password);
+    match client.reconnect() {
+        Ok(_) => {
+            try_skip!(
+                event_tx.send(AppEvent::ServerSwitched(config)),
+                "Failed to send server switched event"
+            );
+            true
         }
-        if let Err(err) = client.set_write_timeout(Some(config.mpd_write_timeout)) {
-            log::error!(error:? = err; "Failed to set write timeout");
-            return false;
+        Err(err) => {
+            status_error!(err:?;
+                "Failed to connect to the MPD server of profile '{}', staying on the current one: {}",
+                config.active_profile.unwrap_or_default(),
+                err
+            );
+            client.set_address(address, password);
+            false
         }
-        true
-    } else {
-        false
     }
 }
 
@@ -324,5 +401,7 @@ fn handle_client_request(client: &mut Client<'_>, request: ClientRequest) -> Res
             query.tx.send(result)?;
             Ok(WorkDone::None)
         }
+        // Handled by the request thread because the connection is replaced
+        ClientRequest::SwitchServer(_) => Ok(WorkDone::None),
     }
 }
@@ -1,18 +1,32 @@
 use std::{io::Write, path::PathBuf};
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 use itertools::Itertools;
+use serde::Serialize;
 
 use crate::{
     config::{
-        cli::{Command, StickerCmd},
+        cli::{Command, QueueCmd, StickerCmd},
         cli_config::CliConfig,
+        theme::{
+            properties::{Property, PropertyKind},
+            template::Template,
+        },
     },
     context::AppContext,
+    core::client::RECONNECT_WAIT_TIME,
     mpd::{
         client::Client,
-        commands::{IdleEvent, mpd_config::MpdConfig, volume::Bound},
-        mpd_client::{Filter, MpdClient, Tag},
+        commands::{
+            IdleEvent,
+            Song,
+            Status,
+            lsinfo::{Dir, LsInfoEntry},
+            mpd_config::MpdConfig,
+            volume::Bound,
+        },
+        errors::MpdError,
+        mpd_client::{Filter, FilterKind, MpdClient, Tag},
     },
     shared::{
         lrc::LrcIndex,
@@ -44,7 +58,7 @@ impl Command {
 
                     if wait {
                         loop {
-                            client.idle(Some(IdleEvent::Update))?;
+                            client.idle(&[IdleEvent::Update])?;
                             log::trace!("issuing update");
                             let crate::mpd::commands::Status { updating_db, .. } =
                                 client.get_status()?;
@@ -180,6 +194,10 @@ impl Command {
                 println!("{}", serde_json::ser::to_string(&client.decoders()?)?);
                 Ok(())
             })),
+            Command::Stats => Ok(Box::new(|client| {
+                println!("{}", serde_json::ser::to_string(&client.stats()?)?);
+                Ok(())
+            })),
             Command::Outputs => Ok(Box::new(|client| {
                 println!("{}", serde_json::ser::to_string(&client.outputs()?)?);
                 Ok(())
@@ -193,36 +211,55 @@ impl Command {
             Command::DisableOutput { id } => {
                 Ok(Box::new(move |client| Ok(client.disable_output(id)?)))
             }
-            Command::Status => Ok(Box::new(|client| {
+            Command::SoloOutput { id } => Ok(Box::new(move |client| Ok(client.solo_output(id)?))),
+            Command::SetOutput { id, name, value } => {
+                Ok(Box::new(move |client| Ok(client.set_output_attribute(id, &name, &value)?)))
+            }
+            Command::Status { format: None } => Ok(Box::new(|client| {
                 println!("{}", serde_json::ser::to_string(&client.get_status()?)?);
                 Ok(())
             })),
-            Command::Song { path: Some(paths) } if paths.len() == 1 => {
+            Command::Status { format: Some(ref format) }
+            | Command::Song { path: None, format: Some(ref format) } => {
+                let format = parse_format(format)?;
+                let requires_song = matches!(self, Command::Song { .. });
                 Ok(Box::new(move |client| {
-                    let path = &paths[0];
-                    if let Some(song) = client.find_one(&[Filter::new(Tag::File, path.as_str())])? {
-                        println!("{}", serde_json::ser::to_string(&song)?);
-                        Ok(())
-                    } else {
-                        println!("Song with path '{path}' not found.");
+                    let status = client.get_status()?;
+                    let mut song = client.get_current_song()?;
+                    if requires_song && song.is_none() {
                         std::process::exit(1);
                     }
+                    if let Some(song) = &mut song {
+                        fetch_stickers(client, std::slice::from_mut(song), &format)?;
+                    }
+                    println!("{}", format_line(&format, song.as_ref(), &status));
+                    Ok(())
                 }))
             }
-            Command::Song { path: Some(paths) } => Ok(Box::new(move |client| {
-                let mut songs = Vec::new();
-                for path in &paths {
-                    if let Some(song) = client.find_one(&[Filter::new(Tag::File, path.as_str())])? {
-                        songs.push(song);
+            Command::Song { path: Some(paths), format } => {
+                let format = format.as_deref().map(parse_format).transpose()?;
+                Ok(Box::new(move |client| {
+                    let mut songs = Vec::new();
+                    for path in &paths {
+                        if let Some(song) =
+                            client.find_one(&[Filter::new(Tag::File, path.as_str())])?
+                        {
+                            songs.push(song);
+                        } else {
+                            println!("Song with path '{path}' not found.");
+                            std::process::exit(1);
+                        }
+                    }
+
+                    if format.is_none() && songs.len() == 1 {
+                        println!("{}", serde_json::ser::to_string(&songs[0])?);
+                        Ok(())
                     } else {
-                        println!("Song with path '{path}' not found.");
-                        std::process::exit(1);
+                        print_songs(client, songs, format.as_deref())
                     }
-                }
-                println!("{}", serde_json::ser::to_string(&songs)?);
-                Ok(())
-            })),
-            Command::Song { path: None } => Ok(Box::new(|client| {
+                }))
+            }
+            Command::Song { path: None, format: None } => Ok(Box::new(|client| {
                 let current_song = client.get_current_song()?;
                 if let Some(song) = current_song {
                     println!("{}", serde_json::ser::to_string(&song)?);
@@ -231,6 +268,134 @@ impl Command {
                     std::process::exit(1);
                 }
             })),
+            Command::Queue { format, cmd: None } => {
+                let format = format.as_deref().map(parse_format).transpose()?;
+                Ok(Box::new(move |client| {
+                    let queue = client.playlist_info(false)?.unwrap_or_default();
+                    print_songs(client, queue, format.as_deref())
+                }))
+            }
+            Command::Queue { cmd: Some(QueueCmd::Remove { range }), .. } => {
+                Ok(Box::new(move |client| Ok(client.delete_from_queue(range.parse()?)?)))
+            }
+            Command::Queue { cmd: Some(QueueCmd::Move { range, to }), .. } => {
+                Ok(Box::new(move |client| Ok(client.move_in_queue(range.parse()?, to.parse()?)?)))
+            }
+            Command::Queue { cmd: Some(QueueCmd::Shuffle { range }), .. } => {
+                Ok(Box::new(move |client| {
+                    Ok(client.shuffle(range.as_deref().map(str::parse).transpose()?)?)
+                }))
+            }
+            Command::Queue { cmd: Some(QueueCmd::Save { name, mode }), .. } => {
+                Ok(Box::new(move |client| {
+                    Ok(client.save_queue_as_playlist(&name, mode.map(Into::into))?)
+                }))
+            }
+            Command::Search { filters, kind, format, add, play } => {
+                let format = format.as_deref().map(parse_format).transpose()?;
+                Ok(Box::new(move |client| {
+                    let filters = to_filters(&filters, kind.into());
+                    if add || play {
+                        add_to_queue(client, play, |client| Ok(client.search_add(&filters)?))
+                    } else {
+                        let songs = client.search(&filters)?;
+                        print_songs(client, songs, format.as_deref())
+                    }
+                }))
+            }
+            Command::Find { filters, kind, format, add, play } => {
+                let format = format.as_deref().map(parse_format).transpose()?;
+                Ok(Box::new(move |client| {
+                    let filters = to_filters(&filters, kind.into());
+                    if add || play {
+                        add_to_queue(client, play, |client| Ok(client.find_add(&filters)?))
+                    } else {
+                        let songs = client.find(&filters)?;
+                        print_songs(client, songs, format.as_deref())
+                    }
+                }))
+            }
+            Command::List { tag, filters, kind, add, play } => Ok(Box::new(move |client| {
+                let tag = Tag::Custom(tag.leak());
+                let kind = kind.into();
+                let values = {
+                    let filters = to_filters(&filters, kind);
+                    client.list_tag(tag, (!filters.is_empty()).then_some(filters.as_slice()))?
+                };
+
+                if add || play {
+                    add_to_queue(client, play, |client| {
+                        for value in &values.0 {
+                            let mut filters = to_filters(&filters, kind);
+                            filters.push(Filter::new(tag, value));
+                            client.find_add(&filters)?;
+                        }
+                        Ok(())
+                    })
+                } else {
+                    println!("{}", serde_json::ser::to_string(&values)?);
+                    Ok(())
+                }
+            })),
+            Command::Ls { path, format, add, play } => {
+                let format = format.as_deref().map(parse_format).transpose()?;
+                Ok(Box::new(move |client| {
+                    let entries = client.lsinfo(path.as_deref())?;
+                    if add || play {
+                        add_to_queue(client, play, |client| {
+                            for entry in entries {
+                                match entry {
+                                    LsInfoEntry::Dir(Dir { full_path, .. }) => {
+                                        client.add(&full_path)?;
+                                    }
+                                    LsInfoEntry::File(song) => client.add(&song.file)?,
+                                    LsInfoEntry::Playlist(playlist) => {
+                                        client.load_playlist(&playlist.name)?;
+                                    }
+                                }
+                            }
+                            Ok(())
+                        })
+                    } else if let Some(format) = format {
+                        let status = client.get_status()?;
+                        let mut entries = entries.0;
+                        let mut songs = entries
+                            .iter_mut()
+                            .filter_map(|entry| match entry {
+                                LsInfoEntry::File(song) => Some(std::mem::take(song)),
+                                _ => None,
+                            })
+                            .collect_vec();
+                        fetch_stickers(client, &mut songs, &format)?;
+
+                        let mut songs = songs.into_iter();
+                        for entry in entries {
+                            match entry {
+                                LsInfoEntry::Dir(Dir { full_path, .. }) => println!("{full_path}"),
+                                LsInfoEntry::Playlist(playlist) => println!("{}", playlist.name),
+                                LsInfoEntry::File(_) => {
+                                    let song = songs.next();
+                                    println!("{}", format_line(&format, song.as_ref(), &status));
+                                }
+                            }
+                        }
+                        Ok(())
+                    } else {
+                        println!("{}", serde_json::ser::to_string(&entries)?);
+                        Ok(())
+                    }
+                }))
+            }
+            Command::Watch { events, format } => {
+                let events: Vec<IdleEvent> = events
+                    .iter()
+                    .map(|event| {
+                        event.trim().parse().with_context(|| format!("Invalid event '{event}'"))
+                    })
+                    .try_collect()?;
+                let format = format.as_deref().map(parse_format).transpose()?;
+                Ok(Box::new(move |client| watch(client, &events, format.as_deref())))
+            }
             Command::Mount { name, path } => {
                 Ok(Box::new(move |client| Ok(client.mount(&name, &path)?)))
             }
@@ -239,6 +404,10 @@ impl Command {
                 println!("{}", serde_json::ser::to_string(&client.list_mounts()?)?);
                 Ok(())
             })),
+            Command::ListNeighbors => Ok(Box::new(|client| {
+                println!("{}", serde_json::ser::to_string(&client.list_neighbors()?)?);
+                Ok(())
+            })),
             Command::AlbumArt { output } => Ok(Box::new(move |client| {
                 let Some(song) = client.get_current_song()? else {
                     std::process::exit(3);
@@ -309,6 +478,158 @@ impl Command {
     }
 }
 
+type Format = Vec<&'static Property<'static, PropertyKind>>;
+
+fn parse_format(format: &str) -> Result<Format> {
+    format
+        .parse::<Template>()
+        .and_then(Template::into_properties)
+        .with_context(|| format!("Invalid format '{format}'"))
+}
+
+fn format_line(
+    format: &[&'static Property<'static, PropertyKind>],
+    song: Option<&Song>,
+    status: &Status,
+) -> String {
+    format.iter().filter_map(|prop| prop.as_string(song, status)).collect()
+}
+
+/// Prints the songs as a JSON array or, if a format is supplied, one formatted
+/// song per line
+fn print_songs(
+    client: &mut Client<'_>,
+    mut songs: Vec<Song>,
+    format: Option<&[&'static Property<'static, PropertyKind>]>,
+) -> Result<()> {
+    let Some(format) = format else {
+        println!("{}", serde_json::ser::to_string(&songs)?);
+        return Ok(());
+    };
+
+    let status = client.get_status()?;
+    fetch_stickers(client, &mut songs, format)?;
+    for song in &songs {
+        println!("{}", format_line(format, Some(song), &status));
+    }
+
+    Ok(())
+}
+
+/// Fetches stickers for the songs, but only if the format displays them
+fn fetch_stickers(
+    client: &mut Client<'_>,
+    songs: &mut [Song],
+    format: &[&'static Property<'static, PropertyKind>],
+) -> Result<()> {
+    if songs.is_empty() || !format.iter().any(|prop| prop.kind.contains_stickers()) {
+        return Ok(());
+    }
+
+    let stickers = client
+        .list_stickers_multiple(&songs.iter().map(|song| song.file.as_str()).collect_vec())?;
+    for (song, stickers) in songs.iter_mut().zip(stickers) {
+        song.stickers = Some(stickers.0);
+    }
+
+    Ok(())
+}
+
+/// Prints a line for every change in the given subsystems until interrupted,
+/// reconnecting to MPD if the connection is lost
+fn watch(
+    client: &mut Client<'_>,
+    subsystems: &[IdleEvent],
+    format: Option<&[&'static Property<'static, PropertyKind>]>,
+) -> Result<()> {
+    let mut changed = Vec::new();
+    loop {
+        match print_watch_lines(client, &changed, format)
+            .and_then(|()| Ok(client.idle(subsystems)?))
+        {
+            Ok(events) => changed = events,
+            // Other errors would fail the same way again after reconnecting
+            Err(err) if !is_connection_error(&err) => return Err(err),
+            Err(err) => {
+                eprintln!("Lost connection to MPD: {err}");
+                while let Err(err) = client.reconnect() {
+                    log::debug!(err:?; "Failed to reconnect to MPD");
+                    std::thread::sleep(RECONNECT_WAIT_TIME);
+                }
+                changed = Vec::new();
+            }
+        }
+    }
+}
+
+fn is_connection_error(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<MpdError>(),
+        Some(MpdError::Io(_) | MpdError::ClientClosed | MpdError::TimedOut(_))
+    )
+}
+
+/// Prints one line per changed subsystem, or a single line without a
+/// subsystem if there are none
+fn print_watch_lines(
+    client: &mut Client<'_>,
+    changed: &[IdleEvent],
+    format: Option<&[&'static Property<'static, PropertyKind>]>,
+) -> Result<()> {
+    #[derive(Serialize)]
+    struct WatchLine<'a> {
+        event: Option<IdleEvent>,
+        status: &'a Status,
+        song: Option<&'a Song>,
+    }
+
+    let status = client.get_status()?;
+    let mut song = client.get_current_song()?;
+    if let (Some(format), Some(song)) = (format, &mut song) {
+        fetch_stickers(client, std::slice::from_mut(song), format)?;
+    }
+
+    let events = if changed.is_empty() {
+        vec![None]
+    } else {
+        changed.iter().copied().map(Some).collect_vec()
+    };
+    for event in events {
+        if let Some(format) = format {
+            println!("{}", format_line(format, song.as_ref(), &status));
+        } else {
+            let line = WatchLine { event, status: &status, song: song.as_ref() };
+            println!("{}", serde_json::ser::to_string(&line)?);
+        }
+    }
+
+    Ok(())
+}
+
+fn to_filters(filters: &[(String, String)], kind: FilterKind) -> Vec<Filter<'_>> {
+    filters
+        .iter()
+        .map(|(tag, value)| Filter::new_with_kind(Tag::Custom(tag.clone().leak()), value, kind))
+        .collect_vec()
+}
+
+/// Runs the `add` callback and, if `play` is set, starts playback from the
+/// first song it appended to the queue.
+fn add_to_queue(
+    client: &mut Client<'_>,
+    play: bool,
+    add: impl FnOnce(&mut Client<'_>) -> Result<()>,
+) -> Result<()> {
+    let queue_len = client.get_status()?.playlistlength;
+    add(client)?;
+
+    if play && client.get_status()?.playlistlength > queue_len {
+        client.play_pos(queue_len as usize)?;
+    }
+
+    Ok(())
+}
+
 pub fn run_external_blocking<'a, E>(command: &[&str], envs: E) -> Result<()>
 where
     E: IntoIterator<Item = (&'a str, &'a str)> + std::fmt::Debug,
@@ -0,0 +1,130 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crossbeam::channel::{RecvTimeoutError, Sender};
+use rustix::fs::inotify::WatchFlags;
+
+use super::dir_watcher::DirWatcher;
+use crate::{config::ConfigFile, shared::events::AppEvent};
+
+/// Editors often produce several events when saving a single file, the config
+/// is reloaded only once no further changes happened for this long
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Watches the config file, the themes directory next to it and the file of the
+/// current theme, which may live elsewhere, and requests the config to be
+/// reloaded when any of them changes
+pub fn init(
+    config_path: PathBuf,
+    event_tx: Sender<AppEvent>,
+) -> std::io::Result<std::thread::JoinHandle<()>> {
+    let (dirs, events) =
+        DirWatcher::init("config_dir_watcher", WatchFlags::CLOSE_WRITE | WatchFlags::MOVED_TO)?;
+    let config_path = std::fs::canonicalize(&config_path).unwrap_or(config_path);
+    let config_dir = config_path.parent().map(Path::to_path_buf).unwrap_or_default();
+    let themes_dir = config_dir.join("themes");
+    let mut theme_path = read_theme_path(&config_path, &config_dir);
+    watch(&dirs, &config_dir, &themes_dir, theme_path.as_deref());
+    log::debug!(
+        config_path:?,
+        theme_path:?,
+        watch_count = dirs.watch_count();
+        "Watching config for changes"
+    );
+
+    std::thread::Builder::new().name("config_watcher".to_owned()).spawn(move || {
+        let mut changed = false;
+        loop {
+            let event = if changed {
+                events.recv_timeout(DEBOUNCE)
+            } else {
+                events.recv().map_err(RecvTimeoutError::from)
+            };
+
+            match event {
+                Ok(event) => {
+                    changed |= is_config_file(
+                        &event.path,
+                        &config_path,
+                        &themes_dir,
+                        theme_path.as_deref(),
+                    );
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    changed = false;
+                    let new_theme_path = read_theme_path(&config_path, &config_dir);
+                    if new_theme_path != theme_path {
+                        theme_path = new_theme_path;
+                        dirs.unwatch_all();
+                        watch(&dirs, &config_dir, &themes_dir, theme_path.as_deref());
+                        log::debug!(theme_path:?; "Theme changed, watching its directory");
+                    }
+
+                    log::debug!("Config changed, requesting reload");
+                    if let Err(err) = event_tx.send(AppEvent::ReloadConfig) {
+                        log::error!(err:?; "Failed to request config reload");
+                        return;
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+    })
+}
+
+fn watch(dirs: &DirWatcher, config_dir: &Path, themes_dir: &Path, theme_path: Option<&Path>) {
+    for dir in [Some(config_dir), Some(themes_dir), theme_path.and_then(Path::parent)]
+        .into_iter()
+        .flatten()
+    {
+        if let Err(err) = dirs.watch(dir) {
+            log::warn!(error:? = err, dir:?; "Failed to watch config directory");
+        }
+    }
+}
+
+/// Path of the theme set in the config file, [`None`] if there is none or the
+/// config cannot be read
+fn read_theme_path(config_path: &Path, config_dir: &Path) -> Option<PathBuf> {
+    ConfigFile::read(config_path).ok()?.theme_path(config_dir)
+}
+
+fn is_config_file(
+    path: &Path,
+    config_path: &Path,
+    themes_dir: &Path,
+    theme_path: Option<&Path>,
+) -> bool {
+    path == config_path
+        || theme_path.is_some_and(|theme_path| path == theme_path)
+        || (path.parent() == Some(themes_dir) && path.extension().is_some_and(|ext| ext == "ron"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use test_case::test_case;
+
+    use super::is_config_file;
+
+    #[test_case("/cfg/config.ron", true; "config file")]
+    #[test_case("/cfg/themes/dark.ron", true; "theme file")]
+    #[test_case("/cfg/other.ron", false; "other file in config dir")]
+    #[test_case("/cfg/themes/.dark.ron.swp", false; "editor swap file")]
+    #[test_case("/themes/light.ron", true; "theme outside of config dir")]
+    #[test_case("/themes/other.ron", false; "other file next to theme outside of config dir")]
+    fn detects_config_files(path: &str, expected: bool) {
+        assert_eq!(
+            is_config_file(
+                Path::new(path),
+                Path::new("/cfg/config.ron"),
+                Path::new("/cfg/themes"),
+                Some(Path::new("/themes/light.ron"))
+            ),
+            expected
+        );
+    }
+}
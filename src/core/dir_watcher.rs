@@ -0,0 +1,107 @@
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    mem::MaybeUninit,
+    os::{fd::OwnedFd, unix::ffi::OsStrExt},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use crossbeam::channel::{Receiver, Sender, unbounded};
+use rustix::fs::inotify::{self, CreateFlags, ReadFlags, WatchFlags};
+
+/// Change of a file or directory inside of one of the watched directories
+#[derive(Debug)]
+pub struct DirEvent {
+    pub path: PathBuf,
+    pub flags: ReadFlags,
+}
+
+/// Watches directories with inotify and sends their events over a channel.
+/// Events are read on a thread with the given name until the receiver is
+/// dropped.
+#[derive(Debug, Clone)]
+pub struct DirWatcher {
+    watches: Arc<Mutex<Watches>>,
+}
+
+#[derive(Debug)]
+struct Watches {
+    fd: OwnedFd,
+    flags: WatchFlags,
+    /// Watched directories by their watch descriptors
+    dirs: HashMap<i32, PathBuf>,
+}
+
+impl DirWatcher {
+    pub fn init(name: &str, flags: WatchFlags) -> std::io::Result<(Self, Receiver<DirEvent>)> {
+        let fd = inotify::init(CreateFlags::CLOEXEC)?;
+        let reader_fd = fd.try_clone()?;
+        let watches = Arc::new(Mutex::new(Watches { fd, flags, dirs: HashMap::new() }));
+        let (event_tx, event_rx) = unbounded();
+
+        let thread_watches = Arc::clone(&watches);
+        std::thread::Builder::new()
+            .name(name.to_owned())
+            .spawn(move || run(reader_fd, &thread_watches, &event_tx))?;
+
+        Ok((Self { watches }, event_rx))
+    }
+
+    pub fn watch(&self, dir: &Path) -> std::io::Result<()> {
+        let mut watches = lock(&self.watches);
+        let wd = inotify::add_watch(&watches.fd, dir, watches.flags)?;
+        watches.dirs.insert(wd, dir.to_path_buf());
+        Ok(())
+    }
+
+    /// Stops watching all of the directories, events which were not read yet
+    /// are dropped
+    pub fn unwatch_all(&self) {
+        let mut watches = lock(&self.watches);
+        for wd in std::mem::take(&mut watches.dirs).into_keys() {
+            if let Err(err) = inotify::remove_watch(&watches.fd, wd) {
+                log::warn!(error:? = err; "Failed to remove watch");
+            }
+        }
+    }
+
+    pub fn watch_count(&self) -> usize {
+        lock(&self.watches).dirs.len()
+    }
+}
+
+fn lock(watches: &Mutex<Watches>) -> MutexGuard<'_, Watches> {
+    watches.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn run(fd: OwnedFd, watches: &Mutex<Watches>, event_tx: &Sender<DirEvent>) {
+    let mut buf = [MaybeUninit::uninit(); 4096];
+    let mut reader = inotify::Reader::new(fd, &mut buf);
+
+    loop {
+        let event = match reader.next() {
+            Ok(event) => event,
+            Err(err) => {
+                log::error!(err:?; "Failed to read directory changes, stopping watcher");
+                return;
+            }
+        };
+
+        let mut watches = lock(watches);
+        let flags = event.events();
+        if flags.contains(ReadFlags::IGNORED) {
+            watches.dirs.remove(&event.wd());
+            continue;
+        }
+
+        // Events of directories which are no longer watched are skipped
+        let (Some(dir), Some(name)) = (watches.dirs.get(&event.wd()), event.file_name()) else {
+            continue;
+        };
+        let path = dir.join(OsStr::from_bytes(name.to_bytes()));
+        if event_tx.send(DirEvent { path, flags }).is_err() {
+            return;
+        }
+    }
+}
@@ -1,6 +1,12 @@
-use std::{collections::HashSet, io::Stdout, ops::Sub, time::Duration};
+use std::{
+    collections::HashSet,
+    io::Stdout,
+    ops::Sub,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
-use crossbeam::channel::{Receiver, RecvTimeoutError};
+use crossbeam::channel::{Receiver, RecvTimeoutError, Sender, unbounded};
 use itertools::Itertools;
 use ratatui::{
     Terminal,
@@ -8,55 +14,68 @@ use ratatui::{
     prelude::{Backend, CrosstermBackend},
 };
 
-use super::command::{create_env, run_external};
+use super::command::{create_env, run_external, run_external_blocking};
 use crate::{
+    config::{Config, Leak},
     context::AppContext,
     mpd::{
         commands::{IdleEvent, State},
         mpd_client::MpdClient,
     },
     shared::{
-        events::{AppEvent, WorkDone},
+        events::{AppEvent, ClientRequest, Level, WorkDone, WorkRequest},
         ext::{duration::DurationExt, error::ErrorExt},
-        lrc::get_lrc_path,
-        macros::{status_error, status_warn},
+        lrc::{LrcIndex, get_lrc_path},
+        macros::{status_error, status_info, status_warn, try_skip},
         mpd_query::{
             EXTERNAL_COMMAND,
+            GLOBAL_DB_UPDATE_FINISHED,
+            GLOBAL_DB_UPDATE_STARTED,
             GLOBAL_QUEUE_UPDATE,
             GLOBAL_STATUS_UPDATE,
+            GLOBAL_SUPPORTED_COMMANDS_UPDATE,
             GLOBAL_VOLUME_UPDATE,
             MpdQueryResult,
             run_status_update,
         },
     },
-    ui::{KeyHandleResult, Ui, UiEvent},
+    ui::{KeyHandleResult, Ui, UiEvent, panes::advance_spinner},
 };
 
 pub fn init(
     context: AppContext,
     event_rx: Receiver<AppEvent>,
     terminal: Terminal<CrosstermBackend<Stdout>>,
+    config_path: PathBuf,
 ) -> std::io::Result<std::thread::JoinHandle<Terminal<CrosstermBackend<Stdout>>>> {
     std::thread::Builder::new()
         .name("main".to_owned())
-        .spawn(move || main_task(context, event_rx, terminal))
+        .spawn(move || main_task(context, event_rx, terminal, &config_path))
 }
 
 fn main_task<B: Backend + std::io::Write>(
     mut context: AppContext,
     event_rx: Receiver<AppEvent>,
     mut terminal: Terminal<B>,
+    config_path: &Path,
 ) -> Terminal<B> {
     let size = terminal.size().expect("To be able to get terminal size");
     let area = Rect::new(0, 0, size.width, size.height);
     let mut ui = Ui::new(&context).expect("UI to be created correctly");
     let event_receiver = event_rx;
     let mut render_wanted = false;
-    let max_fps = f64::from(context.config.max_fps);
-    let min_frame_duration = Duration::from_secs_f64(1f64 / max_fps);
+    let mut min_frame_duration = frame_duration(context.config);
     let mut last_render = std::time::Instant::now().sub(Duration::from_secs(10));
     let mut additional_evs = HashSet::new();
     let mut connected = true;
+    let desktop_notifier = DesktopNotifier::new();
+    // Started even without a lyrics directory so that switching to a profile
+    // which has one can rebind it
+    #[cfg(target_os = "linux")]
+    let lyrics_watcher =
+        super::lyrics_watcher::init(context.config.lyrics_dir, context.work_sender.clone())
+            .inspect_err(|err| log::error!(err:?; "Failed to watch lyrics directory for changes"))
+            .ok();
     ui.before_show(area, &mut context).expect("Initial render init to succeed");
     let mut _update_loop_guard = None;
 
@@ -68,6 +87,16 @@ fn main_task<B: Backend + std::io::Write>(
             .map(Duration::from_millis)
             .map(|interval| context.scheduler.repeated(interval, run_status_update));
     }
+    // Rerenders the UI while the database is being updated to animate the
+    // spinner of the UpdatingDb status property
+    let mut _db_update_guard = None;
+    // Statistics of the database before the running update, used to tell what
+    // the update changed
+    let mut stats_before_db_update = None;
+    if context.status.updating_db.is_some() {
+        _db_update_guard =
+            Some(context.scheduler.repeated(DB_UPDATE_RENDER_INTERVAL, run_db_update_render));
+    }
 
     loop {
         let now = std::time::Instant::now();
@@ -108,15 +137,26 @@ fn main_task<B: Backend + std::io::Write>(
                     }
                 },
                 AppEvent::Status(message, level) => {
-                    if let Err(err) = ui.on_event(UiEvent::Status(message, level), &context) {
-                        log::error!(error:? = err; "UI failed to handle status message event");
+                    let config = &context.config.notifications;
+                    if config.desktop_levels.contains(&level) {
+                        if let Some(command) = config.desktop_command {
+                            desktop_notifier.notify(command, &message, level);
+                        }
+                    }
+
+                    let now = Instant::now();
+                    if let Some((shown, hidden)) =
+                        context.notifications.push(message, level, config, now)
+                    {
+                        render_wanted = true;
+                        // Send delayed render events to make the queued message
+                        // appear and disappear
+                        for at in [shown, hidden] {
+                            context.scheduler.schedule(at.duration_since(now), |(tx, _)| {
+                                Ok(tx.send(AppEvent::RequestRender)?)
+                            });
+                        }
                     }
-                    render_wanted = true;
-                    // Send delayed render event to make the status message
-                    // disappear
-                    context.scheduler.schedule(Duration::from_secs(5), |(tx, _)| {
-                        Ok(tx.send(AppEvent::RequestRender)?)
-                    });
                 }
                 AppEvent::Log(msg) => {
                     if let Err(err) = ui.on_event(UiEvent::LogAdded(msg), &context) {
@@ -135,12 +175,17 @@ fn main_task<B: Backend + std::io::Write>(
                 AppEvent::RequestRender => {
                     render_wanted = true;
                 }
-                AppEvent::WorkDone(Ok(result)) => match result {
+                AppEvent::WorkDone(Ok(result)) => match *result {
                     WorkDone::LyricsIndexed { index } => {
                         context.lrc_index = index;
                         if let Err(err) = ui.on_event(UiEvent::LyricsIndexed, &context) {
                             log::error!(error:? = err; "UI failed to handle lyrics indexed event");
                         }
+                        // fetch lyrics of the song playing on startup only after the
+                        // index is ready to not fetch lyrics which already exist
+                        if let Err(err) = context.fetch_lyrics_if_missing() {
+                            status_error!(error:? = err; "Failed to request lyrics, error: '{}'", err.to_status());
+                        }
                     }
                     WorkDone::SingleLrcIndexed { lrc_entry } => {
                         if let Some(lrc_entry) = lrc_entry {
@@ -155,7 +200,30 @@ fn main_task<B: Backend + std::io::Write>(
                             let current_song_id =
                                 context.find_current_song_in_queue().map(|(_, song)| song.id);
                             let current_status = context.status.state;
+                            let was_updating_db = context.status.updating_db.is_some();
                             context.status = status;
+
+                            match (was_updating_db, context.status.updating_db.is_some()) {
+                                (false, true) => {
+                                    _db_update_guard =
+                                        Some(context.scheduler.repeated(
+                                            DB_UPDATE_RENDER_INTERVAL,
+                                            run_db_update_render,
+                                        ));
+                                    context
+                                        .query()
+                                        .id(GLOBAL_DB_UPDATE_STARTED)
+                                        .query(|client| Ok(MpdQueryResult::Stats(client.stats()?)));
+                                }
+                                (true, false) => {
+                                    _db_update_guard = None;
+                                    context
+                                        .query()
+                                        .id(GLOBAL_DB_UPDATE_FINISHED)
+                                        .query(|client| Ok(MpdQueryResult::Stats(client.stats()?)));
+                                }
+                                _ => {}
+                            }
                             let mut song_changed = false;
 
                             match context.status.state {
@@ -224,6 +292,9 @@ fn main_task<B: Backend + std::io::Write>(
                                 if let Err(err) = ui.on_event(UiEvent::SongChanged, &context) {
                                     status_error!(error:? = err; "UI failed to handle idle event, error: '{}'", err.to_status());
                                 }
+                                if let Err(err) = context.fetch_lyrics_if_missing() {
+                                    status_error!(error:? = err; "Failed to request lyrics, error: '{}'", err.to_status());
+                                }
                             }
                             render_wanted = true;
                         }
@@ -235,6 +306,31 @@ fn main_task<B: Backend + std::io::Write>(
                             context.queue = queue.unwrap_or_default();
                             render_wanted = true;
                         }
+                        (
+                            GLOBAL_SUPPORTED_COMMANDS_UPDATE,
+                            None,
+                            MpdQueryResult::SupportedCommands(commands),
+                        ) => {
+                            if context.should_fetch_stickers && !commands.contains("sticker") {
+                                status_warn!(
+                                    "Rmpc was configured to display stickers but MPD did not report sticker support"
+                                );
+                            }
+                            context.supported_commands = commands;
+                        }
+                        (GLOBAL_DB_UPDATE_STARTED, None, MpdQueryResult::Stats(stats)) => {
+                            stats_before_db_update = Some(stats);
+                        }
+                        (GLOBAL_DB_UPDATE_FINISHED, None, MpdQueryResult::Stats(stats)) => {
+                            if let Some(previous) = stats_before_db_update.take() {
+                                status_info!(
+                                    "Database update finished: {}",
+                                    stats.describe_changes_since(&previous)
+                                );
+                            } else {
+                                status_info!("Database update finished");
+                            }
+                        }
                         (
                             EXTERNAL_COMMAND,
                             None,
@@ -269,15 +365,104 @@ fn main_task<B: Backend + std::io::Write>(
                     }
                 },
                 AppEvent::Reconnected => {
-                    for ev in [IdleEvent::Player, IdleEvent::Playlist, IdleEvent::Options] {
-                        handle_idle_event(ev, &context, &mut additional_evs);
-                    }
-                    if let Err(err) = ui.on_event(UiEvent::Reconnected, &context) {
-                        log::error!(error:? = err, event:?; "UI failed to handle resize event");
-                    }
+                    reinitialize(&context, &mut ui, &mut additional_evs);
                     status_warn!("rmpc reconnected to MPD and will reinitialize");
                     connected = true;
                 }
+                AppEvent::ServerSwitchFailed => {
+                    // The error is already reported by the client, the previous
+                    // server is connected again so only its state is refetched
+                    reinitialize(&context, &mut ui, &mut additional_evs);
+                    connected = true;
+                }
+                AppEvent::ServerSwitched(switched) => {
+                    // The config of the profile is committed only once its
+                    // server is connected, a failed switch keeps the current one.
+                    // The profile is applied to the current config again so
+                    // that a reload done in the meantime is not lost.
+                    let config = match switched
+                        .active_profile
+                        .map(|name| context.config.clone().with_profile(name))
+                    {
+                        Some(Ok(config)) => config.leak(),
+                        Some(Err(err)) => {
+                            log::warn!(error:? = err; "Failed to apply the profile to the current config, using the one it was switched with");
+                            switched
+                        }
+                        None => switched,
+                    };
+                    if config.lyrics_dir != context.config.lyrics_dir {
+                        context.lrc_index = LrcIndex::default();
+                        #[cfg(target_os = "linux")]
+                        if let Some(lyrics_watcher) = &lyrics_watcher {
+                            lyrics_watcher.rebind(config.lyrics_dir);
+                        }
+                        if let Some(lyrics_dir) = config.lyrics_dir {
+                            try_skip!(
+                                context.work_sender.send(WorkRequest::IndexLyrics {
+                                    lyrics_dir,
+                                    cache_dir: config.cache_dir
+                                }),
+                                "Failed to request lyrics indexing"
+                            );
+                        }
+                    }
+                    try_skip!(
+                        context.work_sender.send(WorkRequest::ConfigChanged(config)),
+                        "Failed to pass the switched config to the worker"
+                    );
+                    context.config = config;
+
+                    reinitialize(&context, &mut ui, &mut additional_evs);
+                    status_info!(
+                        "Connected to MPD server of profile '{}'",
+                        config.active_profile.unwrap_or_default()
+                    );
+                    connected = true;
+                }
+                AppEvent::SwitchProfile(name) => match context.config.clone().with_profile(name) {
+                    Ok(config) => {
+                        if context.status.state != State::Stop {
+                            _update_loop_guard = None;
+                            context.status.state = State::Stop;
+                        }
+                        try_skip!(
+                            context
+                                .client_request_sender
+                                .send(ClientRequest::SwitchServer(config.leak())),
+                            "Failed to request MPD server switch"
+                        );
+                        status_info!("Switching to MPD server of profile '{name}'");
+                        render_wanted = true;
+                    }
+                    Err(err) => {
+                        status_error!(err:?; "Failed to switch MPD server: {}", err.to_status());
+                    }
+                },
+                AppEvent::ReloadConfig => match context.config.reload(config_path) {
+                    Ok(None) => status_info!("Config is unchanged"),
+                    Ok(Some(config)) => {
+                        if let Err(err) = ui.reload(config.leak(), &mut context) {
+                            status_error!(err:?; "Failed to apply reloaded config: {}", err.to_status());
+                        } else {
+                            min_frame_duration = frame_duration(context.config);
+                            if context.status.state == State::Play {
+                                _update_loop_guard = context
+                                    .config
+                                    .status_update_interval_ms
+                                    .map(Duration::from_millis)
+                                    .map(|interval| {
+                                        context.scheduler.repeated(interval, run_status_update)
+                                    });
+                            }
+                            status_info!("Config reloaded");
+                        }
+                        render_wanted = true;
+                    }
+                    Err(err) => {
+                        status_error!(err:?; "Failed to reload config, keeping the current one: {}", err.to_status());
+                    }
+                },
                 AppEvent::LostConnection => {
                     if context.status.state != State::Stop {
                         _update_loop_guard = None;
@@ -313,6 +498,33 @@ fn main_task<B: Backend + std::io::Write>(
     terminal
 }
 
+/// Fetches the state of the MPD server cached in [`AppContext`] again and lets
+/// the panes reinitialize, used after the connection has been reestablished
+fn reinitialize(context: &AppContext, ui: &mut Ui, additional_evs: &mut HashSet<UiEvent>) {
+    for ev in [IdleEvent::Player, IdleEvent::Playlist, IdleEvent::Options] {
+        handle_idle_event(ev, context, additional_evs);
+    }
+    context.query().id(GLOBAL_SUPPORTED_COMMANDS_UPDATE).replace_id("commands").query(
+        move |client| {
+            Ok(MpdQueryResult::SupportedCommands(client.commands()?.0.into_iter().collect()))
+        },
+    );
+    if let Err(err) = ui.on_event(UiEvent::Reconnected, context) {
+        log::error!(error:? = err; "UI failed to handle reconnected event");
+    }
+}
+
+/// Interval of rerendering the UI while the database is being updated
+const DB_UPDATE_RENDER_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Advances the spinner of the `UpdatingDb` status property and rerenders it
+fn run_db_update_render(
+    (app_tx, _): &(Sender<AppEvent>, Sender<ClientRequest>),
+) -> anyhow::Result<()> {
+    advance_spinner();
+    Ok(app_tx.send(AppEvent::RequestRender)?)
+}
+
 fn handle_idle_event(event: IdleEvent, context: &AppContext, result_ui_evs: &mut HashSet<UiEvent>) {
     match event {
         IdleEvent::Mixer if context.supported_commands.contains("getvol") => {
@@ -349,15 +561,19 @@ fn handle_idle_event(event: IdleEvent, context: &AppContext, result_ui_evs: &mut
                 Ok(MpdQueryResult::Queue(client.playlist_info(fetch_stickers)?))
             });
         }
+        IdleEvent::Update => {
+            context
+                .query()
+                .id(GLOBAL_STATUS_UPDATE)
+                .replace_id("status")
+                .query(move |client| Ok(MpdQueryResult::Status(client.get_status()?)));
+        }
         IdleEvent::StoredPlaylist => {}
         IdleEvent::Database => {}
-        IdleEvent::Update => {}
-        IdleEvent::Output
-        | IdleEvent::Partition
-        | IdleEvent::Subscription
-        | IdleEvent::Message
-        | IdleEvent::Neighbor
-        | IdleEvent::Mount => {
+        IdleEvent::Mount => {}
+        // Neighbors are listed on demand in the storage modal
+        IdleEvent::Neighbor => {}
+        IdleEvent::Output | IdleEvent::Partition | IdleEvent::Subscription | IdleEvent::Message => {
             log::warn!(event:?; "Received unhandled event");
         }
     };
@@ -366,3 +582,42 @@ fn handle_idle_event(event: IdleEvent, context: &AppContext, result_ui_evs: &mut
         result_ui_evs.insert(ev);
     }
 }
+
+/// Thread which sends status messages to the desktop notification daemon one
+/// after another. Failures are only logged because reporting them in the
+/// status bar could cause another notification. The thread stops when the
+/// notifier is dropped.
+struct DesktopNotifier {
+    sender: Sender<(&'static [&'static str], [(&'static str, String); 2])>,
+}
+
+impl DesktopNotifier {
+    fn new() -> Self {
+        let (sender, receiver) = unbounded::<(&'static [&'static str], [(&str, String); 2])>();
+
+        std::thread::Builder::new()
+            .name("desktop_notifications".to_owned())
+            .spawn(move || {
+                for (command, envs) in receiver {
+                    let envs = envs.iter().map(|(k, v)| (*k, v.as_str()));
+                    if let Err(err) = run_external_blocking(command, envs) {
+                        log::error!(error:? = err; "Failed to send desktop notification");
+                    }
+                }
+            })
+            .expect("desktop notifications thread to be spawned");
+
+        Self { sender }
+    }
+
+    fn notify(&self, command: &'static [&'static str], message: &str, level: Level) {
+        let envs = [("MESSAGE", message.to_owned()), ("LEVEL", level.to_string())];
+        if let Err(err) = self.sender.send((command, envs)) {
+            log::error!(error:? = err; "Failed to queue desktop notification");
+        }
+    }
+}
+
+fn frame_duration(config: &Config) -> Duration {
+    Duration::from_secs_f64(1f64 / f64::from(config.max_fps))
+}
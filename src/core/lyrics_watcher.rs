@@ -0,0 +1,122 @@
+use std::path::{Path, PathBuf};
+
+use crossbeam::channel::{Receiver, Sender};
+use rustix::fs::inotify::{ReadFlags, WatchFlags};
+use walkdir::WalkDir;
+
+use super::dir_watcher::{DirEvent, DirWatcher};
+use crate::shared::{events::WorkRequest, macros::try_cont};
+
+/// Watches the `lyrics_dir` for new or modified lrc files and requests them
+/// to be indexed. The watched directory can be changed with
+/// [`LyricsWatcher::rebind`].
+pub fn init(
+    lyrics_dir: Option<&'static str>,
+    work_tx: Sender<WorkRequest>,
+) -> std::io::Result<LyricsWatcher> {
+    let (dirs, events) = DirWatcher::init(
+        "lyrics_dir_watcher",
+        WatchFlags::CLOSE_WRITE | WatchFlags::MOVED_TO | WatchFlags::CREATE,
+    )?;
+    let watcher = LyricsWatcher { dirs, work_tx };
+    if let Some(lyrics_dir) = lyrics_dir {
+        watcher.watch_recursive(Path::new(lyrics_dir), false);
+    }
+
+    let thread_watcher = watcher.clone();
+    std::thread::Builder::new()
+        .name("lyrics_watcher".to_owned())
+        .spawn(move || thread_watcher.run(&events))?;
+
+    Ok(watcher)
+}
+
+#[derive(Debug, Clone)]
+pub struct LyricsWatcher {
+    dirs: DirWatcher,
+    work_tx: Sender<WorkRequest>,
+}
+
+impl LyricsWatcher {
+    /// Stops watching the current lyrics directory and starts watching the
+    /// given one instead, if any
+    pub fn rebind(&self, lyrics_dir: Option<&str>) {
+        self.dirs.unwatch_all();
+        if let Some(lyrics_dir) = lyrics_dir {
+            self.watch_recursive(Path::new(lyrics_dir), false);
+        }
+    }
+
+    fn run(&self, events: &Receiver<DirEvent>) {
+        for DirEvent { path, flags } in events {
+            if flags.contains(ReadFlags::ISDIR) {
+                // Files moved in along with the directory or created before the
+                // watch was added do not produce their own events
+                self.watch_recursive(&path, true);
+            } else if flags.intersects(ReadFlags::CLOSE_WRITE | ReadFlags::MOVED_TO) {
+                self.request_index(path);
+            }
+        }
+    }
+
+    /// Adds watches for the directory and all of its subdirectories,
+    /// optionally requesting the lrc files inside to be indexed
+    fn watch_recursive(&self, dir: &Path, index_files: bool) {
+        for entry in WalkDir::new(dir) {
+            let entry = try_cont!(entry, "Skipping lyrics directory entry");
+            if !entry.file_type().is_dir() {
+                if index_files {
+                    self.request_index(entry.into_path());
+                }
+                continue;
+            }
+
+            try_cont!(self.dirs.watch(entry.path()), "Failed to watch lyrics directory");
+        }
+        log::debug!(dir:?, watch_count = self.dirs.watch_count(); "Watching lyrics directory");
+    }
+
+    fn request_index(&self, path: PathBuf) {
+        if path.extension().is_none_or(|ext| ext != "lrc") {
+            return;
+        }
+
+        log::debug!(path:?; "Lrc file changed, requesting index");
+        if let Err(err) = self.work_tx.send(WorkRequest::IndexSingleLrc { path }) {
+            log::error!(err:?; "Failed to request lrc index");
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::time::Duration;
+
+    use crossbeam::channel::unbounded;
+    use rstest::rstest;
+
+    use crate::{
+        shared::events::WorkRequest,
+        tests::fixtures::{TempDir, temp_dir},
+    };
+
+    #[rstest]
+    fn requests_index_of_new_lrc_files(#[from(temp_dir)] dir: TempDir) {
+        let (tx, rx) = unbounded();
+
+        let _watcher = super::init(Some(dir.to_string_lossy().into_owned().leak()), tx).unwrap();
+        // give the watcher thread time to add the watches
+        std::thread::sleep(Duration::from_millis(200));
+        std::fs::write(dir.join("ignored.txt"), "text").unwrap();
+        std::fs::create_dir_all(dir.join("artist")).unwrap();
+        std::fs::write(dir.join("artist").join("song.lrc"), "[00:01.00]line").unwrap();
+
+        let result = rx.recv_timeout(Duration::from_secs(5));
+
+        let Ok(WorkRequest::IndexSingleLrc { path }) = result else {
+            panic!("expected index request, got {result:?}");
+        };
+        assert_eq!(path, dir.join("artist").join("song.lrc"));
+    }
+}
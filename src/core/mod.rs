@@ -1,7 +1,13 @@
 pub mod client;
 pub mod command;
+#[cfg(target_os = "linux")]
+pub mod config_watcher;
+#[cfg(target_os = "linux")]
+mod dir_watcher;
 pub mod event_loop;
 pub mod input;
+#[cfg(target_os = "linux")]
+pub mod lyrics_watcher;
 pub mod scheduler;
 pub mod socket;
 pub mod work;
@@ -1,44 +1,193 @@
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
-use anyhow::Result;
-use crossbeam::channel::{Receiver, Sender};
+use anyhow::{Result, bail};
+use crossbeam::channel::{Receiver, RecvTimeoutError, Sender, unbounded};
 
 use crate::{
-    config::{Config, Leak, cli_config::CliConfig},
+    config::{Config, Leak, cli::Command, cli_config::CliConfig},
+    mpd::{commands::Song, mpd_client::MpdClient},
     shared::{
         events::{AppEvent, ClientRequest, WorkDone, WorkRequest},
-        lrc::LrcIndex,
-        macros::try_skip,
+        lrc::{
+            LrcIndex,
+            lrclib::{self, LyricsNotFound},
+        },
+        macros::{status_info, try_skip},
         mpd_query::MpdCommand,
+        tag_writer::{update_path, write_songs_tags},
     },
 };
 
+const LYRICS_INDEX_FILE: &str = "lyrics_index.json";
+/// Incremental updates of the lyrics index are saved after this long without
+/// further updates
+const INDEX_SAVE_DELAY: Duration = Duration::from_secs(2);
+
+/// Copy of the lyrics index kept by the worker so that incremental updates can
+/// be persisted
+#[derive(Debug)]
+struct PersistedIndex {
+    index: LrcIndex,
+    path: PathBuf,
+    /// Set when the index was updated and is waiting to be saved
+    save_at: Option<Instant>,
+}
+
+impl PersistedIndex {
+    fn save(&mut self) {
+        self.save_at = None;
+        try_skip!(self.index.save(&self.path), "Failed to save lyrics index");
+    }
+}
+
 pub fn init(
     work_rx: Receiver<WorkRequest>,
+    work_tx: Sender<WorkRequest>,
     client_tx: Sender<ClientRequest>,
     event_tx: Sender<AppEvent>,
     config: &'static Config,
 ) -> std::io::Result<std::thread::JoinHandle<()>> {
+    let fetch_tx = init_lyrics_fetch(work_tx, event_tx.clone(), config)?;
     std::thread::Builder::new().name("work".to_owned()).spawn(move || {
-        let cli_config: CliConfig = config.into();
-        let cli_config = cli_config.leak();
-        while let Ok(req) = work_rx.recv() {
-            let result = handle_work_request(req, &client_tx, cli_config);
+        let mut cli_config: &'static CliConfig = CliConfig::from(config).leak();
+        let mut lyrics_index: Option<PersistedIndex> = None;
+        loop {
+            let save_at = lyrics_index.as_ref().and_then(|index| index.save_at);
+            let req = match save_at.map(|deadline| work_rx.recv_deadline(deadline)) {
+                Some(Ok(req)) => req,
+                Some(Err(RecvTimeoutError::Timeout)) => {
+                    if let Some(index) = &mut lyrics_index {
+                        index.save();
+                    }
+                    continue;
+                }
+                Some(Err(RecvTimeoutError::Disconnected)) => break,
+                None => match work_rx.recv() {
+                    Ok(req) => req,
+                    Err(_) => break,
+                },
+            };
+
+            match req {
+                WorkRequest::ConfigChanged(config) => {
+                    cli_config = CliConfig::from(config).leak();
+                    if config.lyrics_dir.is_none() {
+                        if let Some(mut index) = lyrics_index.take() {
+                            if index.save_at.is_some() {
+                                index.save();
+                            }
+                        }
+                    }
+                    try_skip!(
+                        fetch_tx.send(req),
+                        "Failed to pass the changed config to the lyrics fetcher"
+                    );
+                    continue;
+                }
+                WorkRequest::FetchLyrics { .. } => {
+                    try_skip!(fetch_tx.send(req), "Failed to send lyrics fetch request");
+                    continue;
+                }
+                _ => {}
+            }
+
+            let result = handle_work_request(req, &client_tx, cli_config, &mut lyrics_index);
             try_skip!(
-                event_tx.send(AppEvent::WorkDone(result)),
+                event_tx.send(AppEvent::WorkDone(result.map(Box::new))),
                 "Failed to send work done notification"
             );
         }
     })
 }
 
+/// Lyrics are fetched on their own thread so that slow network requests do not
+/// hold up the lyrics indexing and commands handled by the work thread.
+fn init_lyrics_fetch(
+    work_tx: Sender<WorkRequest>,
+    event_tx: Sender<AppEvent>,
+    config: &'static Config,
+) -> std::io::Result<Sender<WorkRequest>> {
+    let (fetch_tx, fetch_rx) = unbounded::<WorkRequest>();
+    std::thread::Builder::new().name("lyrics_fetch".to_owned()).spawn(move || {
+        let mut lyrics_not_found = LyricsNotFound::load(config.cache_dir);
+        while let Ok(req) = fetch_rx.recv() {
+            let result = match req {
+                WorkRequest::FetchLyrics { url, song, path, not_found_max_age } => fetch_lyrics(
+                    url,
+                    song,
+                    path,
+                    not_found_max_age,
+                    &mut lyrics_not_found,
+                    &work_tx,
+                ),
+                WorkRequest::ConfigChanged(config) => {
+                    lyrics_not_found = LyricsNotFound::load(config.cache_dir);
+                    continue;
+                }
+                _ => continue,
+            };
+
+            try_skip!(
+                event_tx.send(AppEvent::WorkDone(result.map(Box::new))),
+                "Failed to send work done notification"
+            );
+        }
+    })?;
+    Ok(fetch_tx)
+}
+
+fn fetch_lyrics(
+    url: &'static str,
+    song: Song,
+    path: PathBuf,
+    not_found_max_age: Option<Duration>,
+    lyrics_not_found: &mut LyricsNotFound,
+    work_tx: &Sender<WorkRequest>,
+) -> Result<WorkDone> {
+    // never overwrite a file which is already there, even if it could not be
+    // parsed
+    if path.exists() {
+        log::debug!(path:?; "Lyrics file already exists, skipping fetch");
+        return Ok(WorkDone::None);
+    }
+
+    if lyrics_not_found.contains(&song.file, not_found_max_age) {
+        log::debug!(file = song.file.as_str(); "Lyrics were not found previously, skipping");
+        return Ok(WorkDone::None);
+    }
+
+    let Some(lrc) = lrclib::fetch_lrc(url, &song)? else {
+        log::debug!(file = song.file.as_str(); "No lyrics found");
+        lyrics_not_found.insert(song.file)?;
+        return Ok(WorkDone::None);
+    };
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, lrc)?;
+    log::debug!(path:?; "Fetched lyrics saved");
+
+    // indexed by the work thread so the persisted lyrics index is updated
+    work_tx.send(WorkRequest::IndexSingleLrc { path })?;
+    Ok(WorkDone::None)
+}
+
 fn handle_work_request(
     request: WorkRequest,
     client_tx: &Sender<ClientRequest>,
     config: &'static CliConfig,
+    lyrics_index: &mut Option<PersistedIndex>,
 ) -> Result<WorkDone> {
     match request {
         WorkRequest::Command(command) => {
+            // watch never finishes and would block the client thread forever
+            if matches!(command, Command::Watch { .. }) {
+                bail!("Cannot use watch command here.");
+            }
             let callback = command.execute(config)?; // TODO log
             try_skip!(
                 client_tx.send(ClientRequest::Command(MpdCommand { callback })),
@@ -46,12 +195,65 @@ fn handle_work_request(
             );
             Ok(WorkDone::None)
         }
-        WorkRequest::IndexLyrics { lyrics_dir } => {
-            let index = LrcIndex::index(&PathBuf::from(lyrics_dir));
+        WorkRequest::IndexLyrics { lyrics_dir, cache_dir } => {
+            let cache_path =
+                cache_dir.map(|dir| PathBuf::from(format!("{dir}{LYRICS_INDEX_FILE}")));
+            let previous = cache_path
+                .as_ref()
+                .filter(|path| path.exists())
+                .and_then(|path| {
+                    LrcIndex::load(path)
+                        .inspect_err(|err| log::warn!(err:?; "Failed to load cached lyrics index"))
+                        .ok()
+                })
+                .unwrap_or_default();
+
+            let index = LrcIndex::update(&PathBuf::from(lyrics_dir), previous);
+            if let Some(mut previous) = lyrics_index.take() {
+                if previous.save_at.is_some() && Some(&previous.path) != cache_path.as_ref() {
+                    previous.save();
+                }
+            }
+            *lyrics_index = cache_path.map(|path| {
+                let mut index = PersistedIndex { index: index.clone(), path, save_at: None };
+                index.save();
+                index
+            });
             Ok(WorkDone::LyricsIndexed { index })
         }
         WorkRequest::IndexSingleLrc { path } => {
-            Ok(WorkDone::SingleLrcIndexed { lrc_entry: LrcIndex::index_single(path)? })
+            let lrc_entry = LrcIndex::index_single(path)?;
+            if let (Some(entry), Some(index)) = (&lrc_entry, lyrics_index.as_mut()) {
+                index.index.add(entry.clone());
+                index.save_at = Some(Instant::now() + INDEX_SAVE_DELAY);
+            }
+            Ok(WorkDone::SingleLrcIndexed { lrc_entry })
+        }
+        WorkRequest::WriteTags { command, music_directory, songs, tags } => {
+            let written = write_songs_tags(command, &music_directory, &songs, &tags);
+            if written.is_empty() {
+                return Ok(WorkDone::None);
+            }
+            status_info!("Tags of {} song(s) written", written.len());
+
+            // a single update for all of the songs instead of one per song,
+            // renamed songs have to be updated at both their old and new path
+            let path = update_path(written.iter().flat_map(|song| {
+                std::iter::once(song.file.as_str()).chain(song.renamed_to.as_deref())
+            }));
+            try_skip!(
+                client_tx.send(ClientRequest::Command(MpdCommand {
+                    callback: Box::new(move |client| {
+                        client.update(path.as_deref())?;
+                        Ok(())
+                    })
+                })),
+                "Failed to request update of the tagged songs"
+            );
+            Ok(WorkDone::None)
         }
+        // Handled by the work loop because they are passed on to the lyrics
+        // fetch thread
+        WorkRequest::FetchLyrics { .. } | WorkRequest::ConfigChanged(_) => Ok(WorkDone::None),
     }
 }
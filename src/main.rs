@@ -30,7 +30,8 @@ use shared::socket::{get_socket_path, list_all_socket_paths};
 use crate::{
     config::{
         ConfigFile,
-        cli::{Args, Command},
+        cli::{Args, Command, ConfigCmd},
+        validate,
     },
     mpd::client::Client,
     shared::{
@@ -59,7 +60,7 @@ mod ui;
 fn main() -> Result<()> {
     let mut args = Args::parse();
     match args.command {
-        Some(Command::Config { current: false }) => {
+        Some(Command::Config { current: false, cmd: None }) => {
             std::io::stdout().write_all(include_bytes!(
                 "../docs/src/content/docs/next/assets/example_config.ron"
             ))?;
@@ -69,7 +70,18 @@ fn main() -> Result<()> {
                 "../docs/src/content/docs/next/assets/example_theme.ron"
             ))?;
         }
-        Some(Command::Config { current: true }) => {
+        Some(Command::Config { cmd: Some(ConfigCmd::Validate { theme }), .. }) => {
+            let problems = validate::validate(&args.config, theme)?;
+            for problem in &problems {
+                eprintln!("{problem}");
+            }
+            if !problems.is_empty() {
+                eprintln!("Found {} problem(s)", problems.len());
+                std::process::exit(1);
+            }
+            println!("No problems found");
+        }
+        Some(Command::Config { current: true, cmd: None }) => {
             let mut file = std::fs::File::open(&args.config).with_context(|| {
                 format!("Config file was not found at '{}'", args.config.to_string_lossy())
             })?;
@@ -164,7 +176,8 @@ fn main() -> Result<()> {
                 Ok(cfg) => cfg,
                 Err(_err) => ConfigFile::default().into(),
             };
-            let config = config.into_config(args.address, args.password).leak();
+            let config =
+                config.into_config(args.address, args.password, args.profile.as_deref())?.leak();
             let mut client = Client::init(config.address, config.password, "main")?;
             client.set_read_timeout(None)?;
             (cmd.execute(config)?)(&mut client)?;
@@ -197,10 +210,14 @@ fn main() -> Result<()> {
                     )?
                 }
             };
+            let config = match &args.profile {
+                Some(profile) => config.with_profile(profile)?,
+                None => config,
+            };
 
             if let Some(lyrics_dir) = config.lyrics_dir {
                 worker_tx
-                    .send(WorkRequest::IndexLyrics { lyrics_dir })
+                    .send(WorkRequest::IndexLyrics { lyrics_dir, cache_dir: config.cache_dir })
                     .context("Failed to request lyrics indexing")?;
             }
             event_tx.send(AppEvent::RequestRender).context("Failed to render first frame")?;
@@ -228,15 +245,24 @@ fn main() -> Result<()> {
             core::client::init(client_rx.clone(), event_tx.clone(), client, context.config)?;
             core::work::init(
                 worker_rx.clone(),
+                worker_tx.clone(),
                 client_tx.clone(),
                 event_tx.clone(),
                 context.config,
             )?;
             core::input::init(event_tx.clone())?;
+            #[cfg(target_os = "linux")]
+            if context.config.watch_config {
+                if let Err(err) = core::config_watcher::init(args.config.clone(), event_tx.clone())
+                {
+                    log::error!(err:?; "Failed to watch config for changes");
+                }
+            }
             let _sock_guard =
                 core::socket::init(event_tx.clone(), worker_tx.clone(), context.config)
                     .context("Failed to initialize socket listener")?;
-            let event_loop_handle = core::event_loop::init(context, event_rx, terminal)?;
+            let event_loop_handle =
+                core::event_loop::init(context, event_rx, terminal, args.config.clone())?;
 
             let original_hook = std::panic::take_hook();
             std::panic::set_hook(Box::new(move |panic| {
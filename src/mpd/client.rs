@@ -31,6 +31,9 @@ pub struct Client<'name> {
     password: Option<MpdPassword<'name>>,
     pub version: Version,
     pub config: Option<MpdConfig>,
+    /// Whether the `config` command was already sent on this connection. It
+    /// always fails over TCP so it is not sent again.
+    pub config_fetched: bool,
 }
 
 impl std::fmt::Debug for Client<'_> {
@@ -138,7 +141,8 @@ impl<'name> Client<'name> {
             );
         }
 
-        let mut client = Self { name, rx, stream, addr, password, version, config: None };
+        let mut client =
+            Self { name, rx, stream, addr, password, version, config: None, config_fetched: false };
 
         if let Some(MpdPassword(password)) = password {
             debug!("Used password auth to MPD");
@@ -180,6 +184,7 @@ impl<'name> Client<'name> {
         self.stream = stream;
         self.version = version;
         self.config = None;
+        self.config_fetched = false;
 
         debug!(name = self.name, addr:? = self.addr, handshake = buf.trim(), version = version.to_string().as_str(); "MPD client initialized");
 
@@ -193,6 +198,16 @@ impl<'name> Client<'name> {
         Ok(self)
     }
 
+    /// Changes the server used by [`Client::reconnect`] and returns the
+    /// previous one
+    pub fn set_address(
+        &mut self,
+        addr: MpdAddress<'name>,
+        password: Option<MpdPassword<'name>>,
+    ) -> (MpdAddress<'name>, Option<MpdPassword<'name>>) {
+        (std::mem::replace(&mut self.addr, addr), std::mem::replace(&mut self.password, password))
+    }
+
     pub fn set_read_timeout(
         &mut self,
         timeout: Option<std::time::Duration>,
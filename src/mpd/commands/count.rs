@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::mpd::{FromMpd, LineHandled, ParseErrorExt, errors::MpdError};
+
+/// Number of songs and their total duration for every value of a tag, as
+/// returned by `count group <tag>`
+#[derive(Debug, Serialize, Default, PartialEq, Eq)]
+pub struct GroupedCount(pub Vec<SongCount>);
+
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct SongCount {
+    /// Value of the tag the songs are grouped by
+    pub group: String,
+    pub songs: u32,
+    pub playtime: Duration,
+}
+
+impl FromMpd for GroupedCount {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        // The value of the group precedes the counts belonging to it
+        match (key, self.0.last_mut()) {
+            ("songs", Some(count)) => count.songs = value.parse().logerr(key, &value)?,
+            ("playtime", Some(count)) => {
+                count.playtime = Duration::from_secs(value.parse().logerr(key, &value)?);
+            }
+            _ => self.0.push(SongCount { group: value, ..Default::default() }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{GroupedCount, SongCount};
+    use crate::mpd::FromMpd;
+
+    #[test]
+    fn parses_grouped_count() {
+        let mut count = GroupedCount::default();
+        for line in
+            ["Genre: ", "songs: 2", "playtime: 300", "Genre: Rock", "songs: 10", "playtime: 2400"]
+        {
+            count.next(line.to_owned()).unwrap();
+        }
+
+        assert_eq!(count.0, [
+            SongCount { group: String::new(), songs: 2, playtime: Duration::from_secs(300) },
+            SongCount { group: "Rock".to_owned(), songs: 10, playtime: Duration::from_secs(2400) },
+        ]);
+    }
+}
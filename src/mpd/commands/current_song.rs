@@ -39,6 +39,11 @@ impl Song {
     pub fn album(&self) -> Option<&String> {
         self.metadata.get("album")
     }
+
+    /// Lyrics embedded in the song's tags
+    pub fn lyrics(&self) -> Option<&String> {
+        self.metadata.get("lyrics").or_else(|| self.metadata.get("unsyncedlyrics"))
+    }
 }
 
 impl FromMpd for Song {
@@ -1,8 +1,9 @@
 use derive_more::{AsMut, AsRef, Into, IntoIterator};
+use serde::Serialize;
 
 use crate::mpd::{FromMpd, LineHandled, errors::MpdError};
 
-#[derive(Debug, Default, IntoIterator, AsRef, AsMut, Into)]
+#[derive(Debug, Serialize, Default, IntoIterator, AsRef, AsMut, Into)]
 pub struct MpdList(pub Vec<String>);
 
 impl From<Vec<String>> for MpdList {
@@ -17,3 +18,56 @@ impl FromMpd for MpdList {
         Ok(LineHandled::Yes)
     }
 }
+
+/// Values of a tag together with the value of the tag they are grouped by, as
+/// returned by `list <tag> group <group>`
+#[derive(Debug, Serialize, Default)]
+pub struct GroupedList {
+    pub items: Vec<(String, String)>,
+    #[serde(skip)]
+    group_key: Option<String>,
+    #[serde(skip)]
+    current_group: String,
+}
+
+impl FromMpd for GroupedList {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        // The value of the group precedes the values belonging to it
+        match &self.group_key {
+            None => {
+                self.group_key = Some(key.to_owned());
+                self.current_group = value;
+            }
+            Some(group_key) if group_key == key => self.current_group = value,
+            Some(_) => self.items.push((self.current_group.clone(), value)),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::GroupedList;
+    use crate::mpd::FromMpd;
+
+    #[test]
+    fn groups_values() {
+        let mut list = GroupedList::default();
+        for (key, value) in [
+            ("AlbumArtist", ""),
+            ("Album", "Singles"),
+            ("AlbumArtist", "Artist"),
+            ("Album", "First"),
+            ("Album", "Second"),
+        ] {
+            list.next(format!("{key}: {value}")).unwrap();
+        }
+
+        assert_eq!(list.items, [
+            (String::new(), "Singles".to_owned()),
+            ("Artist".to_owned(), "First".to_owned()),
+            ("Artist".to_owned(), "Second".to_owned()),
+        ]);
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::{Context, anyhow};
+use derive_more::{AsMut, AsRef, Into, IntoIterator};
+use serde::Serialize;
+
+use crate::mpd::{FromMpd, LineHandled, errors::MpdError};
+
+#[derive(Debug, Serialize, Default, IntoIterator, AsRef, AsMut, Into)]
+pub struct Neighbors(pub Vec<Neighbor>);
+
+#[derive(Debug, Default, Serialize, Clone)]
+pub struct Neighbor {
+    /// Storage URI of the neighbor, can be passed directly to `mount`
+    pub neighbor: String,
+    pub name: String,
+}
+
+impl FromMpd for Neighbors {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        if key == "neighbor" {
+            self.0.push(Neighbor::default());
+        }
+
+        self.0
+            .last_mut()
+            .context(anyhow!(
+                "No element in accumulator while parsing Neighbors. Key '{}' Value :'{}'",
+                key,
+                value
+            ))?
+            .next_internal(key, value)
+    }
+}
+
+impl FromMpd for Neighbor {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "neighbor" => self.neighbor = value,
+            "name" => self.name = value,
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::Neighbors;
+    use crate::mpd::{FromMpd, LineHandled};
+
+    #[test]
+    fn parses_multiple_neighbors() {
+        let mut neighbors = Neighbors::default();
+        for (key, value) in [
+            ("neighbor", "smb://nas"),
+            ("name", "nas (SMB)"),
+            ("neighbor", "upnp://uuid:1234/0"),
+            ("name", "Media Server"),
+        ] {
+            assert!(matches!(
+                neighbors.next_internal(key, value.to_owned()).unwrap(),
+                LineHandled::Yes
+            ));
+        }
+
+        assert_eq!(neighbors.0.len(), 2);
+        assert_eq!(neighbors.0[0].neighbor, "smb://nas");
+        assert_eq!(neighbors.0[0].name, "nas (SMB)");
+        assert_eq!(neighbors.0[1].neighbor, "upnp://uuid:1234/0");
+        assert_eq!(neighbors.0[1].name, "Media Server");
+    }
+}
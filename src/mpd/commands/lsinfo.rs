@@ -1,20 +1,22 @@
 use anyhow::{Context, anyhow};
 use derive_more::{AsMut, AsRef, Into, IntoIterator};
+use serde::Serialize;
 
 use super::Song;
 use crate::mpd::{FromMpd, LineHandled, errors::MpdError};
 
-#[derive(Debug, Default, IntoIterator, AsRef, AsMut, Into)]
+#[derive(Debug, Serialize, Default, IntoIterator, AsRef, AsMut, Into)]
 pub struct LsInfo(pub Vec<LsInfoEntry>);
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "lowercase")]
 pub enum LsInfoEntry {
     Dir(Dir),
     File(Song),
     Playlist(Playlist),
 }
 
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Serialize, Default, PartialEq, Eq)]
 pub struct Dir {
     /// Last segment of the part, the dir name
     pub path: String,
@@ -23,10 +25,10 @@ pub struct Dir {
     pub last_modified: String,
 }
 
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Serialize, Default, PartialEq, Eq)]
 pub struct Playlist {
-    name: String,
-    last_modified: String,
+    pub name: String,
+    pub last_modified: String,
 }
 
 impl FromMpd for Dir {
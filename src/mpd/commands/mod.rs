@@ -1,15 +1,18 @@
+pub mod count;
 pub mod current_song;
 pub mod decoders;
 pub mod idle;
 pub mod list;
 pub mod list_files;
 pub mod list_mounts;
+pub mod list_neighbors;
 pub mod list_playlist;
 pub mod list_playlists;
 pub mod lsinfo;
 pub mod mpd_config;
 pub mod outputs;
 pub mod playlist_info;
+pub mod stats;
 pub mod status;
 pub mod stickers;
 pub mod update;
@@ -21,9 +24,11 @@ pub use self::{
     idle::IdleEvent,
     list_files::ListFiles,
     list_mounts::Mounts,
+    list_neighbors::Neighbors,
     list_playlists::Playlist,
     lsinfo::LsInfo,
     outputs::Output,
+    stats::Stats,
     status::{State, Status},
     update::Update,
     volume::Volume,
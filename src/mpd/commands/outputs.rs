@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use anyhow::{Context, anyhow};
 use derive_more::{AsMut, AsRef, Into, IntoIterator};
 use serde::Serialize;
@@ -11,7 +13,11 @@ pub struct Outputs(pub Vec<Output>);
 pub struct Output {
     pub id: u32,
     pub name: String,
+    pub plugin: String,
     pub enabled: bool,
+    /// Runtime attributes of the output which can be changed with
+    /// `outputset`, for example `dop` or `allowed_formats`
+    pub attributes: BTreeMap<String, String>,
 }
 
 impl FromMpd for Outputs {
@@ -36,6 +42,13 @@ impl FromMpd for Output {
         match key {
             "outputid" => self.id = value.parse().logerr(key, &value)?,
             "outputname" => self.name = value,
+            "plugin" => self.plugin = value,
+            "attribute" => match value.split_once('=') {
+                Some((name, value)) => {
+                    self.attributes.insert(name.to_owned(), value.to_owned());
+                }
+                None => return Ok(LineHandled::No { value }),
+            },
             "outputenabled" => match value.as_str() {
                 "0" => self.enabled = false,
                 "1" => self.enabled = true,
@@ -46,3 +59,39 @@ impl FromMpd for Output {
         Ok(LineHandled::Yes)
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::Outputs;
+    use crate::mpd::{FromMpd, LineHandled};
+
+    #[test]
+    fn parses_attributes() {
+        let mut outputs = Outputs::default();
+        for (key, value) in [
+            ("outputid", "0"),
+            ("outputname", "Speakers"),
+            ("plugin", "alsa"),
+            ("outputenabled", "1"),
+            ("attribute", "allowed_formats="),
+            ("attribute", "dop=0"),
+            ("outputid", "1"),
+            ("outputname", "Headphones"),
+            ("plugin", "pulse"),
+            ("outputenabled", "0"),
+        ] {
+            assert!(matches!(
+                outputs.next_internal(key, value.to_owned()).unwrap(),
+                LineHandled::Yes
+            ));
+        }
+
+        assert_eq!(outputs.0.len(), 2);
+        assert_eq!(outputs.0[0].plugin, "alsa");
+        assert_eq!(outputs.0[0].attributes["dop"], "0");
+        assert_eq!(outputs.0[0].attributes["allowed_formats"], "");
+        assert!(!outputs.0[1].enabled);
+        assert!(outputs.0[1].attributes.is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::mpd::{FromMpd, LineHandled, ParseErrorExt, errors::MpdError};
+
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub artists: u32,
+    pub albums: u32,
+    pub songs: u32,
+    /// Time since MPD was started
+    pub uptime: Duration,
+    /// Time the songs have been playing since MPD was started
+    pub playtime: Duration,
+    /// Sum of the durations of all songs in the database
+    pub db_playtime: Duration,
+    /// Unix timestamp of the last database update
+    pub db_update: u64,
+}
+
+impl Stats {
+    /// Describes how the number of songs, albums and artists in the database
+    /// changed since `previous`, for example `3 songs added, 1 album removed`
+    pub fn describe_changes_since(&self, previous: &Stats) -> String {
+        let changes = [
+            (self.songs, previous.songs, "song"),
+            (self.albums, previous.albums, "album"),
+            (self.artists, previous.artists, "artist"),
+        ]
+        .into_iter()
+        .filter(|(current, previous, _)| current != previous)
+        .map(|(current, previous, name)| {
+            let count = current.abs_diff(previous);
+            let plural = if count == 1 { "" } else { "s" };
+            let verb = if current > previous { "added" } else { "removed" };
+            format!("{count} {name}{plural} {verb}")
+        })
+        .collect::<Vec<_>>();
+
+        if changes.is_empty() {
+            "no songs, albums or artists added or removed".to_owned()
+        } else {
+            changes.join(", ")
+        }
+    }
+}
+
+impl FromMpd for Stats {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "artists" => self.artists = value.parse().logerr(key, &value)?,
+            "albums" => self.albums = value.parse().logerr(key, &value)?,
+            "songs" => self.songs = value.parse().logerr(key, &value)?,
+            "uptime" => self.uptime = Duration::from_secs(value.parse().logerr(key, &value)?),
+            "playtime" => self.playtime = Duration::from_secs(value.parse().logerr(key, &value)?),
+            "db_playtime" => {
+                self.db_playtime = Duration::from_secs(value.parse().logerr(key, &value)?);
+            }
+            "db_update" => self.db_update = value.parse().logerr(key, &value)?,
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+    use crate::mpd::FromMpd;
+
+    #[test]
+    fn parses_stats() {
+        let mut stats = Stats::default();
+        for (key, value) in [
+            ("uptime", "120"),
+            ("playtime", "60"),
+            ("artists", "3"),
+            ("albums", "4"),
+            ("songs", "50"),
+            ("db_playtime", "10000"),
+            ("db_update", "1700000000"),
+        ] {
+            stats.next_internal(key, value.to_owned()).unwrap();
+        }
+
+        assert_eq!(stats, Stats {
+            artists: 3,
+            albums: 4,
+            songs: 50,
+            uptime: Duration::from_secs(120),
+            playtime: Duration::from_secs(60),
+            db_playtime: Duration::from_secs(10000),
+            db_update: 1_700_000_000,
+        });
+    }
+
+    #[test]
+    fn describes_changes() {
+        let previous = Stats { artists: 3, albums: 4, songs: 50, ..Default::default() };
+
+        assert_eq!(
+            Stats { artists: 3, albums: 5, songs: 62, ..Default::default() }
+                .describe_changes_since(&previous),
+            "12 songs added, 1 album added"
+        );
+        assert_eq!(
+            Stats { artists: 1, albums: 4, songs: 49, ..Default::default() }
+                .describe_changes_since(&previous),
+            "1 song removed, 2 artists removed"
+        );
+        assert_eq!(
+            previous.describe_changes_since(&previous),
+            "no songs, albums or artists added or removed"
+        );
+    }
+}
@@ -12,6 +12,8 @@ pub enum MpdError {
     ValueExpected(String),
     UnsupportedMpdVersion(&'static str),
     TimedOut(String),
+    /// Reading from or writing to the connection failed
+    Io(String),
 }
 
 impl std::error::Error for MpdError {}
@@ -21,7 +23,7 @@ impl From<std::io::Error> for MpdError {
             std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => {
                 MpdError::TimedOut(err.to_string())
             }
-            _ => MpdError::Generic(format!("{err}")),
+            _ => MpdError::Io(err.to_string()),
         }
     }
 }
@@ -45,6 +47,7 @@ impl Display for MpdError {
                 write!(f, "Unsupported MPD version: '{val}'")
             }
             MpdError::TimedOut(msg) => write!(f, "Reading response from MPD timed out, '{msg}'"),
+            MpdError::Io(msg) => write!(f, "IoError: '{msg}'"),
         }
     }
 }
@@ -1,11 +1,13 @@
 use std::{
     ops::{Range, RangeInclusive},
     str::FromStr,
+    time::Duration,
 };
 
 use anyhow::Result;
 use derive_more::Deref;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use strum::AsRefStr;
 
 use super::{
@@ -16,13 +18,16 @@ use super::{
         ListFiles,
         LsInfo,
         Mounts,
+        Neighbors,
         Playlist,
         Song,
+        Stats,
         Status,
         Update,
         Volume,
+        count::GroupedCount,
         decoders::Decoders,
-        list::MpdList,
+        list::{GroupedList, MpdList},
         list_playlist::FileList,
         mpd_config::MpdConfig,
         outputs::Outputs,
@@ -39,7 +44,6 @@ use crate::shared::{ext::error::ErrorExt, macros::status_error};
 type MpdResult<T> = Result<T, MpdError>;
 
 #[derive(AsRefStr, Debug)]
-#[allow(dead_code)]
 pub enum SaveMode {
     #[strum(serialize = "create")]
     Create,
@@ -90,7 +94,9 @@ pub trait MpdClient: Sized {
     fn commands(&mut self) -> MpdResult<MpdList>;
     fn update(&mut self, path: Option<&str>) -> MpdResult<Update>;
     fn rescan(&mut self, path: Option<&str>) -> MpdResult<Update>;
-    fn idle(&mut self, subsystem: Option<IdleEvent>) -> MpdResult<Vec<IdleEvent>>;
+    /// Waits until one of the given subsystems changes. Waits for any change
+    /// if no subsystems are given.
+    fn idle(&mut self, subsystems: &[IdleEvent]) -> MpdResult<Vec<IdleEvent>>;
     fn enter_idle(&mut self) -> MpdResult<ProtoClient<'static, '_, Self>>
     where
         Self: SocketClient;
@@ -107,6 +113,7 @@ pub trait MpdClient: Sized {
     fn volume(&mut self, change: ValueChange) -> MpdResult<()>;
     fn get_current_song(&mut self) -> MpdResult<Option<Song>>;
     fn get_status(&mut self) -> MpdResult<Status>;
+    fn stats(&mut self) -> MpdResult<Stats>;
     // Playback control
     fn pause_toggle(&mut self) -> MpdResult<()>;
     fn pause(&mut self) -> MpdResult<()>;
@@ -118,6 +125,7 @@ pub trait MpdClient: Sized {
     fn play_id(&mut self, id: u32) -> MpdResult<()>;
     fn stop(&mut self) -> MpdResult<()>;
     fn seek_current(&mut self, value: ValueChange) -> MpdResult<()>;
+    fn seek_current_to(&mut self, position: Duration) -> MpdResult<()>;
     fn repeat(&mut self, enabled: bool) -> MpdResult<()>;
     fn random(&mut self, enabled: bool) -> MpdResult<()>;
     fn single(&mut self, single: OnOffOneshot) -> MpdResult<()>;
@@ -126,12 +134,15 @@ pub trait MpdClient: Sized {
     fn mount(&mut self, name: &str, path: &str) -> MpdResult<()>;
     fn unmount(&mut self, name: &str) -> MpdResult<()>;
     fn list_mounts(&mut self) -> MpdResult<Mounts>;
+    fn list_neighbors(&mut self) -> MpdResult<Neighbors>;
     // Current queue
     fn add(&mut self, path: &str) -> MpdResult<()>;
     fn clear(&mut self) -> MpdResult<()>;
     fn delete_id(&mut self, id: u32) -> MpdResult<()>;
     fn delete_from_queue(&mut self, songs: SingleOrRange) -> MpdResult<()>;
     fn playlist_info(&mut self, fetch_stickers: bool) -> MpdResult<Option<Vec<Song>>>;
+    /// Shuffles the whole queue or only the songs in the given range
+    fn shuffle(&mut self, range: Option<SingleOrRange>) -> MpdResult<()>;
     fn find(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>>;
     fn search(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>>;
     fn move_in_queue(&mut self, from: SingleOrRange, to: QueueMoveTarget) -> MpdResult<()>;
@@ -140,6 +151,11 @@ pub trait MpdClient: Sized {
     fn find_add(&mut self, filter: &[Filter<'_>]) -> MpdResult<()>;
     fn search_add(&mut self, filter: &[Filter<'_>]) -> MpdResult<()>;
     fn list_tag(&mut self, tag: Tag, filter: Option<&[Filter<'_>]>) -> MpdResult<MpdList>;
+    /// Lists the values of the tag paired with the value of the `group` tag of
+    /// the songs they belong to
+    fn list_tag_grouped(&mut self, tag: Tag, group: Tag) -> MpdResult<GroupedList>;
+    /// Counts the songs and their total duration for every value of the tag
+    fn count_grouped(&mut self, group: Tag) -> MpdResult<GroupedCount>;
     // Database
     fn lsinfo(&mut self, path: Option<&str>) -> MpdResult<LsInfo>;
     fn list_files(&mut self, path: Option<&str>) -> MpdResult<ListFiles>;
@@ -181,6 +197,10 @@ pub trait MpdClient: Sized {
     fn toggle_output(&mut self, id: u32) -> MpdResult<()>;
     fn enable_output(&mut self, id: u32) -> MpdResult<()>;
     fn disable_output(&mut self, id: u32) -> MpdResult<()>;
+    /// Enables the output and disables all of the other ones in a single
+    /// command list
+    fn solo_output(&mut self, id: u32) -> MpdResult<()>;
+    fn set_output_attribute(&mut self, id: u32, name: &str, value: &str) -> MpdResult<()>;
     // Decoders
     fn decoders(&mut self) -> MpdResult<Decoders>;
     // Stickers
@@ -228,7 +248,8 @@ impl MpdClient for Client<'_> {
     }
 
     fn config(&mut self) -> Option<&MpdConfig> {
-        if self.config.is_none() {
+        if !self.config_fetched {
+            self.config_fetched = true;
             match self.send("config").and_then(read_response) {
                 Ok(config) => {
                     self.config = Some(config);
@@ -272,11 +293,11 @@ impl MpdClient for Client<'_> {
     }
 
     // Queries
-    fn idle(&mut self, subsystem: Option<IdleEvent>) -> MpdResult<Vec<IdleEvent>> {
-        if let Some(subsystem) = subsystem {
-            self.send(&format!("idle {subsystem}")).and_then(read_response)
-        } else {
+    fn idle(&mut self, subsystems: &[IdleEvent]) -> MpdResult<Vec<IdleEvent>> {
+        if subsystems.is_empty() {
             self.send("idle").and_then(read_response)
+        } else {
+            self.send(&format!("idle {}", subsystems.iter().join(" "))).and_then(read_response)
         }
     }
 
@@ -320,6 +341,10 @@ impl MpdClient for Client<'_> {
         self.send("status").and_then(read_response)
     }
 
+    fn stats(&mut self) -> MpdResult<Stats> {
+        self.send("stats").and_then(read_response)
+    }
+
     // Playback control
     fn pause_toggle(&mut self) -> MpdResult<()> {
         self.send("pause").and_then(read_ok)
@@ -361,6 +386,10 @@ impl MpdClient for Client<'_> {
         self.send(&format!("seekcur {}", value.to_mpd_str())).and_then(read_ok)
     }
 
+    fn seek_current_to(&mut self, position: Duration) -> MpdResult<()> {
+        self.send(&format!("seekcur {:.3}", position.as_secs_f64())).and_then(read_ok)
+    }
+
     fn repeat(&mut self, enabled: bool) -> MpdResult<()> {
         self.send(&format!("repeat {}", u8::from(enabled))).and_then(read_ok)
     }
@@ -395,6 +424,10 @@ impl MpdClient for Client<'_> {
         self.send("listmounts").and_then(read_response)
     }
 
+    fn list_neighbors(&mut self) -> MpdResult<Neighbors> {
+        self.send("listneighbors").and_then(read_response)
+    }
+
     // Current queue
     fn add(&mut self, uri: &str) -> MpdResult<()> {
         self.send(&format!("add {}", uri.quote_and_escape())).and_then(read_ok)
@@ -445,6 +478,14 @@ impl MpdClient for Client<'_> {
         Ok(Some(songs))
     }
 
+    fn shuffle(&mut self, range: Option<SingleOrRange>) -> MpdResult<()> {
+        if let Some(range) = range {
+            self.send(&format!("shuffle {}", range.as_mpd_range())).and_then(read_ok)
+        } else {
+            self.send("shuffle").and_then(read_ok)
+        }
+    }
+
     /// Search the database for songs matching FILTER
     fn find(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>> {
         self.send(&format!("find \"({})\"", filter.to_query_str())).and_then(read_response)
@@ -498,6 +539,15 @@ impl MpdClient for Client<'_> {
         .and_then(read_response)
     }
 
+    fn list_tag_grouped(&mut self, tag: Tag, group: Tag) -> MpdResult<GroupedList> {
+        self.send(&format!("list {} group {}", tag.as_str(), group.as_str()))
+            .and_then(read_response)
+    }
+
+    fn count_grouped(&mut self, group: Tag) -> MpdResult<GroupedCount> {
+        self.send(&format!("count group {}", group.as_str())).and_then(read_response)
+    }
+
     // Database
     fn lsinfo(&mut self, path: Option<&str>) -> MpdResult<LsInfo> {
         Ok(if let Some(path) = path {
@@ -682,6 +732,38 @@ impl MpdClient for Client<'_> {
         self.send(&format!("disableoutput {id}")).and_then(read_ok)
     }
 
+    fn solo_output(&mut self, id: u32) -> MpdResult<()> {
+        let outputs = self.outputs()?.0;
+        // soloing an unknown output would disable all of them
+        if !outputs.iter().any(|output| output.id == id) {
+            return Err(MpdError::Generic(format!("Output with id '{id}' does not exist")));
+        }
+
+        self.start_cmd_list()?;
+        for output in &outputs {
+            if output.id == id {
+                self.send(&format!("enableoutput {}", output.id))?;
+            } else {
+                self.send(&format!("disableoutput {}", output.id))?;
+            }
+        }
+
+        let mut proto = self.execute_cmd_list()?;
+        for _ in &outputs {
+            proto.read_ok()?;
+        }
+        proto.read_ok()
+    }
+
+    fn set_output_attribute(&mut self, id: u32, name: &str, value: &str) -> MpdResult<()> {
+        self.send(&format!(
+            "outputset {id} {} {}",
+            name.quote_and_escape(),
+            value.quote_and_escape()
+        ))
+        .and_then(read_ok)
+    }
+
     // Decoders
     fn decoders(&mut self) -> MpdResult<Decoders> {
         self.send("decoders").and_then(read_response)
@@ -793,8 +875,7 @@ impl MpdClient for Client<'_> {
     }
 }
 
-#[derive(Debug)]
-#[allow(dead_code)]
+#[derive(Debug, PartialEq)]
 pub enum QueueMoveTarget {
     /// relative to the currently playing song; e.g. +0 moves to right after the
     /// current song
@@ -805,6 +886,22 @@ pub enum QueueMoveTarget {
     Absolute(usize),
 }
 
+impl FromStr for QueueMoveTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            v if v.starts_with('-') => {
+                Ok(QueueMoveTarget::RelativeSub(v.trim_start_matches('-').parse()?))
+            }
+            v if v.starts_with('+') => {
+                Ok(QueueMoveTarget::RelativeAdd(v.trim_start_matches('+').parse()?))
+            }
+            v => Ok(QueueMoveTarget::Absolute(v.parse()?)),
+        }
+    }
+}
+
 impl QueueMoveTarget {
     fn as_mpd_str(&self) -> String {
         match self {
@@ -833,6 +930,23 @@ impl From<Range<usize>> for SingleOrRange {
     }
 }
 
+impl FromStr for SingleOrRange {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((start, end)) => {
+                let (start, end) = (start.trim().parse()?, end.trim().parse()?);
+                if start >= end {
+                    anyhow::bail!("Invalid range '{s}', start must be lower than end");
+                }
+                Ok(Self::range(start, end))
+            }
+            None => Ok(Self::single(s.trim().parse()?)),
+        }
+    }
+}
+
 #[derive(Deref)]
 pub struct Ranges(Vec<SingleOrRange>);
 
@@ -883,7 +997,7 @@ impl Tag {
     }
 }
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum FilterKind {
     Exact,
@@ -1000,6 +1114,36 @@ impl FilterExt for &[Filter<'_>] {
     }
 }
 
+#[cfg(test)]
+mod range_tests {
+    use test_case::test_case;
+
+    use super::{QueueMoveTarget, SingleOrRange};
+
+    #[test_case("5", SingleOrRange::single(5))]
+    #[test_case("2:7", SingleOrRange::range(2, 7))]
+    #[test_case(" 0 : 1 ", SingleOrRange::range(0, 1))]
+    fn parses_single_or_range(input: &str, expected: SingleOrRange) {
+        assert_eq!(input.parse::<SingleOrRange>().ok(), Some(expected));
+    }
+
+    #[test_case("")]
+    #[test_case("a")]
+    #[test_case("5:")]
+    #[test_case("5:5")]
+    #[test_case("7:2")]
+    fn rejects_invalid_range(input: &str) {
+        assert!(input.parse::<SingleOrRange>().is_err());
+    }
+
+    #[test_case("3", QueueMoveTarget::Absolute(3))]
+    #[test_case("+0", QueueMoveTarget::RelativeAdd(0))]
+    #[test_case("-2", QueueMoveTarget::RelativeSub(2))]
+    fn parses_queue_move_target(input: &str, expected: QueueMoveTarget) {
+        assert_eq!(input.parse::<QueueMoveTarget>().ok(), Some(expected));
+    }
+}
+
 #[cfg(test)]
 mod strext_tests {
     use crate::mpd::mpd_client::StrExt;
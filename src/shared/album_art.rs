@@ -0,0 +1,258 @@
+use std::{
+    io::Cursor,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result};
+use image::DynamicImage;
+
+use super::image::jpg_encode;
+use crate::{
+    config::{Size, album_art::AlbumArtConfig},
+    mpd::{commands::Song, mpd_client::MpdClient},
+};
+
+const COVER_CACHE_DIR: &str = "covers";
+
+/// Resolves the album art of the song. Looks into the cover cache in
+/// `cache_dir` first, then for image files next to the song when MPD's
+/// `music_directory` is reachable and finally asks MPD itself if it supports
+/// it. Found album art is stored in the cover cache resized to
+/// `max_size_px`.
+pub fn find_album_art(
+    client: &mut impl MpdClient,
+    song: &Song,
+    config: &AlbumArtConfig,
+    cache_dir: Option<&str>,
+    mpd_supported: bool,
+) -> Result<Option<Vec<u8>>> {
+    let song_uri = song.file.as_str();
+    let cache_path = cache_dir.map(|dir| cover_cache_path(dir, song));
+    if let Some(path) = &cache_path {
+        match read_cached_cover(path, config.cache_max_age) {
+            Ok(Some(data)) => {
+                log::debug!(path:?; "Found album art in cover cache");
+                return Ok(Some(data));
+            }
+            Ok(None) => {}
+            Err(err) => log::warn!(err:?, path:?; "Failed to read cached album art"),
+        }
+    }
+
+    let music_dir = client.config().map(|config| config.music_directory.clone());
+    let data = match music_dir
+        .and_then(|dir| find_local_cover(Path::new(&dir), song_uri, &config.cover_names))
+    {
+        Some(data) => Some(data),
+        None if mpd_supported => client.find_album_art(song_uri)?,
+        None => None,
+    };
+
+    if let (Some(data), Some(path)) = (&data, &cache_path) {
+        if let Err(err) = cache_cover(data, path, config.max_size_px) {
+            log::warn!(err:?, path:?; "Failed to store album art in cover cache");
+        }
+    }
+
+    Ok(data)
+}
+
+/// Reads the cached cover, entries older than `max_age` are treated as
+/// missing so that they are fetched again
+fn read_cached_cover(path: &Path, max_age: Option<Duration>) -> Result<Option<Vec<u8>>> {
+    let modified = match std::fs::metadata(path).and_then(|metadata| metadata.modified()) {
+        Ok(modified) => modified,
+        Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let age = SystemTime::now().duration_since(modified).unwrap_or_default();
+    if max_age.is_some_and(|max_age| age > max_age) {
+        log::debug!(path:?, age:?; "Cached album art expired");
+        return Ok(None);
+    }
+
+    Ok(Some(std::fs::read(path)?))
+}
+
+/// All songs of an album share its cover, so the cache is keyed by the album
+/// and its artist. Songs without an album are cached by their uri.
+fn cover_cache_path(cache_dir: &str, song: &Song) -> PathBuf {
+    let key = match song.album() {
+        Some(album) => {
+            let artist = song.metadata.get("albumartist").or_else(|| song.artist());
+            format!("{}\0{album}", artist.map_or("", String::as_str))
+        }
+        None => song.file.clone(),
+    };
+    Path::new(cache_dir).join(COVER_CACHE_DIR).join(format!("{:016x}", fnv1a(key.as_bytes())))
+}
+
+/// 64-bit FNV-1a hash. Unlike the std hashers its output does not change
+/// between Rust versions which keeps the cache file names stable.
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Reads the first existing image with one of the `names` from the directory
+/// of the song
+fn find_local_cover(music_dir: &Path, song_uri: &str, names: &[&str]) -> Option<Vec<u8>> {
+    if song_uri.contains("://") {
+        return None;
+    }
+
+    let dir = music_dir.join(song_uri);
+    let dir = dir.parent()?;
+    names.iter().map(|name| dir.join(name)).find_map(|path| match std::fs::read(&path) {
+        Ok(data) => {
+            log::debug!(path:?; "Found local album art");
+            Some(data)
+        }
+        Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => None,
+        Err(err) => {
+            log::warn!(err:?, path:?; "Failed to read local album art");
+            None
+        }
+    })
+}
+
+/// Stores the image in the cover cache, downscaling it to fit into
+/// `max_size_px` first if it is larger
+fn cache_cover(data: &[u8], path: &Path, max_size_px: Size) -> Result<()> {
+    let image = image::ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .context("Unable to guess image format")?
+        .decode()
+        .context("Unable to decode image")?;
+
+    let (max_width, max_height) = (u32::from(max_size_px.width), u32::from(max_size_px.height));
+    let resized;
+    let data = if image.width() > max_width || image.height() > max_height {
+        let image = image.resize(max_width, max_height, image::imageops::FilterType::Lanczos3);
+        resized = jpg_encode(&DynamicImage::ImageRgb8(image.to_rgb8()))?;
+        &resized
+    } else {
+        data
+    };
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, data)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{
+        io::Cursor,
+        time::{Duration, SystemTime},
+    };
+
+    use image::{DynamicImage, ImageFormat, RgbImage};
+    use rstest::rstest;
+
+    use super::{cache_cover, cover_cache_path, find_local_cover, fnv1a, read_cached_cover};
+    use crate::{
+        config::Size,
+        mpd::commands::Song,
+        tests::fixtures::{TempDir, temp_dir},
+    };
+
+    fn song(file: &str, album: Option<&str>) -> Song {
+        Song {
+            file: file.to_owned(),
+            metadata: album
+                .into_iter()
+                .map(|album| ("album".to_owned(), album.to_owned()))
+                .chain([("albumartist".to_owned(), "artist".to_owned())])
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(width, height))
+            .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+            .unwrap();
+        data
+    }
+
+    #[rstest]
+    fn finds_cover_next_to_song(#[from(temp_dir)] dir: TempDir) {
+        std::fs::create_dir_all(dir.join("artist").join("album")).unwrap();
+        std::fs::write(dir.join("artist").join("album").join("folder.png"), "folder").unwrap();
+
+        let names = ["cover.jpg", "folder.png"];
+        let found = find_local_cover(&dir, "artist/album/song.flac", &names);
+        let not_found = find_local_cover(&dir, "artist/song.flac", &names);
+        let stream = find_local_cover(&dir, "https://artist/album/song.flac", &names);
+
+        assert_eq!(found, Some(b"folder".to_vec()));
+        assert_eq!(not_found, None);
+        assert_eq!(stream, None);
+    }
+
+    #[rstest]
+    fn caches_resized_cover(#[from(temp_dir)] dir: TempDir) {
+        let large = cover_cache_path(dir.to_str().unwrap(), &song("large.flac", None));
+        let small = cover_cache_path(dir.to_str().unwrap(), &song("small.flac", None));
+        let max_size_px = Size { width: 100, height: 100 };
+
+        cache_cover(&png(400, 200), &large, max_size_px).unwrap();
+        cache_cover(&png(50, 50), &small, max_size_px).unwrap();
+        let large = image::load_from_memory(&std::fs::read(large).unwrap()).unwrap();
+        let small = std::fs::read(small).unwrap();
+
+        assert_eq!((large.width(), large.height()), (100, 50));
+        assert_eq!(small, png(50, 50));
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn caches_covers_by_album() {
+        let first = cover_cache_path("/cache", &song("album/1.flac", Some("album")));
+        let second = cover_cache_path("/cache", &song("album/2.flac", Some("album")));
+        let other = cover_cache_path("/cache", &song("other/1.flac", Some("other")));
+        let no_album = cover_cache_path("/cache", &song("album/3.flac", None));
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_ne!(first, no_album);
+    }
+
+    #[rstest]
+    fn expires_cached_covers(#[from(temp_dir)] dir: TempDir) {
+        let path = cover_cache_path(dir.to_str().unwrap(), &song("song.flac", None));
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "cover").unwrap();
+        let two_hours_ago = SystemTime::now() - Duration::from_secs(2 * 3600);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(two_hours_ago)
+            .unwrap();
+
+        let kept = read_cached_cover(&path, None).unwrap();
+        let fresh = read_cached_cover(&path, Some(Duration::from_secs(3 * 3600))).unwrap();
+        let expired = read_cached_cover(&path, Some(Duration::from_secs(3600))).unwrap();
+        let missing = read_cached_cover(&dir.join("missing"), None).unwrap();
+
+        assert_eq!(kept, Some(b"cover".to_vec()));
+        assert_eq!(fresh, Some(b"cover".to_vec()));
+        assert_eq!(expired, None);
+        assert_eq!(missing, None);
+    }
+}
@@ -16,8 +16,10 @@ pub static PYTHON3MUTAGEN: LazyLock<Dep> = LazyLock::new(|| {
     ])
 });
 
-pub static DEPENDENCIES: [&std::sync::LazyLock<Dep>; 6] =
-    [&FFMPEG, &FFPROBE, &YTDLP, &UEBERZUGPP, &PYTHON3, &PYTHON3MUTAGEN];
+pub static CURL: LazyLock<Dep> = LazyLock::new(|| Dep::new("curl", "curl", &["--version"]));
+
+pub static DEPENDENCIES: [&std::sync::LazyLock<Dep>; 7] =
+    [&FFMPEG, &FFPROBE, &YTDLP, &UEBERZUGPP, &PYTHON3, &PYTHON3MUTAGEN, &CURL];
 
 pub fn is_youtube_supported(mpd_address: MpdAddress) -> Result<(), Vec<String>> {
     let mut unsupported = Vec::new();
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 use anyhow::Result;
 use crossterm::event::KeyEvent;
@@ -10,8 +10,8 @@ use super::{
     mpd_query::{MpdCommand, MpdQuery, MpdQueryResult, MpdQuerySync},
 };
 use crate::{
-    config::{cli::Command, tabs::PaneType},
-    mpd::commands::IdleEvent,
+    config::{Config, cli::Command, tabs::PaneType},
+    mpd::commands::{IdleEvent, Song},
     ui::UiAppEvent,
 };
 
@@ -21,6 +21,9 @@ pub(crate) enum ClientRequest {
     Query(MpdQuery),
     QuerySync(MpdQuerySync),
     Command(MpdCommand),
+    /// Disconnects from the current MPD server and connects to the one of the
+    /// given config instead
+    SwitchServer(&'static Config),
 }
 
 #[derive(Debug)]
@@ -28,12 +31,34 @@ pub(crate) enum ClientRequest {
 pub(crate) enum WorkRequest {
     IndexLyrics {
         lyrics_dir: &'static str,
+        /// Directory where the index is persisted between runs
+        cache_dir: Option<&'static str>,
     },
     IndexSingleLrc {
         /// Absolute path to the lrc file
         path: PathBuf,
     },
+    FetchLyrics {
+        /// LRCLIB compatible endpoint to fetch the lyrics from
+        url: &'static str,
+        song: Song,
+        /// Absolute path where the fetched lrc file should be written to
+        path: PathBuf,
+        /// Songs without lyrics are searched again after this long
+        not_found_max_age: Option<Duration>,
+    },
     Command(Command),
+    /// Writes the tags to the songs and updates them in MPD afterwards
+    WriteTags {
+        /// External command writing the tags, python's mutagen if `None`
+        command: Option<&'static [&'static str]>,
+        music_directory: String,
+        songs: Vec<Song>,
+        tags: Vec<(String, String)>,
+    },
+    /// Replaces the config the worker was started with after switching to
+    /// another profile
+    ConfigChanged(&'static Config),
 }
 
 #[derive(Debug)]
@@ -54,13 +79,18 @@ pub(crate) enum AppEvent {
     IdleEvent(IdleEvent),
     RequestRender,
     Resized { columns: u16, rows: u16 },
-    WorkDone(Result<WorkDone>),
+    WorkDone(Result<Box<WorkDone>>),
     UiEvent(UiAppEvent),
     Reconnected,
     LostConnection,
+    ReloadConfig,
+    SwitchProfile(&'static str),
+    ServerSwitched(&'static Config),
+    ServerSwitchFailed,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Copy, Eq, Hash, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, Eq, Hash, PartialEq, strum::Display)]
+#[strum(serialize_all = "lowercase")]
 #[allow(dead_code)]
 pub enum Level {
     Trace,
@@ -27,6 +27,7 @@ pub mod error {
                     format!("Unsuported MPD version: {e}")
                 }
                 MpdError::TimedOut(_) => "Request to MPD timed out".to_string(),
+                MpdError::Io(e) => format!("Connection error: {e}"),
             }
         }
     }
@@ -132,13 +133,20 @@ pub mod iter {
 }
 
 pub mod mpd_client {
-    use crate::mpd::{
-        errors::{ErrorCode, MpdError, MpdFailureResponse},
-        mpd_client::MpdClient,
+    use crate::{
+        mpd::{
+            commands::Update,
+            errors::{ErrorCode, MpdError, MpdFailureResponse},
+            mpd_client::MpdClient,
+        },
+        shared::macros::status_info,
     };
 
     pub trait MpdClientExt {
         fn play_last(&mut self, queue_len: usize) -> Result<(), MpdError>;
+        /// Starts updating or rescanning the given path or the whole database
+        /// and informs the user about it
+        fn start_db_update(&mut self, path: Option<&str>, rescan: bool) -> Result<(), MpdError>;
     }
 
     impl<T: MpdClient> MpdClientExt for T {
@@ -157,6 +165,17 @@ pub mod mpd_client {
             };
             Ok(())
         }
+
+        fn start_db_update(&mut self, path: Option<&str>, rescan: bool) -> Result<(), MpdError> {
+            let Update { job_id } = if rescan { self.rescan(path)? } else { self.update(path)? };
+            let kind = if rescan { "rescan" } else { "update" };
+            if let Some(path) = path {
+                status_info!("Database {kind} #{job_id} of '{path}' started");
+            } else {
+                status_info!("Database {kind} #{job_id} started");
+            }
+            Ok(())
+        }
     }
 }
 
@@ -3,7 +3,7 @@ use crossterm::event::{KeyCode, KeyEvent as CKeyEvent};
 #[cfg(debug_assertions)]
 use crate::config::keys::LogsActions;
 use crate::{
-    config::keys::{CommonAction, GlobalAction, QueueActions},
+    config::keys::{CommonAction, DirectoriesActions, GlobalAction, LyricsActions, QueueActions},
     context::AppContext,
 };
 
@@ -76,4 +76,26 @@ impl KeyEvent {
             None
         }
     }
+
+    pub fn as_directories_action(&mut self, context: &AppContext) -> Option<DirectoriesActions> {
+        if self.already_handled {
+            None
+        } else if let Some(action) = context.config.keybinds.directories.get(&self.inner.into()) {
+            self.already_handled = true;
+            Some(*action)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_lyrics_action(&mut self, context: &AppContext) -> Option<LyricsActions> {
+        if self.already_handled {
+            None
+        } else if let Some(action) = context.config.keybinds.lyrics.get(&self.inner.into()) {
+            self.already_handled = true;
+            Some(*action)
+        } else {
+            None
+        }
+    }
 }
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+
+use super::with_metadata;
+use crate::mpd::commands::Song;
+
+/// Shifts the timing of the lrc text by `delta` milliseconds by updating its
+/// `offset` tag or adding one if it is missing. Returns the updated text along
+/// with the new offset.
+pub fn adjust_offset(text: &str, delta: i64) -> Result<(String, i64)> {
+    let mut offset = None;
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        match line.trim().strip_prefix("[offset:").and_then(|rest| rest.strip_suffix(']')) {
+            Some(current) if offset.is_none() => {
+                let current: i64 = current
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Invalid lrc offset: '{current}'"))?;
+                offset = Some(current + delta);
+                lines.push(format!("[offset:{:+}]", current + delta));
+            }
+            _ => lines.push(line.to_owned()),
+        }
+    }
+
+    let offset = offset.unwrap_or_else(|| {
+        // offset applies only to the timestamps after it
+        lines.insert(0, format!("[offset:{delta:+}]"));
+        delta
+    });
+
+    Ok((lines.join("\n") + "\n", offset))
+}
+
+/// Creates lrc text from lines stamped with the time at which they start
+pub fn synced_lrc(song: &Song, lines: &[(Duration, String)]) -> String {
+    let lyrics =
+        lines.iter().map(|(time, line)| format!("[{}]{line}", format_time(*time))).join("\n");
+    with_metadata(song, &lyrics)
+}
+
+fn format_time(time: Duration) -> String {
+    let hundreths = time.as_millis() / 10;
+    format!("{:02}:{:02}.{:02}", hundreths / 6000, hundreths / 100 % 60, hundreths % 100)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use super::{adjust_offset, synced_lrc};
+    use crate::{mpd::commands::Song, shared::lrc::Lrc};
+
+    #[test]
+    fn adds_missing_offset() {
+        let (result, offset) = adjust_offset("[ti:title]\n[00:01.00]line\n", 100).unwrap();
+
+        assert_eq!(offset, 100);
+        assert_eq!(result, "[offset:+100]\n[ti:title]\n[00:01.00]line\n");
+        let lrc: Lrc = result.parse().unwrap();
+        assert_eq!(lrc.lines[0].time, Duration::from_millis(900));
+    }
+
+    #[test]
+    fn updates_existing_offset() {
+        let (result, offset) = adjust_offset("[offset: +50]\n[00:01.00]line", -100).unwrap();
+
+        assert_eq!(offset, -50);
+        assert_eq!(result, "[offset:-50]\n[00:01.00]line\n");
+    }
+
+    #[test]
+    fn synced_lrc_can_be_parsed() {
+        let song = Song {
+            file: "song.flac".to_string(),
+            duration: Some(Duration::from_secs(125)),
+            metadata: HashMap::from([("title".to_string(), "title".to_string())]),
+            ..Default::default()
+        };
+
+        let result = synced_lrc(&song, &[
+            (Duration::from_millis(1230), "first".to_string()),
+            (Duration::from_secs(61), "second".to_string()),
+        ]);
+
+        assert_eq!(result, "[ti:title]\n[length:2:05]\n\n[00:01.23]first\n[01:01.00]second\n");
+        let lrc: Lrc = result.parse().unwrap();
+        assert_eq!(lrc.lines[1].time, Duration::from_secs(61));
+    }
+}
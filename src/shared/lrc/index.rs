@@ -1,24 +1,30 @@
 use std::{
+    collections::BTreeMap,
     io::{BufRead, BufReader},
-    path::PathBuf,
-    time::Duration,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
 };
 
 use anyhow::{Context, Result, bail};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use walkdir::WalkDir;
 
-use super::{Lrc, parse_length};
+use super::{Lrc, is_translation, parse_length};
 use crate::{mpd::commands::Song, shared::macros::try_cont};
 
-#[derive(Debug, Eq, PartialEq, Default, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Default, Serialize, Deserialize)]
 pub struct LrcIndex {
-    index: Vec<LrcIndexEntry>,
+    /// Entries keyed by the path of their file, persisted as a list
+    #[serde(with = "entries")]
+    index: BTreeMap<PathBuf, LrcIndexEntry>,
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct LrcIndexEntry {
     pub path: PathBuf,
+    /// Modification time of the file when it was indexed
+    #[serde(default)]
+    pub mtime: Option<SystemTime>,
     /// ti
     pub title: String,
     /// ar
@@ -31,14 +37,32 @@ pub struct LrcIndexEntry {
 
 impl LrcIndex {
     pub fn index(lyrics_dir: &PathBuf) -> Self {
+        Self::update(lyrics_dir, Self::default())
+    }
+
+    /// Indexes the `lyrics_dir` reusing entries of the previous index whose
+    /// files were not modified since. Files without enough metadata are not
+    /// part of the index and are thus always read again.
+    pub fn update(lyrics_dir: &PathBuf, previous: Self) -> Self {
         let start = std::time::Instant::now();
         let dir = WalkDir::new(lyrics_dir);
         log::info!(dir:?; "Starting lyrics index lyrics");
 
-        let mut index = Vec::new();
+        let mut previous = previous.index;
+        let mut reused_count = 0;
+        let mut index = BTreeMap::new();
         for entry in dir {
             let entry = try_cont!(entry, "skipping entry");
 
+            if let Some(previous_entry) = previous.remove(entry.path()) {
+                let mtime = entry.metadata().ok().and_then(|meta| meta.modified().ok());
+                if mtime.is_some() && previous_entry.mtime == mtime {
+                    reused_count += 1;
+                    index.insert(previous_entry.path.clone(), previous_entry);
+                    continue;
+                }
+            }
+
             let index_entry =
                 try_cont!(Self::index_single(entry.path().to_path_buf()), "Failed to index entry");
 
@@ -48,18 +72,36 @@ impl LrcIndex {
             };
 
             log::trace!(entry:?; "Successfully indexed entry");
-            index.push(index_entry);
+            index.insert(index_entry.path.clone(), index_entry);
         }
 
-        log::info!(found_count = index.len(), elapsed:? = start.elapsed(); "Indexed lrc files");
+        log::info!(found_count = index.len(), reused_count, elapsed:? = start.elapsed(); "Indexed lrc files");
         Self { index }
     }
 
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path).context("Failed to open lyrics index")?;
+        serde_json::from_reader(BufReader::new(file)).context("Failed to read lyrics index")
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = std::fs::File::create(path).context("Failed to create lyrics index")?;
+        serde_json::to_writer(std::io::BufWriter::new(file), self)
+            .context("Failed to write lyrics index")
+    }
+
     pub fn index_single(path: PathBuf) -> Result<Option<LrcIndexEntry>> {
         if path.extension().is_none_or(|ext| !ext.to_string_lossy().ends_with("lrc")) {
             log::trace!(path:?; "skipping non lrc file");
             return Ok(None);
         }
+        if is_translation(&path) {
+            log::trace!(path:?; "skipping lyrics translation");
+            return Ok(None);
+        }
         let file = std::fs::File::open(&path).context("failed to open entry file")?;
 
         log::trace!(file:?, entry:? = path; "Trying to index lyrics entry");
@@ -68,13 +110,17 @@ impl LrcIndex {
     }
 
     pub fn find_lrc_for_song(&self, song: &Song) -> Result<Option<Lrc>> {
+        self.find_lrc_path_for_song(song)
+            .map_or(Ok(None), |path| Ok(Some(std::fs::read_to_string(path)?.parse()?)))
+    }
+
+    pub fn find_lrc_path_for_song(&self, song: &Song) -> Option<&PathBuf> {
         match (song.artist(), song.title(), song.album(), song.duration) {
             (Some(artist), Some(title), Some(album), length) => {
-                self.find_lrc(artist, title, album, length)
+                self.find_lrc(artist, title, album, length).map(|entry| &entry.path)
             }
             _ => None,
         }
-        .map_or(Ok(None), |lrc| Ok(Some(std::fs::read_to_string(&lrc.path)?.parse()?)))
     }
 
     fn find_lrc(
@@ -84,7 +130,7 @@ impl LrcIndex {
         album: &str,
         length: Option<Duration>,
     ) -> Option<&LrcIndexEntry> {
-        self.index.iter().find(|entry| {
+        self.index.values().find(|entry| {
             log::trace!(entry:?; "searching entry");
 
             let length_matches = match (entry.length, length) {
@@ -94,12 +140,41 @@ impl LrcIndex {
                 _ => true,
             };
 
-            length_matches && entry.artist == artist && entry.title == title && entry.album == album
+            length_matches
+                && entry.artist == artist
+                && entry.title == title
+                && entry.album == album
+                // the file might have been removed since it was indexed
+                && entry.path.exists()
         })
     }
 
+    /// Adds the entry to the index, replacing the previous one for the same
+    /// file
     pub(crate) fn add(&mut self, entry: LrcIndexEntry) {
-        self.index.push(entry);
+        self.index.insert(entry.path.clone(), entry);
+    }
+}
+
+mod entries {
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::LrcIndexEntry;
+
+    pub fn serialize<S: Serializer>(
+        index: &BTreeMap<PathBuf, LrcIndexEntry>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(index.values())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<PathBuf, LrcIndexEntry>, D::Error> {
+        let entries = Vec::<LrcIndexEntry>::deserialize(deserializer)?;
+        Ok(entries.into_iter().map(|entry| (entry.path.clone(), entry)).collect())
     }
 }
 
@@ -157,6 +232,35 @@ impl LrcIndexEntry {
             return Ok(None);
         };
 
-        Ok(Some(Self { path, title, artist, album, length }))
+        let mtime = std::fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+        Ok(Some(Self { path, mtime, title, artist, album, length }))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use rstest::rstest;
+
+    use super::LrcIndex;
+    use crate::tests::fixtures::{TempDir, temp_dir};
+
+    #[rstest]
+    fn update_reuses_unmodified_entries_and_drops_removed_files(#[from(temp_dir)] dir: TempDir) {
+        let song = dir.join("song.lrc");
+        let removed = dir.join("removed.lrc");
+        std::fs::write(&song, "[ar:artist]\n[al:album]\n[ti:title]\n[00:01.00]line\n").unwrap();
+        std::fs::write(&removed, "[ar:a]\n[al:b]\n[ti:c]\n[00:01.00]line\n").unwrap();
+
+        let mut previous = LrcIndex::index(&dir);
+        std::fs::remove_file(&removed).unwrap();
+        for entry in previous.index.values_mut() {
+            entry.title = "cached".to_owned();
+        }
+        let result = LrcIndex::update(&dir, previous);
+
+        assert_eq!(result.index.len(), 1);
+        assert_eq!(result.index[&song].path, song);
+        assert_eq!(result.index[&song].title, "cached");
     }
 }
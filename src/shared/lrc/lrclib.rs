@@ -0,0 +1,181 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use super::with_metadata;
+use crate::{mpd::commands::Song, shared::dependencies::CURL};
+
+const NOT_FOUND_FILE: &str = "lyrics_not_found.json";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LrclibResponse {
+    synced_lyrics: Option<String>,
+}
+
+/// Queries an LRCLIB compatible API for synced lyrics of the song and returns
+/// them as the content of a lrc file. Returns `None` if the API does not know
+/// the song or has no synced lyrics for it.
+pub fn fetch_lrc(url: &str, song: &Song) -> Result<Option<String>> {
+    let (Some(artist), Some(title)) = (song.artist(), song.title()) else {
+        log::debug!(file = song.file.as_str(); "Not fetching lyrics, song has no artist or title");
+        return Ok(None);
+    };
+
+    if !CURL.installed {
+        bail!("Fetching lyrics requires curl but it was not found on PATH");
+    }
+
+    let mut command = Command::new("curl");
+    command.args([
+        "--silent",
+        "--show-error",
+        "--get",
+        "--max-time",
+        "10",
+        "--user-agent",
+        concat!("rmpc/", env!("CARGO_PKG_VERSION")),
+        "--write-out",
+        "\n%{http_code}",
+    ]);
+    command.arg("--data-urlencode").arg(format!("artist_name={artist}"));
+    command.arg("--data-urlencode").arg(format!("track_name={title}"));
+    if let Some(album) = song.album() {
+        command.arg("--data-urlencode").arg(format!("album_name={album}"));
+    }
+    if let Some(duration) = song.duration {
+        command.arg("--data-urlencode").arg(format!("duration={}", duration.as_secs()));
+    }
+    command.arg(url);
+
+    log::debug!(url, artist = artist.as_str(), title = title.as_str(); "Fetching lyrics");
+    let out = command.output()?;
+    if !out.status.success() {
+        bail!("Failed to fetch lyrics: '{}'", String::from_utf8_lossy(&out.stderr).trim());
+    }
+
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let (body, status) = stdout.rsplit_once('\n').context("Unexpected output from curl")?;
+    match status.trim() {
+        "200" => {
+            let response: LrclibResponse =
+                serde_json::from_str(body).context("Invalid response from the lyrics API")?;
+
+            Ok(response
+                .synced_lyrics
+                .filter(|lyrics| !lyrics.trim().is_empty())
+                .map(|lyrics| with_metadata(song, &lyrics)))
+        }
+        "404" => Ok(None),
+        status => bail!("Lyrics API responded with unexpected status '{status}'"),
+    }
+}
+
+/// Songs for which the lyrics API did not return any lyrics together with
+/// the unix timestamp of the last attempt, so they are not queried again too
+/// soon. Persisted in the cache directory if it is configured.
+#[derive(Debug, Default)]
+pub struct LyricsNotFound {
+    path: Option<PathBuf>,
+    songs: HashMap<String, u64>,
+}
+
+impl LyricsNotFound {
+    pub fn load(cache_dir: Option<&str>) -> Self {
+        let Some(path) = cache_dir.map(|dir| PathBuf::from(format!("{dir}{NOT_FOUND_FILE}")))
+        else {
+            return Self::default();
+        };
+
+        let songs = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|err| {
+                log::error!(err:?, path:?; "Failed to parse cached songs without lyrics");
+                HashMap::new()
+            }),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(err) => {
+                log::error!(err:?, path:?; "Failed to read cached songs without lyrics");
+                HashMap::new()
+            }
+        };
+
+        Self { path: Some(path), songs }
+    }
+
+    /// Whether the lyrics of the song were not found within `max_age`, or
+    /// ever if there is no maximum age
+    pub fn contains(&self, song_file: &str, max_age: Option<Duration>) -> bool {
+        self.songs.get(song_file).is_some_and(|&timestamp| {
+            max_age.is_none_or(|max_age| now().saturating_sub(timestamp) < max_age.as_secs())
+        })
+    }
+
+    pub fn insert(&mut self, song_file: String) -> Result<()> {
+        self.songs.insert(song_file, now());
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_vec(&self.songs)?)?;
+        Ok(())
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use super::{LyricsNotFound, now, with_metadata};
+    use crate::{mpd::commands::Song, shared::lrc::Lrc};
+
+    #[test]
+    fn not_found_songs_expire() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let mut not_found = LyricsNotFound::default();
+        not_found.songs.insert("old.flac".to_owned(), now() - 8 * day.as_secs());
+        not_found.songs.insert("new.flac".to_owned(), now() - day.as_secs());
+
+        assert!(!not_found.contains("old.flac", Some(7 * day)));
+        assert!(not_found.contains("new.flac", Some(7 * day)));
+        assert!(not_found.contains("old.flac", None));
+        assert!(!not_found.contains("other.flac", None));
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn fetched_lyrics_contain_song_metadata() {
+        let song = Song {
+            id: 0,
+            file: "a.flac".to_owned(),
+            duration: Some(Duration::from_secs(125)),
+            metadata: HashMap::from([
+                ("artist".to_owned(), "Artist".to_owned()),
+                ("album".to_owned(), "Album".to_owned()),
+                ("title".to_owned(), "Title".to_owned()),
+            ]),
+            stickers: None,
+        };
+
+        let result: Lrc =
+            with_metadata(&song, "[00:01.00]first\n[00:02.50]second\n").parse().unwrap();
+
+        assert_eq!(result.artist.as_deref(), Some("Artist"));
+        assert_eq!(result.album.as_deref(), Some("Album"));
+        assert_eq!(result.title.as_deref(), Some("Title"));
+        assert_eq!(result.length, Some(Duration::from_secs(125)));
+        assert_eq!(result.lines.len(), 2);
+    }
+}
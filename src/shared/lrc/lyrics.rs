@@ -7,7 +7,23 @@ use super::parse_length;
 #[derive(Debug, Eq, PartialEq)]
 pub struct LrcLine {
     pub time: Duration,
+    /// Content of the line with the word timestamps stripped
     pub content: String,
+    /// Word timings from the enhanced lrc format, empty if the line has none
+    pub words: Vec<LrcWord>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LrcWord {
+    pub time: Duration,
+    pub content: String,
+}
+
+impl LrcLine {
+    /// Index of the word which is being sung at the given time
+    pub fn current_word_idx(&self, elapsed: Duration) -> Option<usize> {
+        self.words.iter().rposition(|word| elapsed >= word.time)
+    }
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -52,36 +68,12 @@ impl FromStr for Lrc {
 
             match meta_or_time.chars().next() {
                 Some(c) if c.is_numeric() => {
-                    for meta_or_time in meta_or_time.split("][") {
-                        let (minutes, time_rest) =
-                            meta_or_time.split_once(':').with_context(|| {
-                                format!("Invalid lrc minutes format: '{meta_or_time}'")
-                            })?;
-                        let (seconds, hundreths) = time_rest
-                            .split_once('.')
-                            .or_else(|| time_rest.split_once(':'))
-                            .with_context(|| {
-                                format!("Invalid lrc seconds and hundreths format: '{time_rest}'")
-                            })?;
-
-                        let mut milis = 0;
-                        milis += minutes.parse::<u64>()? * 60 * 1000;
-                        milis += seconds.parse::<u64>()? * 1000;
-                        milis += hundreths.parse::<u64>()? * 10;
-
-                        milis = match offset {
-                            Some(offset) if offset > 0 => {
-                                milis.saturating_sub(offset.unsigned_abs())
-                            }
-                            Some(offset) if offset < 0 => {
-                                milis.saturating_add(offset.unsigned_abs())
-                            }
-                            _ => milis,
-                        };
-
+                    let (content, words) = parse_words(line, offset);
+                    for time in meta_or_time.split("][") {
                         result.lines.push(LrcLine {
-                            time: Duration::from_millis(milis),
-                            content: line.to_owned(),
+                            time: parse_time(time, offset)?,
+                            content: content.clone(),
+                            words: words.clone(),
                         });
                     }
                 }
@@ -109,12 +101,76 @@ impl FromStr for Lrc {
     }
 }
 
+/// Parses `mm:ss.xx` timestamp and applies the lrc offset to it
+fn parse_time(input: &str, offset: Option<i64>) -> Result<Duration> {
+    let (minutes, time_rest) =
+        input.split_once(':').with_context(|| format!("Invalid lrc minutes format: '{input}'"))?;
+    let (seconds, hundreths) = time_rest
+        .split_once('.')
+        .or_else(|| time_rest.split_once(':'))
+        .with_context(|| format!("Invalid lrc seconds and hundreths format: '{time_rest}'"))?;
+
+    let mut milis = 0;
+    milis += minutes.parse::<u64>()? * 60 * 1000;
+    milis += seconds.parse::<u64>()? * 1000;
+    milis += hundreths.parse::<u64>()? * 10;
+
+    Ok(apply_offset(Duration::from_millis(milis), offset))
+}
+
+fn apply_offset(time: Duration, offset: Option<i64>) -> Duration {
+    match offset {
+        Some(offset) if offset > 0 => {
+            time.saturating_sub(Duration::from_millis(offset.unsigned_abs()))
+        }
+        Some(offset) if offset < 0 => {
+            time.saturating_add(Duration::from_millis(offset.unsigned_abs()))
+        }
+        _ => time,
+    }
+}
+
+/// Splits line content with `<mm:ss.xx>` word timestamps into the plain content
+/// and the timed words. Text before the first timestamp is part of the content
+/// only. A `<` which does not start a valid timestamp, like in "I <3 you", is
+/// kept as text of the preceding word.
+fn parse_words(line: &str, offset: Option<i64>) -> (String, Vec<LrcWord>) {
+    let Some((prefix, rest)) = line.split_once('<') else {
+        return (line.to_owned(), Vec::new());
+    };
+
+    let mut content = prefix.to_owned();
+    let mut words: Vec<LrcWord> = Vec::new();
+    for part in rest.split('<') {
+        let timed_word = part
+            .split_once('>')
+            .and_then(|(time, word)| Some((parse_time(time.trim(), offset).ok()?, word)));
+
+        if let Some((time, word)) = timed_word {
+            content.push_str(word);
+            words.push(LrcWord { time, content: word.to_owned() });
+        } else {
+            content.push('<');
+            content.push_str(part);
+            if let Some(word) = words.last_mut() {
+                word.content.push('<');
+                word.content.push_str(part);
+            }
+        }
+    }
+
+    (content, words)
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
     use std::time::Duration;
 
-    use crate::shared::lrc::{Lrc, lyrics::LrcLine};
+    use crate::shared::lrc::{
+        Lrc,
+        lyrics::{LrcLine, LrcWord},
+    };
 
     #[test]
     fn lrc() {
@@ -141,16 +197,23 @@ mod tests {
             lines: vec![
                 LrcLine {
                     time: Duration::from_millis(1860),
-                    content: "line with dot before hundredths".to_string()
+                    content: "line with dot before hundredths".to_string(),
+                    words: Vec::new()
                 },
                 LrcLine {
                     time: Duration::from_millis(4730),
-                    content: "line with colon before hundredths".to_string()
+                    content: "line with colon before hundredths".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_millis(11240),
+                    content: String::new(),
+                    words: Vec::new()
                 },
-                LrcLine { time: Duration::from_millis(11240), content: String::new() },
                 LrcLine {
                     time: Duration::from_millis(676_910),
-                    content: "line with long time".to_string()
+                    content: "line with long time".to_string(),
+                    words: Vec::new()
                 },
             ],
         });
@@ -174,8 +237,16 @@ mod tests {
             author: None,
             length: None,
             lines: vec![
-                LrcLine { time: Duration::from_millis(860), content: "line1".to_string() },
-                LrcLine { time: Duration::from_millis(3730), content: "line2".to_string() },
+                LrcLine {
+                    time: Duration::from_millis(860),
+                    content: "line1".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_millis(3730),
+                    content: "line2".to_string(),
+                    words: Vec::new()
+                },
             ],
         });
     }
@@ -198,8 +269,16 @@ mod tests {
             author: None,
             length: None,
             lines: vec![
-                LrcLine { time: Duration::from_millis(2860), content: "line1".to_string() },
-                LrcLine { time: Duration::from_millis(5730), content: "line2".to_string() },
+                LrcLine {
+                    time: Duration::from_millis(2860),
+                    content: "line1".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_millis(5730),
+                    content: "line2".to_string(),
+                    words: Vec::new()
+                },
             ],
         });
     }
@@ -221,12 +300,90 @@ mod tests {
             author: None,
             length: None,
             lines: vec![
-                LrcLine { time: Duration::from_millis(1860), content: "line1".to_string() },
-                LrcLine { time: Duration::from_millis(4730), content: "line2".to_string() },
-                LrcLine { time: Duration::from_millis(5730), content: "line2".to_string() },
-                LrcLine { time: Duration::from_millis(6730), content: "line2".to_string() },
-                LrcLine { time: Duration::from_millis(7860), content: "line3".to_string() },
+                LrcLine {
+                    time: Duration::from_millis(1860),
+                    content: "line1".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_millis(4730),
+                    content: "line2".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_millis(5730),
+                    content: "line2".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_millis(6730),
+                    content: "line2".to_string(),
+                    words: Vec::new()
+                },
+                LrcLine {
+                    time: Duration::from_millis(7860),
+                    content: "line3".to_string(),
+                    words: Vec::new()
+                },
             ],
         });
     }
+
+    #[test]
+    fn enhanced_lrc_word_timings() {
+        let input = r"
+[offset: +100]
+[00:01.00]<00:01.00>first <00:01.50>word<00:02.00>
+[00:03.00]plain line
+";
+
+        let result: Lrc = input.parse().unwrap();
+
+        assert_eq!(result.lines, vec![
+            LrcLine {
+                time: Duration::from_millis(900),
+                content: "first word".to_string(),
+                words: vec![
+                    LrcWord { time: Duration::from_millis(900), content: "first ".to_string() },
+                    LrcWord { time: Duration::from_millis(1400), content: "word".to_string() },
+                    LrcWord { time: Duration::from_millis(1900), content: String::new() },
+                ],
+            },
+            LrcLine {
+                time: Duration::from_millis(2900),
+                content: "plain line".to_string(),
+                words: Vec::new()
+            },
+        ]);
+        assert_eq!(result.lines[0].current_word_idx(Duration::from_millis(800)), None);
+        assert_eq!(result.lines[0].current_word_idx(Duration::from_millis(1500)), Some(1));
+    }
+
+    #[test]
+    fn enhanced_lrc_invalid_word_timestamp_is_text() {
+        let input = "[00:01.00]<00:01.00 word";
+
+        let result: Lrc = input.parse().unwrap();
+
+        assert_eq!(result.lines[0].content, "<00:01.00 word");
+        assert!(result.lines[0].words.is_empty());
+    }
+
+    #[test]
+    fn lrc_with_angle_bracket_in_text() {
+        let input = r"
+[00:01.00]I <3 you
+[00:02.00]<00:02.00>I <00:02.50><3 you
+";
+
+        let result: Lrc = input.parse().unwrap();
+
+        assert_eq!(result.lines[0].content, "I <3 you");
+        assert!(result.lines[0].words.is_empty());
+        assert_eq!(result.lines[1].content, "I <3 you");
+        assert_eq!(result.lines[1].words, vec![
+            LrcWord { time: Duration::from_secs(2), content: "I ".to_string() },
+            LrcWord { time: Duration::from_millis(2500), content: "<3 you".to_string() },
+        ]);
+    }
 }
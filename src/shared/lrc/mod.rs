@@ -1,11 +1,41 @@
+mod edit;
 mod index;
+pub mod lrclib;
 mod lyrics;
 
-use std::{path::PathBuf, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use anyhow::{Context, Result, bail};
+pub use edit::{adjust_offset, synced_lrc};
 pub use index::{LrcIndex, LrcIndexEntry};
-pub use lyrics::Lrc;
+pub use lyrics::{Lrc, LrcLine};
+
+use crate::mpd::commands::Song;
+
+/// Lyrics of a song, either synchronized or plain text
+#[derive(Debug, Eq, PartialEq)]
+pub enum Lyrics {
+    Synced(Lrc),
+    Unsynced(Vec<String>),
+}
+
+impl Lyrics {
+    pub fn plain(text: &str) -> Self {
+        Self::Unsynced(text.lines().map(|line| line.trim_end().to_owned()).collect())
+    }
+
+    /// Parses the text as lrc if it contains timestamped lines, as plain text
+    /// otherwise
+    pub fn parse(text: &str) -> Self {
+        match text.parse::<Lrc>() {
+            Ok(lrc) if !lrc.lines.is_empty() => Self::Synced(lrc),
+            _ => Self::plain(text),
+        }
+    }
+}
 
 fn parse_length(input: &str) -> anyhow::Result<Duration> {
     let (minutes, seconds) = input.split_once(':').context("Invalid lrc length format")?;
@@ -14,6 +44,98 @@ fn parse_length(input: &str) -> anyhow::Result<Duration> {
     Ok(Duration::from_secs(minutes * 60 + seconds))
 }
 
+/// Prepends the song's metadata to the lyrics so the lrc file can be matched
+/// by the lyrics index
+pub(crate) fn with_metadata(song: &Song, lyrics: &str) -> String {
+    let mut lines = Vec::new();
+    if let Some(artist) = song.artist() {
+        lines.push(format!("[ar:{artist}]"));
+    }
+    if let Some(album) = song.album() {
+        lines.push(format!("[al:{album}]"));
+    }
+    if let Some(title) = song.title() {
+        lines.push(format!("[ti:{title}]"));
+    }
+    if let Some(duration) = song.duration {
+        let secs = duration.as_secs();
+        lines.push(format!("[length:{}:{:02}]", secs / 60, secs % 60));
+    }
+    lines.push(String::new());
+    lines.push(lyrics.trim_end().to_owned());
+
+    lines.join("\n") + "\n"
+}
+
+/// Path of the companion lrc file with the translation of the lyrics into
+/// the given language, ie. `song.en.lrc`
+pub(crate) fn get_translation_path(lrc_path: &Path, lang: &str) -> PathBuf {
+    lrc_path.with_extension(format!("{lang}.lrc"))
+}
+
+/// Languages of the companion lrc files found next to the lrc path
+pub(crate) fn find_translations(lrc_path: &Path) -> Result<Vec<String>> {
+    let (Some(dir), Some(stem)) = (lrc_path.parent(), lrc_path.file_stem()) else {
+        return Ok(Vec::new());
+    };
+    let stem = stem.to_string_lossy();
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut languages: Vec<String> = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name();
+            let (base, lang) = split_translation_name(name.to_str()?)?;
+            (base == stem).then(|| lang.to_owned())
+        })
+        .collect();
+    languages.sort();
+
+    Ok(languages)
+}
+
+/// Whether the lrc file is a translation of another lrc file next to it, ie.
+/// `song.en.lrc` when `song.lrc` exists
+pub(crate) fn is_translation(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| split_translation_name(name.to_str()?))
+        .is_some_and(|(base, _)| path.with_file_name(format!("{base}.lrc")).exists())
+}
+
+/// Splits the file name of a translation into the stem of the lrc file it
+/// translates and the language, ie. `song.en.lrc` into `song` and `en`. Only
+/// language tags and romanizations count as a language so that dotted names
+/// like `Song.Live.lrc` are not mistaken for translations.
+fn split_translation_name(name: &str) -> Option<(&str, &str)> {
+    let (base, lang) = name.strip_suffix(".lrc")?.rsplit_once('.')?;
+    (!base.is_empty() && is_language(lang)).then_some((base, lang))
+}
+
+/// Romanizations are commonly named after the romanization system instead of
+/// a language tag
+const ROMANIZATIONS: [&str; 4] = ["romaji", "romaja", "pinyin", "jyutping"];
+
+/// Whether the value is a language tag like `en`, `pt-BR` or `ja-Latn` or
+/// one of the [`ROMANIZATIONS`]
+fn is_language(value: &str) -> bool {
+    if ROMANIZATIONS.contains(&value) {
+        return true;
+    }
+
+    let mut subtags = value.split('-');
+    let is_primary = subtags.next().is_some_and(|primary| {
+        (2..=3).contains(&primary.len()) && primary.bytes().all(|b| b.is_ascii_lowercase())
+    });
+    is_primary
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.bytes().all(|b| b.is_ascii_alphanumeric())
+        })
+}
+
 pub(crate) fn get_lrc_path(lyrics_dir: &str, song_file: &str) -> Result<PathBuf> {
     let mut path: PathBuf = PathBuf::from(lyrics_dir);
     path.push(song_file);
@@ -25,3 +147,91 @@ pub(crate) fn get_lrc_path(lyrics_dir: &str, song_file: &str) -> Result<PathBuf>
     path.push(stem);
     Ok(path)
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::time::Duration;
+
+    use rstest::rstest;
+
+    use super::{
+        Lyrics,
+        find_translations,
+        get_translation_path,
+        is_translation,
+        lyrics::LrcLine,
+    };
+    use crate::tests::fixtures::{TempDir, temp_dir};
+
+    #[test]
+    fn parses_timestamped_text_as_synced() {
+        let result = Lyrics::parse("[ti:title]\n[00:01.50]first line\n[00:03.00]second line");
+
+        let Lyrics::Synced(lrc) = result else {
+            panic!("expected synced lyrics, got {result:?}");
+        };
+        assert_eq!(lrc.title.as_deref(), Some("title"));
+        assert_eq!(lrc.lines, vec![
+            LrcLine {
+                time: Duration::from_millis(1500),
+                content: "first line".to_string(),
+                words: Vec::new()
+            },
+            LrcLine {
+                time: Duration::from_secs(3),
+                content: "second line".to_string(),
+                words: Vec::new()
+            },
+        ]);
+    }
+
+    #[test]
+    fn parses_plain_text_as_unsynced() {
+        let result = Lyrics::parse("first line  \n\nsecond line\n");
+
+        assert_eq!(
+            result,
+            Lyrics::Unsynced(vec![
+                "first line".to_string(),
+                String::new(),
+                "second line".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_metadata_only_lrc_as_unsynced() {
+        let result = Lyrics::parse("[ti:title]\n[ar:artist]");
+
+        assert_eq!(
+            result,
+            Lyrics::Unsynced(vec!["[ti:title]".to_string(), "[ar:artist]".to_string()])
+        );
+    }
+
+    #[rstest]
+    fn finds_translations_next_to_lrc_file(#[from(temp_dir)] dir: TempDir) {
+        for name in [
+            "song.lrc",
+            "song.romaji.lrc",
+            "song.en.lrc",
+            "song.pt-BR.lrc",
+            "song.Live.lrc",
+            "song.txt",
+            "other.en.lrc",
+        ] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+
+        let result = find_translations(&dir.join("song.lrc")).unwrap();
+        let translations: Vec<_> = ["song.lrc", "song.en.lrc", "song.Live.lrc", "other.en.lrc"]
+            .into_iter()
+            .map(|name| is_translation(&dir.join(name)))
+            .collect();
+
+        assert_eq!(result, vec!["en".to_owned(), "pt-BR".to_owned(), "romaji".to_owned()]);
+        assert_eq!(get_translation_path(&dir.join("song.lrc"), "en"), dir.join("song.en.lrc"));
+        assert_eq!(translations, vec![false, true, false, false]);
+    }
+}
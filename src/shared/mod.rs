@@ -1,3 +1,4 @@
+pub mod album_art;
 pub mod dependencies;
 pub mod env;
 pub mod events;
@@ -11,7 +12,9 @@ pub mod lrc;
 pub mod macros;
 pub mod mouse_event;
 pub mod mpd_query;
+pub mod notifications;
 pub mod percent;
 pub mod socket;
+pub mod tag_writer;
 pub mod tmux;
 pub mod ytdlp;
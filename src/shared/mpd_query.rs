@@ -1,4 +1,4 @@
-use std::any::Any;
+use std::{any::Any, collections::HashSet};
 
 use anyhow::Result;
 use bon::Builder;
@@ -10,7 +10,16 @@ use crate::{
     config::tabs::PaneType,
     mpd::{
         client::Client,
-        commands::{Decoder, Output, Song, Status, Volume},
+        commands::{
+            Decoder,
+            Output,
+            Song,
+            Stats,
+            Status,
+            Volume,
+            list_mounts::Mount,
+            list_neighbors::Neighbor,
+        },
         mpd_client::MpdClient,
     },
     shared::{events::ClientRequest, macros::try_skip},
@@ -21,6 +30,9 @@ pub const EXTERNAL_COMMAND: &str = "external_command";
 pub const GLOBAL_STATUS_UPDATE: &str = "global_status_update";
 pub const GLOBAL_VOLUME_UPDATE: &str = "global_volume_update";
 pub const GLOBAL_QUEUE_UPDATE: &str = "global_queue_update";
+pub const GLOBAL_SUPPORTED_COMMANDS_UPDATE: &str = "global_supported_commands_update";
+pub const GLOBAL_DB_UPDATE_STARTED: &str = "global_db_update_started";
+pub const GLOBAL_DB_UPDATE_FINISHED: &str = "global_db_update_finished";
 
 #[derive(derive_more::Debug, Builder)]
 pub(crate) struct MpdQuery {
@@ -88,11 +100,15 @@ pub(crate) enum MpdQueryResult {
     DirOrSong { data: Vec<DirOrSong>, origin_path: Option<Vec<String>> },
     AddToPlaylist { playlists: Vec<String>, song_file: String },
     AlbumArt(Option<Vec<u8>>),
+    AlbumCover { album: String, data: Option<Vec<u8>> },
     Status(Status),
     Queue(Option<Vec<Song>>),
     Volume(Volume),
     Outputs(Vec<Output>),
     Decoders(Vec<Decoder>),
+    Stats(Stats),
+    SupportedCommands(HashSet<String>),
+    Storage { mounts: Vec<Mount>, neighbors: Vec<Neighbor> },
     ExternalCommand(&'static [&'static str], Vec<Song>),
     Any(Box<dyn Any + Send + Sync>),
 }
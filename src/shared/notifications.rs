@@ -0,0 +1,213 @@
+use std::{collections::VecDeque, time::Instant};
+
+use chrono::{DateTime, Local};
+
+use super::events::Level;
+use crate::config::notifications::NotificationsConfig;
+
+/// Maximum number of messages waiting behind the one shown in the status bar.
+/// The oldest waiting message is dropped from the status bar, but not from the
+/// history, when another one arrives.
+const MAX_PENDING: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub message: String,
+    pub level: Level,
+    pub timestamp: DateTime<Local>,
+    visible_from: Instant,
+    visible_until: Instant,
+}
+
+/// Messages waiting to be displayed in the status bar together with the
+/// history of all received messages. Every queued message is shown for the
+/// timeout of its level after the previous one disappears. Repeated messages
+/// are shown only once and the number of waiting messages is limited so a
+/// burst of errors does not keep the status bar busy for minutes.
+#[derive(Debug, Default)]
+pub struct Notifications {
+    queue: VecDeque<Notification>,
+    history: VecDeque<Notification>,
+}
+
+impl Notifications {
+    /// Queues the message and returns the instants at which it appears and
+    /// disappears from the status bar, or [`None`] if it is only recorded in
+    /// the history because the timeout of its level is zero or the same
+    /// message is already queued
+    pub fn push(
+        &mut self,
+        message: String,
+        level: Level,
+        config: &NotificationsConfig,
+        now: Instant,
+    ) -> Option<(Instant, Instant)> {
+        self.queue.retain(|n| n.visible_until > now);
+
+        let timeout = config.timeout(level);
+        let visible_from = self.queue.back().map_or(now, |n| n.visible_until.max(now));
+        let notification = Notification {
+            message,
+            level,
+            timestamp: Local::now(),
+            visible_from,
+            visible_until: visible_from + timeout,
+        };
+
+        if config.history_size > 0 {
+            if self.history.len() >= config.history_size {
+                self.history.pop_front();
+            }
+            self.history.push_back(notification.clone());
+        }
+
+        let is_repeated = self
+            .queue
+            .iter()
+            .any(|n| n.level == notification.level && n.message == notification.message);
+        if timeout.is_zero() || is_repeated {
+            return None;
+        }
+
+        self.queue.push_back(notification);
+        if self.pending(now) > MAX_PENDING {
+            self.drop_oldest_pending(now);
+        }
+
+        self.queue.back().map(|n| (n.visible_from, n.visible_until))
+    }
+
+    /// Removes the first message which is not shown yet and moves the ones
+    /// after it forward to take its place
+    fn drop_oldest_pending(&mut self, now: Instant) {
+        let Some(idx) = self.queue.iter().position(|n| n.visible_from > now) else {
+            return;
+        };
+        self.queue.remove(idx);
+
+        let mut visible_from = idx
+            .checked_sub(1)
+            .and_then(|prev| self.queue.get(prev))
+            .map_or(now, |n| n.visible_until.max(now));
+        for notification in self.queue.iter_mut().skip(idx) {
+            let timeout = notification.visible_until - notification.visible_from;
+            notification.visible_from = visible_from;
+            notification.visible_until = visible_from + timeout;
+            visible_from = notification.visible_until;
+        }
+    }
+
+    /// Message which should currently be displayed in the status bar
+    pub fn current(&self, now: Instant) -> Option<&Notification> {
+        self.queue.iter().find(|n| n.visible_from <= now && now < n.visible_until)
+    }
+
+    /// Number of messages waiting to be displayed after the current one
+    pub fn pending(&self, now: Instant) -> usize {
+        self.queue.iter().filter(|n| n.visible_from > now).count()
+    }
+
+    /// All recorded messages, oldest first
+    pub fn history(&self) -> &VecDeque<Notification> {
+        &self.history
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::Notifications;
+    use crate::{config::notifications::NotificationsConfig, shared::events::Level};
+
+    fn config() -> NotificationsConfig {
+        NotificationsConfig {
+            info_timeout: Duration::from_secs(5),
+            warn_timeout: Duration::from_secs(5),
+            error_timeout: Duration::from_secs(10),
+            history_size: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn queued_messages_are_shown_one_after_another() {
+        let mut notifications = Notifications::default();
+        let now = Instant::now();
+
+        notifications.push("first".to_owned(), Level::Error, &config(), now);
+        let (from, until) =
+            notifications.push("second".to_owned(), Level::Info, &config(), now).unwrap();
+
+        assert_eq!(from, now + Duration::from_secs(10));
+        assert_eq!(until, now + Duration::from_secs(15));
+        assert_eq!(notifications.current(now).unwrap().message, "first");
+        assert_eq!(notifications.pending(now), 1);
+        assert_eq!(notifications.current(from).unwrap().message, "second");
+        assert_eq!(notifications.pending(from), 0);
+        assert!(notifications.current(until).is_none());
+    }
+
+    #[test]
+    fn repeated_messages_are_shown_once() {
+        let mut notifications = Notifications::default();
+        let now = Instant::now();
+
+        notifications.push("failed".to_owned(), Level::Error, &config(), now);
+        let repeated = notifications.push("failed".to_owned(), Level::Error, &config(), now);
+
+        assert!(repeated.is_none());
+        assert_eq!(notifications.pending(now), 0);
+        assert_eq!(notifications.history().len(), 2);
+    }
+
+    #[test]
+    fn oldest_pending_message_is_dropped() {
+        let mut notifications = Notifications::default();
+        let now = Instant::now();
+
+        for message in ["a", "b", "c", "d"] {
+            notifications.push(message.to_owned(), Level::Error, &config(), now);
+        }
+        let (from, until) =
+            notifications.push("e".to_owned(), Level::Error, &config(), now).unwrap();
+
+        assert_eq!(notifications.pending(now), 3);
+        assert_eq!(from, now + Duration::from_secs(30));
+        assert_eq!(until, now + Duration::from_secs(40));
+        let shown = [0, 10, 20, 30]
+            .map(|secs| notifications.current(now + Duration::from_secs(secs)).unwrap())
+            .map(|n| n.message.as_str());
+        assert_eq!(shown, ["a", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn zero_timeout_only_records_history() {
+        let mut notifications = Notifications::default();
+        let now = Instant::now();
+        let config = NotificationsConfig { info_timeout: Duration::ZERO, ..config() };
+
+        assert!(notifications.push("quiet".to_owned(), Level::Info, &config, now).is_none());
+        assert!(notifications.current(now).is_none());
+        assert_eq!(notifications.history().len(), 1);
+    }
+
+    #[test]
+    fn history_is_limited() {
+        let mut notifications = Notifications::default();
+        let now = Instant::now();
+
+        for message in ["a", "b", "c"] {
+            notifications.push(message.to_owned(), Level::Warn, &config(), now);
+        }
+
+        let history =
+            notifications.history().iter().map(|n| n.message.as_str()).collect::<Vec<_>>();
+        assert_eq!(history, ["b", "c"]);
+    }
+}
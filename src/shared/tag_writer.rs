@@ -0,0 +1,296 @@
+use std::{path::Path, process::Command};
+
+use anyhow::{Result, bail};
+use itertools::Itertools;
+
+use super::{dependencies::PYTHON3MUTAGEN, macros::status_error};
+use crate::{core::command::run_external_blocking, mpd::commands::Song};
+
+/// Tags offered by the tag editor even when the edited songs do not have them
+pub const EDITABLE_TAGS: [&str; 6] = ["title", "artist", "album", "track", "date", "genre"];
+
+/// Key of the tag editor row which renames the files instead of writing a tag.
+/// Its value is the new file name without the extension.
+pub const FILE_NAME_KEY: &str = "filename";
+
+/// Values reported by MPD alongside the tags which cannot be edited
+const READ_ONLY_KEYS: [&str; 5] = ["duration", "time", "format", "last-modified", "added"];
+
+/// Sets the tags given as `key value` argument pairs, an empty value removes
+/// the tag
+const MUTAGEN_SCRIPT: &str = r#"
+import sys
+import mutagen
+
+f = mutagen.File(sys.argv[1], easy=True)
+if f is None:
+    sys.exit("Unsupported file format")
+if f.tags is None:
+    f.add_tags()
+for key, value in zip(sys.argv[2::2], sys.argv[3::2]):
+    if value:
+        f[key] = value
+    elif key in f:
+        del f[key]
+f.save()
+"#;
+
+pub fn is_editable(key: &str) -> bool {
+    !READ_ONLY_KEYS.contains(&key)
+}
+
+/// Expands placeholders in the value of a tag for the given song. `{n}` is
+/// replaced with the position of the song among the edited songs starting at
+/// one and `{tag}` with the current value of the tag, for example
+/// `{track} - {title}`. Editable tags the song does not have expand to an
+/// empty string, other unknown placeholders are kept as written. `{{` and `}}`
+/// are written as a single brace.
+pub fn expand_pattern(pattern: &str, song: &Song, n: usize) -> String {
+    let mut result = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(start) = rest.find(['{', '}']) {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            result.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix('}') {
+            result.push('}');
+            rest = after;
+            continue;
+        }
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        match &rest[1..end] {
+            "n" => result.push_str(&n.to_string()),
+            tag => match song.metadata.get(tag) {
+                Some(value) => result.push_str(value),
+                None if EDITABLE_TAGS.contains(&tag) => {}
+                None => result.push_str(&rest[..=end]),
+            },
+        }
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Song whose tags were written by [`write_songs_tags`]
+#[derive(Debug)]
+pub struct WrittenSong {
+    pub file: String,
+    /// New path of the file relative to the music directory if it was renamed
+    pub renamed_to: Option<String>,
+}
+
+/// Writes the tags to all of the songs in MPD's `music_directory` and renames
+/// them if [`FILE_NAME_KEY`] is among the tags. Placeholders in the values are
+/// expanded only when more than one song is edited, the file name is expanded
+/// with the newly written tags. Returns the songs whose tags were written.
+pub fn write_songs_tags(
+    command: Option<&[&str]>,
+    music_directory: &str,
+    songs: &[Song],
+    tags: &[(String, String)],
+) -> Vec<WrittenSong> {
+    let (file_name, tags): (Vec<_>, Vec<_>) =
+        tags.iter().partition(|(key, _)| key == FILE_NAME_KEY);
+    let file_name = file_name.first().map(|(_, value)| value.as_str());
+
+    let mut written = Vec::new();
+    for (idx, song) in songs.iter().enumerate() {
+        let expand = |value: &str, song: &Song| {
+            if songs.len() > 1 { expand_pattern(value, song, idx + 1) } else { value.to_owned() }
+        };
+        let song_tags =
+            tags.iter().map(|(key, value)| (key.clone(), expand(value, song))).collect_vec();
+        if !song_tags.is_empty() {
+            let path = Path::new(music_directory).join(&song.file);
+            if let Err(err) = write_tags(command, &path, &song_tags) {
+                status_error!(err:?; "Failed to write tags of '{}': {}", song.file, err);
+                continue;
+            }
+        }
+
+        let renamed_to = file_name.and_then(|file_name| {
+            let mut tagged = song.clone();
+            for (key, value) in &song_tags {
+                if value.is_empty() {
+                    tagged.metadata.remove(key);
+                } else {
+                    tagged.metadata.insert(key.clone(), value.clone());
+                }
+            }
+            rename_file(music_directory, &song.file, &expand(file_name, &tagged))
+                .inspect_err(|err| {
+                    status_error!(err:?; "Failed to rename '{}': {}", song.file, err);
+                })
+                .ok()
+                .flatten()
+        });
+        written.push(WrittenSong { file: song.file.clone(), renamed_to });
+    }
+
+    written
+}
+
+/// Renames the file to `name` keeping its directory and extension. Returns the
+/// new path relative to the music directory, `None` if the name is unchanged.
+fn rename_file(music_directory: &str, file: &str, name: &str) -> Result<Option<String>> {
+    let new_file = renamed_path(file, name)?;
+    if new_file == file {
+        return Ok(None);
+    }
+
+    let root = Path::new(music_directory);
+    if root.join(&new_file).exists() {
+        bail!("File '{new_file}' already exists");
+    }
+    std::fs::rename(root.join(file), root.join(&new_file))?;
+
+    Ok(Some(new_file))
+}
+
+fn renamed_path(file: &str, name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() || name.contains('/') || name == "." || name == ".." {
+        bail!("Invalid file name '{name}'");
+    }
+
+    let (dir, old_name) = file.rsplit_once('/').map_or(("", file), |(dir, name)| (dir, name));
+    let new_name = match Path::new(old_name).extension() {
+        Some(ext) => format!("{name}.{}", ext.to_string_lossy()),
+        None => name.to_owned(),
+    };
+
+    Ok(if dir.is_empty() { new_name } else { format!("{dir}/{new_name}") })
+}
+
+/// Path MPD has to update to pick up changes of all of the files, the file
+/// itself for a single file and otherwise their deepest common directory.
+/// `None` means the whole database.
+pub fn update_path<'a>(files: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let mut files = files.into_iter();
+    let first = files.next()?;
+    let mut common: Option<Vec<&str>> = None;
+    for file in files {
+        let dirs = common.get_or_insert_with(|| dir_components(first));
+        let len = dirs.iter().zip(dir_components(file)).take_while(|(a, b)| *a == b).count();
+        dirs.truncate(len);
+    }
+
+    match common {
+        None => Some(first.to_owned()),
+        Some(dirs) if dirs.is_empty() => None,
+        Some(dirs) => Some(dirs.join("/")),
+    }
+}
+
+fn dir_components(file: &str) -> Vec<&str> {
+    let mut components = file.split('/').collect_vec();
+    components.pop();
+    components
+}
+
+/// Writes the tags to the file at `path`. Uses the given external command if
+/// configured, otherwise the tags are written with python's mutagen.
+pub fn write_tags(command: Option<&[&str]>, path: &Path, tags: &[(String, String)]) -> Result<()> {
+    if let Some(command) = command {
+        let envs = std::iter::once(("FILE".to_owned(), path.to_string_lossy().into_owned()))
+            .chain(tags.iter().map(|(key, value)| {
+                (format!("TAG_{}", key.to_uppercase().replace('-', "_")), value.clone())
+            }))
+            .collect_vec();
+
+        return run_external_blocking(
+            command,
+            envs.iter().map(|(key, value)| (key.as_str(), value.as_str())),
+        );
+    }
+
+    if !PYTHON3MUTAGEN.installed {
+        bail!("python-mutagen is required to edit tags unless tag_editor command is configured");
+    }
+
+    let out = Command::new("python3")
+        .arg("-c")
+        .arg(MUTAGEN_SCRIPT)
+        .arg(path)
+        .args(tags.iter().flat_map(|(key, value)| [mutagen_key(key), value.as_str()]))
+        .output()?;
+    if !out.status.success() {
+        bail!("Failed to write tags: '{}'", String::from_utf8_lossy(&out.stderr).trim());
+    }
+
+    Ok(())
+}
+
+/// Maps MPD's tag names to the ones used by mutagen's easy interface
+fn mutagen_key(key: &str) -> &str {
+    match key {
+        "track" => "tracknumber",
+        "disc" => "discnumber",
+        key => key,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{expand_pattern, renamed_path, update_path};
+    use crate::mpd::commands::Song;
+
+    #[test]
+    fn expands_pattern() {
+        let song = Song {
+            metadata: HashMap::from([
+                ("title".to_owned(), "Song".to_owned()),
+                ("track".to_owned(), "3".to_owned()),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(expand_pattern("{track} - {title}", &song, 1), "3 - Song");
+        assert_eq!(expand_pattern("Part {n}: {title}{genre}", &song, 2), "Part 2: Song");
+        assert_eq!(expand_pattern("{title} {unclosed", &song, 1), "Song {unclosed");
+        assert_eq!(expand_pattern("plain", &song, 1), "plain");
+    }
+
+    #[test]
+    fn keeps_unknown_and_escaped_placeholders() {
+        let song = Song {
+            metadata: HashMap::from([("title".to_owned(), "Song".to_owned())]),
+            ..Default::default()
+        };
+
+        assert_eq!(expand_pattern("Live {2020}", &song, 1), "Live {2020}");
+        assert_eq!(expand_pattern("{{title}} {title}", &song, 1), "{title} Song");
+        assert_eq!(expand_pattern("a } b", &song, 1), "a } b");
+    }
+
+    #[test]
+    fn renamed_path_keeps_directory_and_extension() {
+        assert_eq!(renamed_path("a/b/01.flac", "01 - Song").unwrap(), "a/b/01 - Song.flac");
+        assert_eq!(renamed_path("song.mp3", " new ").unwrap(), "new.mp3");
+        assert_eq!(renamed_path("a/song", "new").unwrap(), "a/new");
+        assert!(renamed_path("a/song.flac", "b/new").is_err());
+        assert!(renamed_path("a/song.flac", "").is_err());
+    }
+
+    #[test]
+    fn update_path_is_common_directory() {
+        assert_eq!(update_path(["a/b/song.flac"]), Some("a/b/song.flac".to_owned()));
+        assert_eq!(
+            update_path(["a/b/1.flac", "a/b/c/2.flac", "a/b/3.flac"]),
+            Some("a/b".to_owned())
+        );
+        assert_eq!(update_path(["a/1.flac", "b/2.flac"]), None);
+        assert_eq!(update_path([]), None);
+    }
+}
@@ -1,4 +1,9 @@
-use std::{cell::Cell, collections::HashSet};
+use std::{
+    cell::Cell,
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 use crossbeam::channel::{Receiver, Sender, unbounded};
 use ratatui::{Terminal, backend::TestBackend};
@@ -12,11 +17,55 @@ use crate::{
     shared::{
         events::{ClientRequest, WorkRequest},
         lrc::LrcIndex,
+        notifications::Notifications,
     },
 };
 
 pub mod mpd_client;
 
+/// Directory in the system temp dir which is removed again when dropped, even if
+/// the test using it panics
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    #[allow(clippy::unwrap_used)]
+    pub fn new() -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "rmpc_test_{}_{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+}
+
+impl std::ops::Deref for TempDir {
+    type Target = PathBuf;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[fixture]
+pub fn temp_dir() -> TempDir {
+    TempDir::new()
+}
+
 #[fixture]
 pub fn status() -> Status {
     Status::default()
@@ -56,6 +105,7 @@ pub fn app_context(
         needs_render: Cell::new(false),
         lrc_index: LrcIndex::default(),
         should_fetch_stickers: false,
+        notifications: Notifications::default(),
         rendered_frames: 0,
         scheduler,
     }
@@ -15,10 +15,12 @@ use crate::mpd::{
         LsInfo,
         Playlist,
         Song,
+        Stats,
         Status,
         Update,
         Volume,
-        list::MpdList,
+        count::GroupedCount,
+        list::{GroupedList, MpdList},
         list_playlist::FileList,
         mpd_config::MpdConfig,
         status::OnOffOneshot,
@@ -129,7 +131,7 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
-    fn idle(&mut self, _subsystem: Option<IdleEvent>) -> MpdResult<Vec<IdleEvent>> {
+    fn idle(&mut self, _subsystems: &[IdleEvent]) -> MpdResult<Vec<IdleEvent>> {
         todo!("Not yet implemented")
     }
 
@@ -170,6 +172,10 @@ impl MpdClient for TestMpdClient {
         Ok(self.status.clone())
     }
 
+    fn stats(&mut self) -> MpdResult<Stats> {
+        todo!("Not yet implemented")
+    }
+
     fn pause_toggle(&mut self) -> MpdResult<()> {
         use crate::mpd::commands::State as S;
         self.status.state = match self.status.state {
@@ -239,6 +245,10 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn seek_current_to(&mut self, _position: Duration) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
     fn repeat(&mut self, enabled: bool) -> MpdResult<()> {
         self.status.repeat = enabled;
         Ok(())
@@ -271,6 +281,10 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn list_neighbors(&mut self) -> MpdResult<crate::mpd::commands::Neighbors> {
+        todo!("Not yet implemented")
+    }
+
     fn add(&mut self, _path: &str) -> MpdResult<()> {
         todo!("Not yet implemented")
     }
@@ -290,6 +304,10 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn shuffle(&mut self, _range: Option<SingleOrRange>) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
     fn playlist_info(&mut self, _: bool) -> MpdResult<Option<Vec<Song>>> {
         Ok(Some(self.queue.iter().map(|idx| self.songs[*idx].clone()).collect_vec()))
     }
@@ -416,6 +434,14 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn list_tag_grouped(&mut self, _tag: Tag, _group: Tag) -> MpdResult<GroupedList> {
+        todo!("Not yet implemented")
+    }
+
+    fn count_grouped(&mut self, _group: Tag) -> MpdResult<GroupedCount> {
+        todo!("Not yet implemented")
+    }
+
     fn lsinfo(&mut self, _path: Option<&str>) -> MpdResult<LsInfo> {
         todo!("Not yet implemented")
     }
@@ -535,6 +561,14 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn solo_output(&mut self, _id: u32) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn set_output_attribute(&mut self, _id: u32, _name: &str, _value: &str) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
     fn decoders(&mut self) -> MpdResult<crate::mpd::commands::decoders::Decoders> {
         todo!("Not yet implemented")
     }
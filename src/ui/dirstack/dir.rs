@@ -48,6 +48,23 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> Dir<T> {
         return Self { items, state, filter: None, matched_item_count: 0 };
     }
 
+    /// Replaces the items while keeping the selected item selected if it is
+    /// still present. Otherwise the item at the same position is selected.
+    pub fn replace_items(&mut self, items: Vec<T>, scrolloff: usize) {
+        let selected = self.selected_with_idx().map(|(idx, item)| (idx, item.as_path().to_owned()));
+        let idx = selected.and_then(|(idx, path)| {
+            items
+                .iter()
+                .position(|item| item.as_path() == path)
+                .or_else(|| Some(idx.min(items.len().checked_sub(1)?)))
+        });
+
+        self.state.unmark_all();
+        self.state.set_content_len(Some(items.len()));
+        self.items = items;
+        self.state.select(idx.or((!self.items.is_empty()).then_some(0)), scrolloff);
+    }
+
     pub fn filter(&self) -> Option<&str> {
         self.filter.as_deref()
     }
@@ -89,6 +89,44 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> DirStack<T> {
         }
     }
 
+    /// Replaces the items of every opened directory with `levels`, the first
+    /// level being the root. `origin_path` is the path the levels were fetched
+    /// for, levels of directories which are not open anymore are skipped.
+    /// Selected items are kept and directories whose item no longer exists
+    /// are closed.
+    pub fn replace_levels(
+        &mut self,
+        origin_path: &[String],
+        levels: Vec<Vec<T>>,
+        scrolloff: usize,
+    ) {
+        for (depth, items) in levels.into_iter().enumerate() {
+            if depth > self.path.len()
+                || depth > origin_path.len()
+                || self.path[..depth] != origin_path[..depth]
+            {
+                break;
+            }
+
+            if depth == self.path.len() {
+                self.current.replace_items(items, scrolloff);
+                continue;
+            }
+
+            let opened = &self.path[depth];
+            let Some(idx) = items.iter().position(|item| item.as_path() == opened) else {
+                while self.path.len() > depth {
+                    self.pop();
+                }
+                self.current.replace_items(items, scrolloff);
+                break;
+            };
+            let dir = &mut self.others[depth + 1];
+            dir.replace_items(items, scrolloff);
+            dir.select_idx(idx, scrolloff);
+        }
+    }
+
     pub fn push(&mut self, head: Vec<T>) {
         let mut new_state = DirState::default();
         if !head.is_empty() {
@@ -238,4 +276,67 @@ mod tests {
             val.previous();
         }
     }
+
+    mod replace_levels {
+        use crate::ui::dirstack::DirStack;
+
+        fn strings(values: &[&str]) -> Vec<String> {
+            values.iter().map(|v| (*v).to_owned()).collect()
+        }
+
+        fn subject() -> DirStack<String> {
+            let mut subject: DirStack<String> = DirStack::new(strings(&["a", "b", "c"]));
+            subject.current_mut().state.select(Some(1), 0);
+            subject.push(strings(&["d", "e", "f"]));
+            subject.current_mut().state.select(Some(2), 0);
+            subject
+        }
+
+        #[test]
+        fn keeps_selected_items() {
+            let mut subject = subject();
+
+            subject.replace_levels(
+                &strings(&["b"]),
+                vec![strings(&["0", "a", "b", "c"]), strings(&["f", "g"])],
+                0,
+            );
+
+            assert_eq!(subject.path(), strings(&["b"]));
+            assert_eq!(subject.previous().items, strings(&["0", "a", "b", "c"]));
+            assert_eq!(subject.previous().selected(), Some(&"b".to_owned()));
+            assert_eq!(subject.current().items, strings(&["f", "g"]));
+            assert_eq!(subject.current().selected(), Some(&"f".to_owned()));
+        }
+
+        #[test]
+        fn closes_removed_directories() {
+            let mut subject = subject();
+
+            subject.replace_levels(
+                &strings(&["b"]),
+                vec![strings(&["a", "c"]), strings(&["f", "g"])],
+                0,
+            );
+
+            assert_eq!(subject.path(), Vec::<String>::new());
+            assert_eq!(subject.current().items, strings(&["a", "c"]));
+            assert_eq!(subject.current().selected(), Some(&"c".to_owned()));
+        }
+
+        #[test]
+        fn skips_levels_of_other_directories() {
+            let mut subject = subject();
+
+            subject.replace_levels(
+                &strings(&["a"]),
+                vec![strings(&["a", "b", "c", "d"]), strings(&["x"])],
+                0,
+            );
+
+            assert_eq!(subject.path(), strings(&["b"]));
+            assert_eq!(subject.previous().items, strings(&["a", "b", "c", "d"]));
+            assert_eq!(subject.current().items, strings(&["d", "e", "f"]));
+        }
+    }
 }
@@ -0,0 +1,182 @@
+use anyhow::Result;
+use ratatui::layout::Rect;
+
+use super::{facade::AlbumArtFacade, worker::ImageWorker};
+use crate::{
+    MpdQueryResult,
+    config::{Config, tabs::PaneType},
+    context::AppContext,
+    mpd::{client::Client, commands::Song},
+    shared::album_art::find_album_art,
+};
+
+pub const ALBUM_COVER: &str = "album_cover";
+
+/// Cover of an album displayed inside of a pane alongside other images. The
+/// image is shown only once its area stayed the same for a whole frame so that
+/// rendering the pane does not overwrite it.
+#[derive(Debug)]
+pub struct AlbumCover {
+    facade: AlbumArtFacade,
+    /// Album whose cover is shown or requested
+    album: Option<String>,
+    data: Option<Vec<u8>>,
+    area: Rect,
+    shown_area: Option<Rect>,
+    is_hidden: bool,
+    /// Whether a new request replaces the pending one
+    replace_pending: bool,
+}
+
+impl AlbumCover {
+    /// Whether album covers are displayed in the preview column of the
+    /// browsers
+    pub fn is_preview_enabled(config: &Config) -> bool {
+        config.album_art.browser_preview && AlbumArtFacade::supports_multiple_images(config)
+    }
+
+    /// Cover of which only the latest request matters
+    pub fn preview(context: &AppContext) -> Self {
+        Self { replace_pending: true, ..Self::new(context) }
+    }
+
+    pub fn new(context: &AppContext) -> Self {
+        Self::with_facade(AlbumArtFacade::new(context.config))
+    }
+
+    /// Cover displayed by a worker shared with other covers
+    pub fn with_worker(context: &AppContext, worker: ImageWorker) -> Self {
+        Self::with_facade(AlbumArtFacade::with_worker(context.config, worker))
+    }
+
+    fn with_facade(facade: AlbumArtFacade) -> Self {
+        Self {
+            facade,
+            album: None,
+            data: None,
+            area: Rect::default(),
+            shown_area: None,
+            is_hidden: false,
+            replace_pending: false,
+        }
+    }
+
+    pub fn album(&self) -> Option<&str> {
+        self.album.as_deref()
+    }
+
+    pub fn has_image(&self) -> bool {
+        self.data.is_some()
+    }
+
+    /// Requests the cover of the album, hiding the cover of the previous one.
+    /// `find_song` returns the song whose album art is used as the cover.
+    pub fn request(
+        &mut self,
+        album: Option<&str>,
+        target: PaneType,
+        context: &AppContext,
+        find_song: impl FnOnce(&mut Client<'_>) -> Result<Option<Song>> + Send + 'static,
+    ) -> Result<()> {
+        if self.album.as_deref() == album {
+            return Ok(());
+        }
+        self.clear()?;
+        let Some(album) = album else {
+            return Ok(());
+        };
+        self.album = Some(album.to_owned());
+
+        let album = album.to_owned();
+        let config = &context.config.album_art;
+        let cache_dir = context.config.cache_dir;
+        let mpd_supported = context.supported_commands.contains("albumart")
+            && context.supported_commands.contains("readpicture");
+        let replace_id = self.replace_pending.then_some(ALBUM_COVER);
+        context.query().id(ALBUM_COVER).maybe_replace_id(replace_id).target(target).query(
+            move |client| {
+                let data = match find_song(client)? {
+                    Some(song) => find_album_art(client, &song, config, cache_dir, mpd_supported)?,
+                    None => None,
+                };
+                Ok(MpdQueryResult::AlbumCover { album, data })
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Stores the received cover if it belongs to the requested album
+    pub fn receive(
+        &mut self,
+        album: &str,
+        data: Option<Vec<u8>>,
+        context: &AppContext,
+    ) -> Result<()> {
+        if self.album.as_deref() != Some(album) {
+            return Ok(());
+        }
+        self.data = data;
+        self.area = Rect::default();
+        context.render()?;
+
+        Ok(())
+    }
+
+    /// Displays the cover in the area once the area is stable. Should be
+    /// called on every render of the owning pane.
+    pub fn render(&mut self, area: Rect, context: &AppContext) -> Result<()> {
+        let Some(data) = &self.data else {
+            return Ok(());
+        };
+        if self.is_hidden || self.shown_area == Some(area) {
+            return Ok(());
+        }
+        if self.area != area {
+            self.area = area;
+            context.render()?;
+            return Ok(());
+        }
+
+        if self.shown_area.is_some() {
+            self.facade.hide()?;
+        }
+        self.facade.set_size(area);
+        if area.is_empty() {
+            self.shown_area = None;
+        } else {
+            self.facade.show(data.clone())?;
+            self.shown_area = Some(area);
+        }
+
+        Ok(())
+    }
+
+    /// Hides the cover until [`Self::show`] is called, for example while a
+    /// modal is open
+    pub fn hide(&mut self) -> Result<()> {
+        self.is_hidden = true;
+        if self.shown_area.take().is_some() {
+            self.facade.hide()?;
+        }
+        Ok(())
+    }
+
+    pub fn show(&mut self) {
+        self.is_hidden = false;
+    }
+
+    /// Hides the cover and forgets its album
+    pub fn clear(&mut self) -> Result<()> {
+        self.album = None;
+        self.data = None;
+        if self.shown_area.take().is_some() {
+            self.facade.clear()?;
+        }
+        Ok(())
+    }
+
+    pub fn cleanup(&mut self) -> Result<()> {
+        self.facade.cleanup()
+    }
+}
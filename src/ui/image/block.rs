@@ -0,0 +1,314 @@
+use std::{
+    io::Write,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Instant,
+};
+
+use anyhow::{Context, Result};
+use crossterm::{
+    cursor::{RestorePosition, SavePosition},
+    queue,
+    style::Colors,
+};
+use image::{
+    Rgb,
+    RgbImage,
+    imageops::{FilterType, colorops::ColorMap, dither},
+};
+use ratatui::{layout::Rect, style::Color};
+
+use super::{
+    Backend,
+    clear_area,
+    csi_move,
+    worker::{ImageWorker, next_image_id},
+};
+use crate::{
+    config::{
+        Size,
+        album_art::{HorizontalAlign, VerticalAlign},
+    },
+    shared::{env::ENV, image::resize_image},
+};
+
+/// Draws images as text using the upper half block character. Every cell
+/// displays two pixels, the upper one as the foreground color and the lower one
+/// as the background color.
+#[derive(Debug)]
+pub struct Block {
+    worker: ImageWorker,
+    image_id: u32,
+    colors: Colors,
+    max_size: Size,
+    halign: HorizontalAlign,
+    valign: VerticalAlign,
+    truecolor: bool,
+    is_showing: Arc<AtomicBool>,
+}
+
+impl Backend for Block {
+    fn hide(&mut self, size: Rect) -> Result<()> {
+        clear_area(&mut std::io::stdout().lock(), self.colors, size)
+    }
+
+    fn show(&mut self, data: Arc<Vec<u8>>, area: Rect) -> Result<()> {
+        let Self { colors, max_size, halign, valign, truecolor, .. } = *self;
+        let is_showing = Arc::clone(&self.is_showing);
+        self.worker.submit(self.image_id, move || {
+            let buf = encode(&data, area, max_size, halign, valign, truecolor)?;
+            Ok(Box::new(move || {
+                let mut w = std::io::stdout().lock();
+                if !is_showing.load(Ordering::Relaxed) {
+                    log::trace!(
+                        "Not showing image because its not supposed to be displayed anymore"
+                    );
+                    return Ok(());
+                }
+
+                clear_area(&mut w, colors, area).context("Failed to clear block image area")?;
+                display(&mut w, &buf).context("Failed to display block image")
+            }))
+        })
+    }
+}
+
+impl Block {
+    pub fn new(
+        worker: ImageWorker,
+        max_size: Size,
+        bg_color: Option<Color>,
+        halign: HorizontalAlign,
+        valign: VerticalAlign,
+        is_showing: Arc<AtomicBool>,
+    ) -> Self {
+        let colors = Colors { background: bg_color.map(Into::into), foreground: None };
+
+        Self {
+            worker,
+            image_id: next_image_id(),
+            colors,
+            max_size,
+            halign,
+            valign,
+            truecolor: is_truecolor_supported(),
+            is_showing,
+        }
+    }
+}
+
+fn is_truecolor_supported() -> bool {
+    ENV.var("COLORTERM").is_ok_and(|value| value == "truecolor" || value == "24bit")
+}
+
+fn display(w: &mut impl Write, data: &[u8]) -> Result<()> {
+    log::debug!(bytes = data.len(); "transmitting data");
+    queue!(w, SavePosition)?;
+    w.write_all(data)?;
+    w.flush()?;
+    queue!(w, RestorePosition)?;
+
+    Ok(())
+}
+
+fn encode(
+    data: &[u8],
+    area: Rect,
+    max_size: Size,
+    halign: HorizontalAlign,
+    valign: VerticalAlign,
+    truecolor: bool,
+) -> Result<Vec<u8>> {
+    let start = Instant::now();
+
+    let (image, aligned_area) = resize_image(data, area, max_size, halign, valign)?;
+    let area = aligned_area.area;
+    // Cells are assumed to be twice as tall as they are wide if the terminal
+    // did not report its size in pixels
+    let image =
+        image.resize(u32::from(area.width), u32::from(area.height) * 2, FilterType::Triangle);
+    let mut image = image.to_rgb8();
+    if !truecolor {
+        dither(&mut image, &XtermPalette);
+    }
+
+    let buf = encode_half_blocks(&image, area, halign, valign, truecolor)?;
+    log::debug!(bytes = buf.len(), elapsed:? = start.elapsed(); "encoded data");
+
+    Ok(buf)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn encode_half_blocks(
+    image: &RgbImage,
+    area: Rect,
+    halign: HorizontalAlign,
+    valign: VerticalAlign,
+    truecolor: bool,
+) -> Result<Vec<u8>> {
+    // image was resized to fit the area so its size fits into u16
+    let width = image.width() as u16;
+    let height = image.height().div_ceil(2) as u16;
+    let x = match halign {
+        HorizontalAlign::Left => area.x,
+        HorizontalAlign::Center => area.x + area.width.saturating_sub(width) / 2,
+        HorizontalAlign::Right => area.right().saturating_sub(width),
+    };
+    let y = match valign {
+        VerticalAlign::Top => area.y,
+        VerticalAlign::Center => area.y + area.height.saturating_sub(height) / 2,
+        VerticalAlign::Bottom => area.bottom().saturating_sub(height),
+    };
+
+    let mut buf = Vec::new();
+    for row in 0..height {
+        csi_move!(buf, x, y + row)?;
+        for col in 0..width {
+            let upper = image.get_pixel(u32::from(col), u32::from(row) * 2);
+            let lower = image.get_pixel_checked(u32::from(col), u32::from(row) * 2 + 1);
+            write_color(&mut buf, 38, *upper, truecolor)?;
+            if let Some(lower) = lower {
+                write_color(&mut buf, 48, *lower, truecolor)?;
+            }
+            write!(buf, "▀")?;
+        }
+        write!(buf, "\x1b[0m")?;
+    }
+
+    Ok(buf)
+}
+
+/// Writes SGR sequence setting the foreground (38) or background (48) color
+fn write_color(buf: &mut Vec<u8>, target: u8, color: Rgb<u8>, truecolor: bool) -> Result<()> {
+    let Rgb([r, g, b]) = color;
+    if truecolor {
+        write!(buf, "\x1b[{target};2;{r};{g};{b}m")?;
+    } else {
+        write!(buf, "\x1b[{target};5;{}m", XtermPalette.index_of(&color))?;
+    }
+    Ok(())
+}
+
+/// Color cube and grayscale ramp of the 256 color palette. The first 16 colors
+/// are skipped because terminals commonly change them.
+///
+/// Unlike sixel, which defines its own palette and can use the one computed by
+/// `color_quant`, SGR sequences can only refer to the fixed colors of the
+/// terminal, so the image is dithered to those directly.
+struct XtermPalette;
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl XtermPalette {
+    fn nearest_level(value: u8) -> usize {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, level)| level.abs_diff(value))
+            .map_or(0, |(idx, _)| idx)
+    }
+
+    fn distance(a: Rgb<u8>, b: Rgb<u8>) -> u32 {
+        a.0.iter().zip(b.0).map(|(a, b)| u32::from(a.abs_diff(b)).pow(2)).sum()
+    }
+}
+
+impl ColorMap for XtermPalette {
+    type Color = Rgb<u8>;
+
+    fn index_of(&self, color: &Rgb<u8>) -> usize {
+        let Rgb([r, g, b]) = *color;
+        let (r, g, b) = (Self::nearest_level(r), Self::nearest_level(g), Self::nearest_level(b));
+        let cube_idx = 16 + 36 * r + 6 * g + b;
+
+        let average = color.0.iter().map(|c| u32::from(*c)).sum::<u32>() / 3;
+        let gray_idx = 232 + (average.saturating_sub(3) / 10).min(23) as usize;
+
+        [cube_idx, gray_idx]
+            .into_iter()
+            .min_by_key(|idx| {
+                self.lookup(*idx).map_or(u32::MAX, |palette| Self::distance(*color, palette))
+            })
+            .unwrap_or(cube_idx)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn lookup(&self, index: usize) -> Option<Rgb<u8>> {
+        match index {
+            16..=231 => {
+                let index = index - 16;
+                Some(Rgb([
+                    CUBE_LEVELS[index / 36],
+                    CUBE_LEVELS[index / 6 % 6],
+                    CUBE_LEVELS[index % 6],
+                ]))
+            }
+            232..=255 => {
+                let value = 8 + (index - 232) as u8 * 10;
+                Some(Rgb([value, value, value]))
+            }
+            _ => None,
+        }
+    }
+
+    fn has_lookup(&self) -> bool {
+        true
+    }
+
+    fn map_color(&self, color: &mut Rgb<u8>) {
+        if let Some(mapped) = self.lookup(self.index_of(color)) {
+            *color = mapped;
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use image::{Rgb, RgbImage, imageops::colorops::ColorMap};
+    use ratatui::layout::Rect;
+
+    use super::{XtermPalette, encode_half_blocks};
+    use crate::config::album_art::{HorizontalAlign, VerticalAlign};
+
+    #[test]
+    fn maps_colors_to_xterm_palette() {
+        assert_eq!(XtermPalette.index_of(&Rgb([255, 0, 0])), 196);
+        assert_eq!(XtermPalette.index_of(&Rgb([0, 0, 0])), 16);
+        assert_eq!(XtermPalette.index_of(&Rgb([128, 128, 128])), 244);
+        assert_eq!(XtermPalette.lookup(196), Some(Rgb([255, 0, 0])));
+        assert_eq!(XtermPalette.lookup(244), Some(Rgb([128, 128, 128])));
+    }
+
+    #[test]
+    fn encodes_two_pixels_per_cell() {
+        let mut image = RgbImage::new(2, 3);
+        image.put_pixel(0, 0, Rgb([255, 0, 0]));
+        image.put_pixel(0, 1, Rgb([0, 0, 255]));
+
+        let buf = encode_half_blocks(
+            &image,
+            Rect::new(1, 1, 4, 2),
+            HorizontalAlign::Center,
+            VerticalAlign::Top,
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            concat!(
+                "\x1b[2;3H",
+                "\x1b[38;2;255;0;0m\x1b[48;2;0;0;255m▀",
+                "\x1b[38;2;0;0;0m\x1b[48;2;0;0;0m▀",
+                "\x1b[0m",
+                "\x1b[3;3H",
+                "\x1b[38;2;0;0;0m▀",
+                "\x1b[38;2;0;0;0m▀",
+                "\x1b[0m",
+            )
+        );
+    }
+}
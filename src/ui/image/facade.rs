@@ -8,24 +8,26 @@ use ratatui::layout::Rect;
 
 use super::{
     Backend,
+    block::Block,
     iterm2::Iterm2,
     kitty::Kitty,
     sixel::Sixel,
     ueberzug::{Layer, Ueberzug},
+    worker::ImageWorker,
 };
 use crate::{
     config::{Config, album_art::ImageMethod},
     shared::image::ImageProtocol,
 };
 
-pub static IS_SHOWING: AtomicBool = AtomicBool::new(false);
-
 #[derive(Debug)]
 pub struct AlbumArtFacade {
     image_state: ImageState,
     current_album_art: Option<Arc<Vec<u8>>>,
     default_album_art: Arc<Vec<u8>>,
     last_size: Rect,
+    /// Whether the backend should still display the image once it is encoded
+    is_showing: Arc<AtomicBool>,
 }
 
 #[derive(Debug, Default)]
@@ -34,30 +36,65 @@ enum ImageState {
     Ueberzug(Ueberzug),
     Iterm2(Iterm2),
     Sixel(Sixel),
+    Block(Block),
     #[default]
     None,
 }
 
 impl AlbumArtFacade {
     pub fn new(config: &Config) -> Self {
+        Self::create(config, None)
+    }
+
+    /// Facade whose image is encoded and displayed by the given worker, so
+    /// that multiple images do not need a thread each
+    pub fn with_worker(config: &Config, worker: ImageWorker) -> Self {
+        Self::create(config, Some(worker))
+    }
+
+    fn create(config: &Config, worker: Option<ImageWorker>) -> Self {
         let max_size = config.album_art.max_size_px;
         let bg_color = config.theme.background_color;
         let valign = config.album_art.vertical_align;
         let halign = config.album_art.horizontal_align;
+        let is_showing = Arc::new(AtomicBool::new(false));
         let proto = match config.album_art.method.into() {
-            ImageProtocol::Kitty => {
-                ImageState::Kitty(Kitty::new(max_size, bg_color, halign, valign))
-            }
+            ImageProtocol::Kitty => ImageState::Kitty(Kitty::new(
+                worker.unwrap_or_else(ImageWorker::new),
+                max_size,
+                bg_color,
+                halign,
+                valign,
+                Arc::clone(&is_showing),
+            )),
             ImageProtocol::UeberzugWayland => {
                 ImageState::Ueberzug(Ueberzug::new(Layer::Wayland, max_size))
             }
             ImageProtocol::UeberzugX11 => ImageState::Ueberzug(Ueberzug::new(Layer::X11, max_size)),
-            ImageProtocol::Iterm2 => {
-                ImageState::Iterm2(Iterm2::new(max_size, bg_color, halign, valign))
-            }
-            ImageProtocol::Sixel => {
-                ImageState::Sixel(Sixel::new(max_size, bg_color, halign, valign))
-            }
+            ImageProtocol::Iterm2 => ImageState::Iterm2(Iterm2::new(
+                worker.unwrap_or_else(ImageWorker::new),
+                max_size,
+                bg_color,
+                halign,
+                valign,
+                Arc::clone(&is_showing),
+            )),
+            ImageProtocol::Sixel => ImageState::Sixel(Sixel::new(
+                worker.unwrap_or_else(ImageWorker::new),
+                max_size,
+                bg_color,
+                halign,
+                valign,
+                Arc::clone(&is_showing),
+            )),
+            ImageProtocol::Block => ImageState::Block(Block::new(
+                worker.unwrap_or_else(ImageWorker::new),
+                max_size,
+                bg_color,
+                halign,
+                valign,
+                Arc::clone(&is_showing),
+            )),
             ImageProtocol::None => ImageState::None,
         };
         Self {
@@ -65,9 +102,29 @@ impl AlbumArtFacade {
             current_album_art: None,
             last_size: Rect::default(),
             default_album_art: Arc::new(config.theme.default_album_art.to_vec()),
+            is_showing,
         }
     }
 
+    /// Whether the configured image method can display more than one image at
+    /// once
+    pub fn supports_multiple_images(config: &Config) -> bool {
+        matches!(
+            config.album_art.method.into(),
+            ImageProtocol::Kitty
+                | ImageProtocol::Iterm2
+                | ImageProtocol::Sixel
+                | ImageProtocol::Block
+        )
+    }
+
+    /// Hides the image and forgets it so that it is not displayed again by
+    /// [`Self::show_current`]
+    pub fn clear(&mut self) -> Result<()> {
+        self.current_album_art = None;
+        self.hide()
+    }
+
     pub fn show_default(&mut self) -> Result<()> {
         self.current_album_art = Some(Arc::clone(&self.default_album_art));
         self.show_current()
@@ -79,7 +136,7 @@ impl AlbumArtFacade {
             return Ok(());
         };
 
-        IS_SHOWING.store(true, Ordering::Relaxed);
+        self.is_showing.store(true, Ordering::Relaxed);
 
         let data = Arc::clone(current_album_art);
         log::debug!(bytes = data.len(), area:? = self.last_size; "Displaying current image again",);
@@ -89,12 +146,13 @@ impl AlbumArtFacade {
             ImageState::Ueberzug(ueberzug) => ueberzug.show(data, self.last_size),
             ImageState::Iterm2(iterm2) => iterm2.show(data, self.last_size),
             ImageState::Sixel(s) => s.show(data, self.last_size),
+            ImageState::Block(b) => b.show(data, self.last_size),
             ImageState::None => Ok(()),
         }
     }
 
     pub fn show(&mut self, data: Vec<u8>) -> Result<()> {
-        IS_SHOWING.store(true, Ordering::Relaxed);
+        self.is_showing.store(true, Ordering::Relaxed);
 
         log::debug!(bytes = data.len(), area:? = self.last_size; "New image received",);
         let data = Arc::new(data);
@@ -105,17 +163,19 @@ impl AlbumArtFacade {
             ImageState::Ueberzug(ueberzug) => ueberzug.show(data, self.last_size),
             ImageState::Iterm2(iterm2) => iterm2.show(data, self.last_size),
             ImageState::Sixel(s) => s.show(data, self.last_size),
+            ImageState::Block(b) => b.show(data, self.last_size),
             ImageState::None => Ok(()),
         }
     }
 
     pub fn hide(&mut self) -> Result<()> {
-        IS_SHOWING.store(false, Ordering::Relaxed);
+        self.is_showing.store(false, Ordering::Relaxed);
         match &mut self.image_state {
             ImageState::Kitty(kitty) => kitty.hide(self.last_size)?,
             ImageState::Ueberzug(ueberzug) => ueberzug.hide(self.last_size)?,
             ImageState::Iterm2(iterm2) => iterm2.hide(self.last_size)?,
             ImageState::Sixel(s) => s.hide(self.last_size)?,
+            ImageState::Block(b) => b.hide(self.last_size)?,
             ImageState::None => {}
         }
         Ok(())
@@ -123,12 +183,13 @@ impl AlbumArtFacade {
 
     pub fn cleanup(&mut self) -> Result<()> {
         let state = std::mem::take(&mut self.image_state);
-        IS_SHOWING.store(false, Ordering::Relaxed);
+        self.is_showing.store(false, Ordering::Relaxed);
         match state {
             ImageState::Kitty(kitty) => Box::new(kitty).cleanup(self.last_size),
             ImageState::Ueberzug(ueberzug) => Box::new(ueberzug).cleanup(self.last_size),
             ImageState::Iterm2(iterm2) => Box::new(iterm2).cleanup(self.last_size),
             ImageState::Sixel(s) => Box::new(s).cleanup(self.last_size),
+            ImageState::Block(b) => Box::new(b).cleanup(self.last_size),
             ImageState::None => Ok(()),
         }
     }
@@ -146,6 +207,7 @@ impl From<ImageMethod> for ImageProtocol {
             ImageMethod::UeberzugX11 => ImageProtocol::UeberzugX11,
             ImageMethod::Iterm2 => ImageProtocol::Iterm2,
             ImageMethod::Sixel => ImageProtocol::Sixel,
+            ImageMethod::Block => ImageProtocol::Block,
             ImageMethod::None | ImageMethod::Unsupported => ImageProtocol::None,
         }
     }
@@ -1,11 +1,13 @@
 use std::{
     io::Write,
-    sync::{Arc, atomic::Ordering},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
 };
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 use base64::Engine;
-use crossbeam::channel::{Sender, unbounded};
 use crossterm::{
     cursor::{MoveTo, RestorePosition, SavePosition},
     queue,
@@ -13,19 +15,20 @@ use crossterm::{
 };
 use ratatui::{layout::Rect, style::Color};
 
-use super::Backend;
+use super::{
+    Backend,
+    worker::{ImageWorker, next_image_id},
+};
 use crate::{
     config::{
         Size,
         album_art::{HorizontalAlign, VerticalAlign},
     },
     shared::{
-        ext::mpsc::RecvLast,
         image::{create_aligned_area, get_gif_frames, jpg_encode, resize_image},
-        macros::try_cont,
         tmux::tmux_write,
     },
-    ui::image::{clear_area, facade::IS_SHOWING},
+    ui::image::clear_area,
 };
 
 #[derive(Debug)]
@@ -37,16 +40,15 @@ struct EncodedData {
     img_height_px: u32,
 }
 
-#[derive(Debug)]
-struct DataToEncode {
-    area: Rect,
-    data: Arc<Vec<u8>>,
-}
-
 #[derive(Debug)]
 pub struct Iterm2 {
-    sender: Sender<DataToEncode>,
+    worker: ImageWorker,
+    image_id: u32,
     colors: Colors,
+    max_size: Size,
+    halign: HorizontalAlign,
+    valign: VerticalAlign,
+    is_showing: Arc<AtomicBool>,
 }
 
 impl Backend for Iterm2 {
@@ -55,60 +57,38 @@ impl Backend for Iterm2 {
     }
 
     fn show(&mut self, data: Arc<Vec<u8>>, area: Rect) -> Result<()> {
-        Ok(self.sender.send(DataToEncode { area, data })?)
+        let Self { colors, max_size, halign, valign, .. } = *self;
+        let is_showing = Arc::clone(&self.is_showing);
+        self.worker.submit(self.image_id, move || {
+            let encoded = encode(area, &data, max_size, halign, valign)?;
+            Ok(Box::new(move || {
+                let mut w = std::io::stdout().lock();
+                if !is_showing.load(Ordering::Relaxed) {
+                    log::trace!(
+                        "Not showing image because its not supposed to be displayed anymore"
+                    );
+                    return Ok(());
+                }
+
+                clear_area(&mut w, colors, area).context("Failed to clear iterm2 image area")?;
+                display(&mut w, encoded).context("Failed to display iterm2 image")
+            }))
+        })
     }
 }
 
 impl Iterm2 {
     pub fn new(
+        worker: ImageWorker,
         max_size: Size,
         bg_color: Option<Color>,
         halign: HorizontalAlign,
         valign: VerticalAlign,
+        is_showing: Arc<AtomicBool>,
     ) -> Self {
-        let (sender, receiver) = unbounded::<DataToEncode>();
         let colors = Colors { background: bg_color.map(Into::into), foreground: None };
 
-        std::thread::Builder::new()
-            .name("iterm2".to_string())
-            .spawn(move || {
-                let mut pending_req = None;
-                loop {
-                    let Ok(DataToEncode { area, data }) =
-                        pending_req.take().ok_or(()).or_else(|()| receiver.recv_last())
-                    else {
-                        continue;
-                    };
-
-                    let encoded = try_cont!(
-                        encode(area, &data, max_size, halign, valign),
-                        "Failed to encode data"
-                    );
-
-                    let mut w = std::io::stdout().lock();
-                    if !IS_SHOWING.load(Ordering::Relaxed) {
-                        log::trace!(
-                            "Not showing image because its not supposed to be displayed anymore"
-                        );
-                        continue;
-                    }
-
-                    if let Ok(msg) = receiver.try_recv_last() {
-                        pending_req = Some(msg);
-                        log::trace!("Skipping image because another one is waiting in the queue");
-                        continue;
-                    };
-
-                    try_cont!(
-                        clear_area(&mut w, colors, area),
-                        "Failed to clear iterm2 image area"
-                    );
-                    try_cont!(display(&mut w, encoded), "Failed to display iterm2 image");
-                }
-            })
-            .expect("iterm2 thread to be spawned");
-
-        Self { sender, colors }
+        Self { worker, image_id: next_image_id(), colors, max_size, halign, valign, is_showing }
     }
 }
 
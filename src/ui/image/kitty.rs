@@ -1,12 +1,14 @@
 use std::{
     io::Write,
-    sync::{Arc, atomic::Ordering},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
     time::Instant,
 };
 
 use anyhow::{Context, Result};
 use base64::Engine;
-use crossbeam::channel::{Sender, unbounded};
 use crossterm::{
     execute,
     style::{Colors, SetColors},
@@ -15,125 +17,111 @@ use flate2::Compression;
 use itertools::Itertools;
 use ratatui::prelude::{Color, Rect};
 
-use super::{Backend, csi_move, facade::IS_SHOWING};
+use super::{
+    Backend,
+    csi_move,
+    worker::{ImageWorker, next_image_id},
+};
 use crate::{
     config::{
         Size,
         album_art::{HorizontalAlign, VerticalAlign},
     },
     shared::{
-        ext::mpsc::RecvLast,
         image::{create_aligned_area, get_gif_frames, resize_image},
-        macros::{status_error, try_cont},
+        macros::status_error,
         tmux::tmux_write,
     },
 };
 
 #[derive(Debug)]
 pub struct Kitty {
-    sender: Sender<(Arc<Vec<u8>>, Rect)>,
+    worker: ImageWorker,
     colors: Colors,
+    image_id: u32,
+    max_size: Size,
+    halign: HorizontalAlign,
+    valign: VerticalAlign,
+    is_showing: Arc<AtomicBool>,
 }
 
 impl Backend for Kitty {
     fn hide(&mut self, area: Rect) -> Result<()> {
-        clear_area(&mut std::io::stdout().lock(), self.colors, area)
+        clear_area(&mut std::io::stdout().lock(), self.colors, area, self.image_id)
     }
 
     fn show(&mut self, data: Arc<Vec<u8>>, area: Rect) -> Result<()> {
-        Ok(self.sender.send((data, area))?)
+        let Self { colors, image_id, max_size, halign, valign, .. } = *self;
+        let is_showing = Arc::clone(&self.is_showing);
+        self.worker.submit(image_id, move || {
+            let data = match create_data_to_transfer(
+                &data,
+                area,
+                Compression::new(6),
+                max_size,
+                halign,
+                valign,
+            ) {
+                Ok(data) => data,
+                Err(err) => {
+                    status_error!(err:?; "Failed to compress image data");
+                    return Err(err);
+                }
+            };
+
+            Ok(Box::new(move || {
+                let mut w = std::io::stdout().lock();
+                if !is_showing.load(Ordering::Relaxed) {
+                    log::trace!(
+                        "Not showing image because its not supposed to be displayed anymore"
+                    );
+                    return Ok(());
+                }
+
+                clear_area(&mut w, colors, area, image_id)
+                    .context("Failed to clear kitty image area")?;
+                let aligned_area = match data {
+                    Data::ImageData(data) => {
+                        transfer_image_data(
+                            &mut w,
+                            image_id,
+                            &data.content,
+                            data.img_width,
+                            data.img_height,
+                        )
+                        .context("Failed to transfer image data")?;
+                        data.aligned_area
+                    }
+                    Data::AnimationData(data) => {
+                        let aligned_area = data.aligned_area;
+                        transfer_animation_data(&mut w, data, image_id)
+                            .context("Failed to transfer animation data")?;
+                        aligned_area
+                    }
+                };
+                create_unicode_placeholder_grid(&mut w, colors, aligned_area, image_id)
+                    .context("Failed to create unicode placeholders")
+            }))
+        })
     }
 
     fn cleanup(self: Box<Self>, area: Rect) -> Result<()> {
-        clear_area(&mut std::io::stdout().lock(), self.colors, area)
+        clear_area(&mut std::io::stdout().lock(), self.colors, area, self.image_id)
     }
 }
 
 impl Kitty {
     pub fn new(
+        worker: ImageWorker,
         max_size: Size,
         bg_color: Option<Color>,
         halign: HorizontalAlign,
         valign: VerticalAlign,
+        is_showing: Arc<AtomicBool>,
     ) -> Self {
-        let (sender, receiver) = unbounded::<(Arc<Vec<_>>, Rect)>();
         let colors = Colors { background: bg_color.map(Into::into), foreground: None };
 
-        std::thread::Builder::new()
-            .name("kitty".to_string())
-            .spawn(move || {
-                let mut pending_req: Option<(Arc<Vec<_>>, Rect)> = None;
-                loop {
-                    let Ok((vec, area)) =
-                        pending_req.take().ok_or(()).or_else(|()| receiver.recv_last())
-                    else {
-                        continue;
-                    };
-
-                    let data = match create_data_to_transfer(
-                        &vec,
-                        area,
-                        Compression::new(6),
-                        max_size,
-                        halign,
-                        valign,
-                    ) {
-                        Ok(data) => data,
-                        Err(err) => {
-                            status_error!(err:?; "Failed to compress image data");
-                            continue;
-                        }
-                    };
-
-                    let mut w = std::io::stdout().lock();
-                    if !IS_SHOWING.load(Ordering::Relaxed) {
-                        log::trace!(
-                            "Not showing image because its not supposed to be displayed anymore"
-                        );
-                        continue;
-                    }
-
-                    if let Ok(msg) = receiver.try_recv_last() {
-                        pending_req = Some(msg);
-                        log::trace!("Skipping image because another one is waiting in the queue");
-                        continue;
-                    };
-
-                    try_cont!(clear_area(&mut w, colors, area), "Failed to clear kitty image area");
-                    match data {
-                        Data::ImageData(data) => {
-                            try_cont!(
-                                transfer_image_data(
-                                    &mut w,
-                                    &data.content,
-                                    data.img_width,
-                                    data.img_height
-                                ),
-                                "Failed to transfer image data"
-                            );
-
-                            try_cont!(
-                                create_unicode_placeholder_grid(&mut w, colors, data.aligned_area),
-                                "Failed to create unicode placeholders"
-                            );
-                        }
-                        Data::AnimationData(data) => {
-                            let aligned_area = data.aligned_area;
-                            try_cont!(
-                                transfer_animation_data(&mut w, data),
-                                "Failed to transfer animation data"
-                            );
-                            try_cont!(
-                                create_unicode_placeholder_grid(&mut w, colors, aligned_area),
-                                "Failed to create unicode placeholders"
-                            );
-                        }
-                    }
-                }
-            })
-            .expect("Kitty thread to be spawned");
-
-        Self { sender, colors }
+        Self { worker, colors, image_id: next_image_id(), max_size, halign, valign, is_showing }
     }
 }
 
@@ -193,18 +181,23 @@ fn create_data_to_transfer(
     }
 }
 
-fn clear_area(w: &mut impl Write, colors: Colors, area: Rect) -> Result<()> {
+fn clear_area(w: &mut impl Write, colors: Colors, area: Rect, image_id: u32) -> Result<()> {
     super::clear_area(w, colors, area)?;
-    tmux_write!(w, "\x1b_Ga=d,d=A,q=2\x1b\\")?;
+    tmux_write!(w, "\x1b_Ga=d,d=I,i={image_id},q=2\x1b\\")?;
     Ok(())
 }
 
-fn create_unicode_placeholder_grid(w: &mut impl Write, colors: Colors, area: Rect) -> Result<()> {
+fn create_unicode_placeholder_grid(
+    w: &mut impl Write,
+    colors: Colors,
+    area: Rect,
+    image_id: u32,
+) -> Result<()> {
     let mut buf = Vec::with_capacity(area.width as usize * area.height as usize * 2);
     execute!(buf, SetColors(colors))?;
     for y in 0..area.height {
         csi_move!(buf, area.left(), area.top() + y)?;
-        write!(buf, "\x1b[38;5;1m")?;
+        write_image_id_color(&mut buf, image_id)?;
 
         for x in 0..area.width {
             write!(buf, "{DELIM}{row}{col}", row = GRID[y as usize], col = GRID[x as usize])?;
@@ -218,7 +211,19 @@ fn create_unicode_placeholder_grid(w: &mut impl Write, colors: Colors, area: Rec
     Ok(())
 }
 
-fn transfer_animation_data(w: &mut impl Write, data: AnimationData) -> Result<()> {
+/// The placeholders reference the image by their foreground color. Ids which
+/// do not fit into the 256 color palette are passed as a 24-bit color.
+fn write_image_id_color(w: &mut impl Write, image_id: u32) -> Result<()> {
+    if image_id < 256 {
+        write!(w, "\x1b[38;5;{image_id}m")?;
+    } else {
+        let [_, r, g, b] = image_id.to_be_bytes();
+        write!(w, "\x1b[38;2;{r};{g};{b}m")?;
+    }
+    Ok(())
+}
+
+fn transfer_animation_data(w: &mut impl Write, data: AnimationData, image_id: u32) -> Result<()> {
     let start_time = Instant::now();
     let AnimationData { frames, is_compressed, img_width, img_height, aligned_area } = data;
 
@@ -238,7 +243,7 @@ fn transfer_animation_data(w: &mut impl Write, data: AnimationData) -> Result<()
     // Create image and transfer first frame
     tmux_write!(
         w,
-        "\x1b_Gi=1,f=32,U=1,a=T,t=d,m={m},z={delay},q=2,s={img_width},v={img_height},c={cols},r={rows}{compression};{chunk}\x1b\\",
+        "\x1b_Gi={image_id},f=32,U=1,a=T,t=d,m={m},z={delay},q=2,s={img_width},v={img_height},c={cols},r={rows}{compression};{chunk}\x1b\\",
         compression = if is_compressed { ",o=z" } else { "" },
         cols = aligned_area.width,
         rows = aligned_area.height
@@ -248,7 +253,7 @@ fn transfer_animation_data(w: &mut impl Write, data: AnimationData) -> Result<()
     while first_frame_iter.peek().is_some() {
         let chunk: String = first_frame_iter.by_ref().take(4096).collect();
         let m = i32::from(first_frame_iter.peek().is_some());
-        tmux_write!(w, "\x1b_Gi=1,m={m};{chunk}\x1b\\")?;
+        tmux_write!(w, "\x1b_Gi={image_id},m={m};{chunk}\x1b\\")?;
     }
 
     // Transfer rest of the frames, skip first because it was already
@@ -259,19 +264,19 @@ fn transfer_animation_data(w: &mut impl Write, data: AnimationData) -> Result<()
 
         tmux_write!(
             w,
-            "\x1b_Gi=1,a=f,t=d,m={m},z={delay},q=2,s={img_width},v={img_height}{compression};{chunk}\x1b\\",
+            "\x1b_Gi={image_id},a=f,t=d,m={m},z={delay},q=2,s={img_width},v={img_height}{compression};{chunk}\x1b\\",
             compression = if is_compressed { ",o=z" } else { "" }
         )?;
 
         while frame_iter.peek().is_some() {
             let chunk: String = frame_iter.by_ref().take(4096).collect();
             let m = i32::from(frame_iter.peek().is_some());
-            tmux_write!(w, "\x1b_Ga=f,i=1,m={m};{chunk}\x1b\\")?;
+            tmux_write!(w, "\x1b_Ga=f,i={image_id},m={m};{chunk}\x1b\\")?;
         }
     }
 
     // Run the animation
-    tmux_write!(w, "\x1b_Ga=a,i=1,s=3\x1b\\")?;
+    tmux_write!(w, "\x1b_Ga=a,i={image_id},s=3\x1b\\")?;
     log::debug!(duration:? = start_time.elapsed(); "Transfer finished");
 
     Ok(())
@@ -279,6 +284,7 @@ fn transfer_animation_data(w: &mut impl Write, data: AnimationData) -> Result<()
 
 fn transfer_image_data(
     w: &mut impl Write,
+    image_id: u32,
     content: &str,
     img_width: u32,
     img_height: u32,
@@ -290,7 +296,7 @@ fn transfer_image_data(
     let first: String = iter.by_ref().take(4096).collect();
     tmux_write!(
         w,
-        "\x1b_Gi=1,f=32,U=1,t=d,a=T,m=1,q=2,o=z,s={img_width},v={img_height};{first}\x1b\\"
+        "\x1b_Gi={image_id},f=32,U=1,t=d,a=T,m=1,q=2,o=z,s={img_width},v={img_height};{first}\x1b\\"
     )?;
 
     while iter.peek().is_some() {
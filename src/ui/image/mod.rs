@@ -10,11 +10,14 @@ use ratatui::layout::Rect;
 
 use crate::shared::macros::csi_move;
 
+pub mod album_cover;
+pub mod block;
 pub mod facade;
 pub mod iterm2;
 pub mod kitty;
 pub mod sixel;
 pub mod ueberzug;
+pub mod worker;
 
 #[allow(unused)]
 pub trait Backend {
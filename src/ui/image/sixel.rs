@@ -1,13 +1,15 @@
 use std::{
     io::Write,
     ops::AddAssign,
-    sync::{Arc, atomic::Ordering},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
     time::Instant,
 };
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 use color_quant::NeuQuant;
-use crossbeam::channel::{Sender, unbounded};
 use crossterm::{
     cursor::{MoveTo, RestorePosition, SavePosition},
     queue,
@@ -16,31 +18,29 @@ use crossterm::{
 use image::Rgba;
 use ratatui::{layout::Rect, style::Color};
 
-use super::{Backend, clear_area};
+use super::{
+    Backend,
+    clear_area,
+    worker::{ImageWorker, next_image_id},
+};
 use crate::{
     config::{
         Size,
         album_art::{HorizontalAlign, VerticalAlign},
     },
-    shared::{
-        ext::mpsc::RecvLast,
-        image::resize_image,
-        macros::{status_error, try_cont},
-    },
+    shared::{image::resize_image, macros::status_error},
     tmux,
-    ui::image::facade::IS_SHOWING,
 };
 
 #[derive(Debug)]
 pub struct Sixel {
-    sender: Sender<DataToEncode>,
+    worker: ImageWorker,
+    image_id: u32,
     colors: Colors,
-}
-
-#[derive(Debug)]
-struct DataToEncode {
-    area: Rect,
-    data: Arc<Vec<u8>>,
+    max_size: Size,
+    halign: HorizontalAlign,
+    valign: VerticalAlign,
+    is_showing: Arc<AtomicBool>,
 }
 
 impl Backend for Sixel {
@@ -49,57 +49,38 @@ impl Backend for Sixel {
     }
 
     fn show(&mut self, data: Arc<Vec<u8>>, area: Rect) -> Result<()> {
-        Ok(self.sender.send(DataToEncode { area, data })?)
+        let Self { colors, max_size, halign, valign, .. } = *self;
+        let is_showing = Arc::clone(&self.is_showing);
+        self.worker.submit(self.image_id, move || {
+            let (buf, resized_area) = encode(&data, area, max_size, halign, valign)?;
+            Ok(Box::new(move || {
+                let mut w = std::io::stdout().lock();
+                if !is_showing.load(Ordering::Relaxed) {
+                    log::trace!(
+                        "Not showing image because its not supposed to be displayed anymore"
+                    );
+                    return Ok(());
+                }
+
+                clear_area(&mut w, colors, area).context("Failed to clear sixel image area")?;
+                display(&mut w, &buf, resized_area).context("Failed to display sixel image")
+            }))
+        })
     }
 }
 
 impl Sixel {
     pub fn new(
+        worker: ImageWorker,
         max_size: Size,
         bg_color: Option<Color>,
         halign: HorizontalAlign,
         valign: VerticalAlign,
+        is_showing: Arc<AtomicBool>,
     ) -> Self {
-        let (sender, receiver) = unbounded::<DataToEncode>();
         let colors = Colors { background: bg_color.map(Into::into), foreground: None };
 
-        std::thread::Builder::new()
-            .name("sixel".to_string())
-            .spawn(move || {
-                let mut pending_req = None;
-                loop {
-                    let Ok(DataToEncode { area, data }) =
-                        pending_req.take().ok_or(()).or_else(|()| receiver.recv_last())
-                    else {
-                        continue;
-                    };
-
-                    let (buf, resized_area) = try_cont!(
-                        encode(&data, area, max_size, halign, valign),
-                        "Failed to encode"
-                    );
-
-                    let mut w = std::io::stdout().lock();
-                    if !IS_SHOWING.load(Ordering::Relaxed) {
-                        log::trace!(
-                            "Not showing image because its not supposed to be displayed anymore"
-                        );
-                        continue;
-                    }
-
-                    if let Ok(msg) = receiver.try_recv_last() {
-                        pending_req = Some(msg);
-                        log::trace!("Skipping image because another one is waiting in the queue");
-                        continue;
-                    };
-
-                    try_cont!(clear_area(&mut w, colors, area), "Failed to clear sixel image area");
-                    try_cont!(display(&mut w, &buf, resized_area), "Failed to display sixel image");
-                }
-            })
-            .expect("sixel thread to be spawned");
-
-        Self { sender, colors }
+        Self { worker, image_id: next_image_id(), colors, max_size, halign, valign, is_showing }
     }
 }
 
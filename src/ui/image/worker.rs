@@ -0,0 +1,90 @@
+use std::{
+    collections::VecDeque,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use anyhow::{Result, anyhow};
+use crossbeam::channel::{Receiver, Sender, unbounded};
+
+use crate::shared::macros::try_cont;
+
+/// Source of ids which identify images across all workers. Kitty passes the
+/// id to the terminal as a 24-bit color so it has to fit into 24 bits.
+static NEXT_IMAGE_ID: AtomicU32 = AtomicU32::new(0);
+
+pub fn next_image_id() -> u32 {
+    NEXT_IMAGE_ID.fetch_add(1, Ordering::Relaxed) % 0x00FF_FFFF + 1
+}
+
+/// Writes an encoded image to the terminal
+pub type Display = Box<dyn FnOnce() -> Result<()> + Send>;
+
+struct Job {
+    image_id: u32,
+    encode: Box<dyn FnOnce() -> Result<Display> + Send>,
+}
+
+/// Thread which encodes and displays the images of one or more backends, one
+/// after another. Only the latest request of every image is displayed. The
+/// thread stops once every clone of the worker is dropped.
+#[derive(Debug, Clone)]
+pub struct ImageWorker {
+    sender: Sender<Job>,
+}
+
+impl ImageWorker {
+    pub fn new() -> Self {
+        let (sender, receiver) = unbounded::<Job>();
+
+        std::thread::Builder::new()
+            .name("image_worker".to_string())
+            .spawn(move || run(&receiver))
+            .expect("image worker thread to be spawned");
+
+        Self { sender }
+    }
+
+    /// Queues the image to be encoded and displayed, replacing the request
+    /// of the same image which is still waiting in the queue
+    pub fn submit(
+        &self,
+        image_id: u32,
+        encode: impl FnOnce() -> Result<Display> + Send + 'static,
+    ) -> Result<()> {
+        self.sender
+            .send(Job { image_id, encode: Box::new(encode) })
+            .map_err(|_| anyhow!("Image worker stopped"))
+    }
+}
+
+fn run(receiver: &Receiver<Job>) {
+    let mut queue = VecDeque::new();
+    loop {
+        if queue.is_empty() {
+            let Ok(job) = receiver.recv() else {
+                log::trace!("Image channel disconnected, stopping the encoder");
+                break;
+            };
+            enqueue(&mut queue, job);
+        }
+        receiver.try_iter().for_each(|job| enqueue(&mut queue, job));
+
+        let Some(Job { image_id, encode }) = queue.pop_front() else {
+            continue;
+        };
+        let display = try_cont!(encode(), "Failed to encode image");
+
+        receiver.try_iter().for_each(|job| enqueue(&mut queue, job));
+        if queue.iter().any(|job| job.image_id == image_id) {
+            log::trace!("Skipping image because another one is waiting in the queue");
+            continue;
+        }
+
+        try_cont!(display(), "Failed to display image");
+    }
+}
+
+fn enqueue(queue: &mut VecDeque<Job>, job: Job) {
+    queue.retain(|queued| queued.image_id != job.image_id);
+    queue.push_back(job);
+}
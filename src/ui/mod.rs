@@ -8,11 +8,15 @@ use crossterm::{
 };
 use itertools::Itertools;
 use modals::{
+    confirm_modal::ConfirmModal,
     decoders::DecodersModal,
     input_modal::InputModal,
     keybinds::KeybindsModal,
+    notifications::NotificationsModal,
     outputs::OutputsModal,
+    select_modal::SelectModal,
     song_info::SongInfoModal,
+    storage::StorageModal,
 };
 use panes::{PaneContainer, Panes, pane_call};
 use ratatui::{
@@ -31,6 +35,7 @@ use crate::{
     MpdQueryResult,
     config::{
         Config,
+        MpdAddress,
         cli::Args,
         keys::GlobalAction,
         tabs::{PaneType, SizedPaneOrSplit, TabName},
@@ -43,9 +48,10 @@ use crate::{
         version::Version,
     },
     shared::{
-        events::{Level, WorkRequest},
+        events::{AppEvent, Level, WorkRequest},
+        ext::mpd_client::MpdClientExt,
         key_event::KeyEvent,
-        macros::{modal, status_error, status_info, status_warn},
+        macros::{modal, status_error, status_info},
         mouse_event::MouseEvent,
     },
 };
@@ -58,13 +64,6 @@ pub mod panes;
 pub mod tab_screen;
 pub mod widgets;
 
-#[derive(Debug)]
-pub struct StatusMessage {
-    pub message: String,
-    pub level: Level,
-    pub created: std::time::Instant,
-}
-
 #[derive(Debug)]
 pub struct Ui<'ui> {
     panes: PaneContainer<'ui>,
@@ -77,6 +76,7 @@ pub struct Ui<'ui> {
 
 const OPEN_DECODERS_MODAL: &str = "open_decoders_modal";
 const OPEN_OUTPUTS_MODAL: &str = "open_outputs_modal";
+const OPEN_STORAGE_MODAL: &str = "open_storage_modal";
 
 macro_rules! active_tab_call {
     ($self:ident, $fn:ident($($param:expr),+)) => {
@@ -377,7 +377,7 @@ impl<'ui> Ui<'ui> {
                         .query()
                         .id(OPEN_OUTPUTS_MODAL)
                         .replace_id(OPEN_OUTPUTS_MODAL)
-                        .query(|client| Ok(MpdQueryResult::Outputs(client.outputs()?.0)));
+                        .query(OutputsModal::fetch_outputs);
                 }
                 GlobalAction::ShowDecoders => {
                     context
@@ -386,6 +386,72 @@ impl<'ui> Ui<'ui> {
                         .replace_id(OPEN_DECODERS_MODAL)
                         .query(|client| Ok(MpdQueryResult::Decoders(client.decoders()?.0)));
                 }
+                GlobalAction::ShowNotifications => {
+                    modal!(context, NotificationsModal::new(context));
+                }
+                GlobalAction::ShowStorage => {
+                    context
+                        .query()
+                        .id(OPEN_STORAGE_MODAL)
+                        .replace_id(OPEN_STORAGE_MODAL)
+                        .query(StorageModal::fetch_storage);
+                }
+                GlobalAction::CycleLyricsTranslation => {
+                    self.on_event(UiEvent::CycleLyricsTranslation, context)?;
+                }
+                GlobalAction::UpdateDatabase => {
+                    context.command(|client| Ok(client.start_db_update(None, false)?));
+                }
+                GlobalAction::RescanDatabase => {
+                    modal!(
+                        context,
+                        ConfirmModal::new(context)
+                            .message("Rescanning rereads every file and can take a long time. Continue?")
+                            .on_confirm(|context| {
+                                context.command(|client| Ok(client.start_db_update(None, true)?));
+                                Ok(())
+                            })
+                            .confirm_label("Rescan")
+                            .size(45, 6)
+                    );
+                }
+                GlobalAction::ReloadConfig => {
+                    context.app_event_sender.send(AppEvent::ReloadConfig)?;
+                }
+                GlobalAction::SwitchServer if context.config.profiles.is_empty() => {
+                    status_info!("No MPD profiles are configured");
+                }
+                GlobalAction::SwitchServer => {
+                    let profiles = context.config.profiles;
+                    let options = profiles
+                        .iter()
+                        .map(|profile| {
+                            let address = match profile.address {
+                                MpdAddress::IpAndPort(addr) | MpdAddress::SocketPath(addr) => addr,
+                            };
+                            if context.config.active_profile == Some(profile.name) {
+                                format!("{} ({address}, active)", profile.name)
+                            } else {
+                                format!("{} ({address})", profile.name)
+                            }
+                        })
+                        .collect_vec();
+                    modal!(
+                        context,
+                        SelectModal::new(context)
+                            .options(options)
+                            .confirm_label("Connect")
+                            .title("Switch MPD server")
+                            .on_confirm(move |context, _label: &String, idx| {
+                                if let Some(profile) = profiles.get(idx) {
+                                    context
+                                        .app_event_sender
+                                        .send(AppEvent::SwitchProfile(profile.name))?;
+                                }
+                                Ok(())
+                            })
+                    );
+                }
                 GlobalAction::ShowCurrentSongInfo => {
                     if let Some((_, current_song)) = context.find_current_song_in_queue() {
                         modal!(context, SongInfoModal::new(current_song.clone()));
@@ -433,6 +499,33 @@ impl<'ui> Ui<'ui> {
         Ok(())
     }
 
+    /// Rebuilds panes and tabs with the new config. The active tab and the
+    /// position in the queue are kept if possible. The current UI and config
+    /// are left untouched if the new UI cannot be built.
+    pub fn reload(&mut self, config: &'static Config, context: &mut AppContext) -> Result<()> {
+        let previous_config = std::mem::replace(&mut context.config, config);
+        let ui = Ui::new(context);
+        context.config = previous_config;
+        let mut ui = ui?;
+
+        // Image backends have to release their resources before the panes
+        // are dropped
+        self.on_event(UiEvent::Exit, context)?;
+        context.config = config;
+
+        if ui.tabs.contains_key(&self.active_tab) {
+            ui.active_tab = self.active_tab;
+        }
+        let previous = std::mem::replace(self, ui);
+        self.panes.queue.restore_position(previous.panes.queue);
+
+        self.before_show(previous.area, context)?;
+        self.on_event(UiEvent::TabChanged(self.active_tab), context)?;
+        context.render()?;
+
+        Ok(())
+    }
+
     pub fn resize(&mut self, area: Rect, context: &AppContext) -> Result<()> {
         log::trace!(area:?; "Terminal was resized");
         self.calc_areas(area, context);
@@ -452,15 +545,6 @@ impl<'ui> Ui<'ui> {
     }
 
     pub fn on_event(&mut self, mut event: UiEvent, context: &AppContext) -> Result<()> {
-        match event {
-            UiEvent::Database => {
-                status_warn!(
-                    "The music database has been updated. Some parts of the UI may have been reinitialized to prevent inconsistent behaviours."
-                );
-            }
-            _ => {}
-        }
-
         let contains_pane = |p| {
             self.tabs
                 .get(&self.active_tab)
@@ -483,6 +567,10 @@ impl<'ui> Ui<'ui> {
                 Panes::Albums(p) => {
                     p.on_event(&mut event, contains_pane(PaneType::Albums), context)
                 }
+                Panes::AlbumGrid(p) => {
+                    p.on_event(&mut event, contains_pane(PaneType::AlbumGrid), context)
+                }
+                Panes::Stats(p) => p.on_event(&mut event, contains_pane(PaneType::Stats), context),
                 Panes::Artists(p) => {
                     p.on_event(&mut event, contains_pane(PaneType::Artists), context)
                 }
@@ -548,6 +636,12 @@ impl<'ui> Ui<'ui> {
                 Panes::Albums(p) => {
                     p.on_query_finished(id, data, contains_pane(PaneType::Albums), context)
                 }
+                Panes::AlbumGrid(p) => {
+                    p.on_query_finished(id, data, contains_pane(PaneType::AlbumGrid), context)
+                }
+                Panes::Stats(p) => {
+                    p.on_query_finished(id, data, contains_pane(PaneType::Stats), context)
+                }
                 Panes::Artists(p) => {
                     p.on_query_finished(id, data, contains_pane(PaneType::Artists), context)
                 }
@@ -590,6 +684,9 @@ impl<'ui> Ui<'ui> {
                 (OPEN_DECODERS_MODAL, MpdQueryResult::Decoders(decoders)) => {
                     modal!(context, DecodersModal::new(decoders));
                 }
+                (OPEN_STORAGE_MODAL, MpdQueryResult::Storage { mounts, neighbors }) => {
+                    modal!(context, StorageModal::new(mounts, neighbors));
+                }
                 (id, mut data) => {
                     // TODO a proper modal target
                     for modal in &mut self.modals {
@@ -619,14 +716,15 @@ pub enum UiEvent {
     Player,
     Database,
     StoredPlaylist,
+    Mount,
     LogAdded(Vec<u8>),
     ModalOpened,
     ModalClosed,
     Exit,
     LyricsIndexed,
+    CycleLyricsTranslation,
     SongChanged,
     Reconnected,
-    Status(String, Level),
     TabChanged(TabName),
 }
 
@@ -638,6 +736,7 @@ impl TryFrom<IdleEvent> for UiEvent {
             IdleEvent::Player => UiEvent::Player,
             IdleEvent::Database => UiEvent::Database,
             IdleEvent::StoredPlaylist => UiEvent::StoredPlaylist,
+            IdleEvent::Mount => UiEvent::Mount,
             _ => return Err(()),
         })
     }
@@ -131,6 +131,7 @@ impl Modal for KeybindsModal {
         let playlists = keybinds.playlists.to_str().collect_vec();
         let search = keybinds.search.to_str().collect_vec();
         let queue = keybinds.queue.to_str().collect_vec();
+        let lyrics = keybinds.lyrics.to_str().collect_vec();
 
         let rows = row_header(&navigation, "Global", header_style)
             .into_iter()
@@ -149,6 +150,8 @@ impl Modal for KeybindsModal {
             .chain(row(&queue, key_area.width, action_area.width, desc_area.width))
             .chain(row_header(&search, "Search", header_style))
             .chain(row(&search, key_area.width, action_area.width, desc_area.width))
+            .chain(row_header(&lyrics, "Lyrics", header_style))
+            .chain(row(&lyrics, key_area.width, action_area.width, desc_area.width))
             .collect_vec();
 
         self.scrolling_state.set_content_len(Some(rows.len()));
@@ -7,6 +7,7 @@ use ratatui::{
 use crate::{
     MpdQueryResult,
     context::AppContext,
+    mpd::client::Client,
     shared::{key_event::KeyEvent, mouse_event::MouseEvent},
 };
 
@@ -14,9 +15,12 @@ pub mod confirm_modal;
 pub mod decoders;
 pub mod input_modal;
 pub mod keybinds;
+pub mod notifications;
 pub mod outputs;
 pub mod select_modal;
 pub mod song_info;
+pub mod storage;
+pub mod tag_editor;
 
 #[allow(unused)]
 pub(super) trait Modal: std::fmt::Debug {
@@ -36,6 +40,20 @@ pub(super) trait Modal: std::fmt::Debug {
     }
 }
 
+/// Runs the command and fetches the data shown by the modal again afterwards
+/// so that the modal reflects the result of the command
+fn refresh_after(
+    context: &AppContext,
+    id: &'static str,
+    command: impl FnOnce(&mut Client<'_>) -> Result<()> + Send + 'static,
+    fetch: fn(&mut Client<'_>) -> Result<MpdQueryResult>,
+) {
+    context.query().id(id).query(move |client| {
+        command(client)?;
+        fetch(client)
+    });
+}
+
 #[allow(dead_code)]
 pub trait RectExt {
     fn centered(&self, width_percent: u16, height_percent: u16) -> Rect;
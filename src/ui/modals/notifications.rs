@@ -0,0 +1,216 @@
+use anyhow::Result;
+use itertools::Itertools;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Margin, Rect},
+    style::{Color, Style, Stylize},
+    symbols::border,
+    text::Text,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+};
+
+use super::{Modal, RectExt};
+use crate::{
+    config::keys::CommonAction,
+    context::AppContext,
+    shared::{
+        key_event::KeyEvent,
+        macros::pop_modal,
+        mouse_event::{MouseEvent, MouseEventKind},
+    },
+    ui::dirstack::DirState,
+};
+
+const TIME_WIDTH: u16 = 10;
+const LEVEL_WIDTH: u16 = 7;
+
+/// Lists every status message received since rmpc started, newest first
+#[derive(Debug)]
+pub struct NotificationsModal {
+    scrolling_state: DirState<TableState>,
+    table_area: Rect,
+}
+
+impl NotificationsModal {
+    pub fn new(context: &AppContext) -> Self {
+        let mut result = Self { scrolling_state: DirState::default(), table_area: Rect::default() };
+        result.scrolling_state.set_content_len(Some(context.notifications.history().len()));
+        result.scrolling_state.first();
+
+        result
+    }
+}
+
+/// Number of rows starting at `offset` which fit into `height` lines, at
+/// least one so the selected row can always be scrolled to
+fn rows_in_viewport(heights: &[u16], offset: usize, height: u16) -> usize {
+    let mut remaining = height;
+    heights
+        .iter()
+        .skip(offset)
+        .take_while(|row_height| {
+            let fits = **row_height <= remaining;
+            remaining = remaining.saturating_sub(**row_height);
+            fits
+        })
+        .count()
+        .max(1)
+}
+
+impl Modal for NotificationsModal {
+    fn render(&mut self, frame: &mut Frame, app: &mut AppContext) -> Result<()> {
+        let popup_area = frame.area().centered(80, 80);
+        frame.render_widget(Clear, popup_area);
+        if let Some(bg_color) = app.config.theme.modal_background_color {
+            frame.render_widget(Block::default().style(Style::default().bg(bg_color)), popup_area);
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(app.config.as_border_style())
+            .title_alignment(ratatui::prelude::Alignment::Center)
+            .title("Notifications");
+
+        let table_area = popup_area.inner(Margin { horizontal: 2, vertical: 1 });
+        let message_width = table_area.width.saturating_sub(TIME_WIDTH + LEVEL_WIDTH).max(1);
+
+        let (rows, heights): (Vec<_>, Vec<_>) = app
+            .notifications
+            .history()
+            .iter()
+            .rev()
+            .map(|notification| {
+                let lines = textwrap::wrap(&notification.message, usize::from(message_width));
+                let height = u16::try_from(lines.len()).unwrap_or(u16::MAX).max(1);
+                let row = Row::new([
+                    Cell::from(notification.timestamp.format("%H:%M:%S").to_string()),
+                    Cell::from(notification.level.to_string()).fg(Color::from(&notification.level)),
+                    Cell::from(Text::from(
+                        lines.into_iter().map(|line| line.into_owned()).join("\n"),
+                    )),
+                ])
+                .height(height);
+                (row, height)
+            })
+            .unzip();
+
+        // Messages can span multiple lines so the viewport is measured in rows
+        // which fit below the header starting at the first visible one
+        let viewport_len = rows_in_viewport(
+            &heights,
+            self.scrolling_state.offset(),
+            table_area.height.saturating_sub(1),
+        );
+        self.scrolling_state.set_content_len(Some(rows.len()));
+        self.scrolling_state.set_viewport_len(Some(viewport_len));
+
+        let table = Table::new(rows, [
+            Constraint::Length(TIME_WIDTH),
+            Constraint::Length(LEVEL_WIDTH),
+            Constraint::Fill(1),
+        ])
+        .column_spacing(0)
+        .style(app.config.as_text_style())
+        .header(Row::new(["Time", "Level", "Message"]))
+        .row_highlight_style(app.config.theme.current_item_style);
+
+        self.table_area = table_area;
+
+        frame.render_widget(block, popup_area);
+        frame.render_stateful_widget(table, table_area, self.scrolling_state.as_render_state_ref());
+        frame.render_stateful_widget(
+            app.config.as_styled_scrollbar(),
+            popup_area.inner(Margin { horizontal: 0, vertical: 1 }),
+            self.scrolling_state.as_scrollbar_state_ref(),
+        );
+
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        if let Some(action) = key.as_common_action(context) {
+            match action {
+                CommonAction::DownHalf => {
+                    self.scrolling_state.next_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::UpHalf => {
+                    self.scrolling_state.prev_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::Up => {
+                    self.scrolling_state
+                        .prev(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Down => {
+                    self.scrolling_state
+                        .next(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Bottom => {
+                    self.scrolling_state.last();
+
+                    context.render()?;
+                }
+                CommonAction::Top => {
+                    self.scrolling_state.first();
+
+                    context.render()?;
+                }
+                CommonAction::Delete => {
+                    context.notifications.clear_history();
+                    self.scrolling_state.set_content_len(Some(0));
+
+                    context.render()?;
+                }
+                CommonAction::Close => {
+                    pop_modal!(context);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &mut AppContext) -> Result<()> {
+        match event.kind {
+            MouseEventKind::ScrollDown if self.table_area.contains(event.into()) => {
+                self.scrolling_state.next(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::ScrollUp if self.table_area.contains(event.into()) => {
+                self.scrolling_state.prev(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::LeftClick => {}
+            MouseEventKind::DoubleClick => {}
+            MouseEventKind::MiddleClick => {}
+            MouseEventKind::RightClick => {}
+            MouseEventKind::ScrollDown => {}
+            MouseEventKind::ScrollUp => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rows_in_viewport;
+
+    #[test]
+    fn counts_rows_which_fit_into_viewport() {
+        let heights = [1, 3, 1, 2, 1];
+
+        assert_eq!(rows_in_viewport(&heights, 0, 5), 3);
+        assert_eq!(rows_in_viewport(&heights, 2, 5), 3);
+        assert_eq!(rows_in_viewport(&heights, 1, 2), 1);
+        assert_eq!(rows_in_viewport(&heights, 0, 0), 1);
+    }
+}
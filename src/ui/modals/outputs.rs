@@ -1,4 +1,5 @@
 use anyhow::Result;
+use crossterm::event::KeyCode;
 use ratatui::{
     layout::{Constraint, Margin, Rect},
     style::Style,
@@ -6,12 +7,16 @@ use ratatui::{
     widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
 };
 
-use super::{Modal, RectExt};
+use super::{Modal, RectExt, refresh_after};
 use crate::{
     MpdQueryResult,
     config::keys::CommonAction,
     context::AppContext,
-    mpd::{commands::Output, mpd_client::MpdClient},
+    mpd::{
+        client::Client,
+        commands::Output,
+        mpd_client::MpdClient,
+    },
     shared::{
         key_event::KeyEvent,
         macros::pop_modal,
@@ -20,11 +25,30 @@ use crate::{
     ui::dirstack::DirState,
 };
 
+const REFRESH_OUTPUTS: &str = "refresh_outputs";
+
 #[derive(Debug)]
 pub struct OutputsModal {
     scrolling_state: DirState<TableState>,
     outputs_table_area: Rect,
     outputs: Vec<Output>,
+    /// New value of the selected attribute while it is being edited
+    edited_value: Option<String>,
+}
+
+/// Row of the outputs table, attributes are listed below their output
+#[derive(Debug, Clone, Copy)]
+enum OutputRow<'a> {
+    Output(&'a Output),
+    Attribute(&'a Output, &'a str, &'a str),
+}
+
+impl<'a> OutputRow<'a> {
+    fn output(self) -> &'a Output {
+        match self {
+            OutputRow::Output(output) | OutputRow::Attribute(output, ..) => output,
+        }
+    }
 }
 
 impl OutputsModal {
@@ -33,32 +57,108 @@ impl OutputsModal {
             outputs,
             scrolling_state: DirState::default(),
             outputs_table_area: Rect::default(),
+            edited_value: None,
         };
-        result.scrolling_state.set_content_len(Some(result.outputs.len()));
+        result.scrolling_state.set_content_len(Some(result.rows().len()));
         result.scrolling_state.first();
 
         result
     }
 
+    fn rows(&self) -> Vec<OutputRow<'_>> {
+        self.outputs
+            .iter()
+            .flat_map(|output| {
+                std::iter::once(OutputRow::Output(output)).chain(output.attributes.iter().map(
+                    |(name, value)| OutputRow::Attribute(output, name.as_str(), value.as_str()),
+                ))
+            })
+            .collect()
+    }
+
+    fn selected_row(&self) -> Option<OutputRow<'_>> {
+        self.scrolling_state.get_selected().and_then(|idx| self.rows().get(idx).copied())
+    }
+
+    pub fn fetch_outputs(client: &mut Client<'_>) -> Result<MpdQueryResult> {
+        Ok(MpdQueryResult::Outputs(client.outputs()?.0))
+    }
+
+    fn refresh_outputs(
+        context: &AppContext,
+        callback: impl FnOnce(&mut Client<'_>) -> Result<()> + Send + 'static,
+    ) {
+        refresh_after(context, REFRESH_OUTPUTS, callback, Self::fetch_outputs);
+    }
+
     pub fn toggle_selected_output(&mut self, context: &AppContext) {
-        let Some(idx) = self.scrolling_state.get_selected() else {
+        let Some(OutputRow::Output(output)) = self.selected_row() else {
             return;
         };
-        let Some(output) = self.outputs.get(idx) else {
+
+        let id = output.id;
+        Self::refresh_outputs(context, move |client| Ok(client.toggle_output(id)?));
+    }
+
+    /// Enables the output of the selected row and disables all others
+    fn solo_selected_output(&mut self, context: &AppContext) {
+        let Some(row) = self.selected_row() else {
             return;
         };
 
-        let id = output.id;
-        context.query().id("refresh_outputs").query(move |client| {
-            client.toggle_output(id)?;
-            Ok(MpdQueryResult::Outputs(client.outputs()?.0))
-        });
+        let id = row.output().id;
+        Self::refresh_outputs(context, move |client| Ok(client.solo_output(id)?));
+    }
+
+    /// Toggles the selected output or starts editing the selected attribute
+    fn confirm_selected(&mut self, context: &AppContext) -> Result<()> {
+        match self.selected_row() {
+            Some(OutputRow::Output(_)) => self.toggle_selected_output(context),
+            Some(OutputRow::Attribute(_, _, value)) => {
+                self.edited_value = Some(value.to_owned());
+                context.render()?;
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    fn handle_editing_key(&mut self, key: &mut KeyEvent, context: &AppContext) -> Result<()> {
+        match key.as_common_action(context) {
+            Some(CommonAction::Close) => self.edited_value = None,
+            Some(CommonAction::Confirm) => {
+                let value = self.edited_value.take().unwrap_or_default();
+                if let Some(OutputRow::Attribute(output, name, _)) = self.selected_row() {
+                    let (id, name) = (output.id, name.to_owned());
+                    Self::refresh_outputs(context, move |client| {
+                        Ok(client.set_output_attribute(id, &name, &value)?)
+                    });
+                }
+            }
+            _ => {
+                let Some(value) = &mut self.edited_value else {
+                    return Ok(());
+                };
+                match key.code() {
+                    KeyCode::Char(c) => value.push(c),
+                    KeyCode::Backspace => {
+                        value.pop();
+                    }
+                    _ => return Ok(()),
+                }
+            }
+        }
+
+        context.render()?;
+        Ok(())
     }
 }
 
 impl Modal for OutputsModal {
     fn render(&mut self, frame: &mut ratatui::Frame, app: &mut AppContext) -> anyhow::Result<()> {
-        let popup_area = frame.area().centered_exact(60, 10);
+        // Rows, header and borders
+        let height = u16::try_from(self.rows().len()).unwrap_or(u16::MAX).saturating_add(4);
+        let popup_area = frame.area().centered_exact(60, height.max(10));
         frame.render_widget(Clear, popup_area);
         if let Some(bg_color) = app.config.theme.modal_background_color {
             frame.render_widget(Block::default().style(Style::default().bg(bg_color)), popup_area);
@@ -73,24 +173,41 @@ impl Modal for OutputsModal {
 
         let table_area = popup_area.inner(Margin { horizontal: 1, vertical: 1 });
 
-        let rows = self.outputs.iter().map(|output| {
-            Row::new([
-                Cell::from(output.id.to_string()),
-                Cell::from(output.name.clone()),
-                Cell::from(if output.enabled { "yes" } else { "no" }),
-            ])
-        });
+        let selected = self.scrolling_state.get_selected();
+        let rows: Vec<Row<'static>> = self
+            .rows()
+            .into_iter()
+            .enumerate()
+            .map(|(idx, row)| match row {
+                OutputRow::Output(output) => Row::new([
+                    Cell::from(output.id.to_string()),
+                    Cell::from(output.name.clone()),
+                    Cell::from(output.plugin.clone()),
+                    Cell::from(if output.enabled { "yes" } else { "no" }),
+                ]),
+                OutputRow::Attribute(_, name, value) => {
+                    let value = match &self.edited_value {
+                        Some(edited) if selected == Some(idx) => Cell::from(edited.clone())
+                            .style(app.config.theme.highlight_border_style),
+                        _ => Cell::from(value.to_owned()),
+                    };
+                    Row::new([Cell::default(), Cell::from(format!("  {name}")), value])
+                }
+            })
+            .collect();
 
+        self.scrolling_state.set_content_len(Some(rows.len()));
         self.scrolling_state.set_viewport_len(Some(table_area.height.into()));
 
         let table = Table::new(rows, [
             Constraint::Length(3),
             Constraint::Percentage(100),
-            Constraint::Length(10),
+            Constraint::Length(16),
+            Constraint::Length(8),
         ])
         .column_spacing(0)
         .style(app.config.as_text_style())
-        .header(Row::new(["Id", "Name", "Enabled"]))
+        .header(Row::new(["Id", "Name", "Plugin", "Enabled"]))
         .row_highlight_style(app.config.theme.current_item_style);
 
         let table_area = table_area.inner(Margin { horizontal: 1, vertical: 0 });
@@ -114,8 +231,9 @@ impl Modal for OutputsModal {
         context: &AppContext,
     ) -> Result<()> {
         match (id, data) {
-            ("refresh_outputs", MpdQueryResult::Outputs(outputs)) => {
+            (REFRESH_OUTPUTS, MpdQueryResult::Outputs(outputs)) => {
                 self.outputs = std::mem::take(outputs);
+                self.scrolling_state.set_content_len(Some(self.rows().len()));
                 context.render()?;
             }
             _ => {}
@@ -124,6 +242,10 @@ impl Modal for OutputsModal {
     }
 
     fn handle_key(&mut self, key: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        if self.edited_value.is_some() {
+            return self.handle_editing_key(key, context);
+        }
+
         if let Some(action) = key.as_common_action(context) {
             match action {
                 CommonAction::DownHalf => {
@@ -159,7 +281,10 @@ impl Modal for OutputsModal {
                     context.render()?;
                 }
                 CommonAction::Confirm => {
-                    self.toggle_selected_output(context);
+                    self.confirm_selected(context)?;
+                }
+                CommonAction::Select => {
+                    self.solo_selected_output(context);
                 }
                 CommonAction::Close => {
                     pop_modal!(context);
@@ -181,7 +306,7 @@ impl Modal for OutputsModal {
                 }
             }
             MouseEventKind::DoubleClick if self.outputs_table_area.contains(event.into()) => {
-                self.toggle_selected_output(context);
+                self.confirm_selected(context)?;
                 context.render()?;
             }
             MouseEventKind::MiddleClick => {}
@@ -9,14 +9,14 @@ use ratatui::{
     widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
 };
 
-use super::{Modal, RectExt};
+use super::{Modal, RectExt, tag_editor::TagEditorModal};
 use crate::{
     config::keys::CommonAction,
     context::AppContext,
     mpd::commands::Song,
     shared::{
         key_event::KeyEvent,
-        macros::pop_modal,
+        macros::{modal, pop_modal},
         mouse_event::{MouseEvent, MouseEventKind},
     },
     ui::dirstack::DirState,
@@ -27,13 +27,21 @@ pub struct SongInfoModal {
     scrolling_state: DirState<TableState>,
     table_area: Rect,
     song: Song,
+    /// Songs whose tags are edited instead of the displayed one, for example
+    /// marked songs
+    edited_songs: Vec<Song>,
 }
 
 impl SongInfoModal {
     pub fn new(song: Song) -> Self {
         let mut scrolling_state = DirState::default();
         scrolling_state.select(Some(0), 0);
-        Self { scrolling_state, song, table_area: Rect::default() }
+        Self { scrolling_state, song, table_area: Rect::default(), edited_songs: Vec::new() }
+    }
+
+    pub fn edited_songs(mut self, songs: Vec<Song>) -> Self {
+        self.edited_songs = songs;
+        self
     }
 
     #[allow(clippy::cast_possible_truncation)]
@@ -68,12 +76,18 @@ impl Modal for SongInfoModal {
             frame.render_widget(Block::default().style(Style::default().bg(bg_color)), popup_area);
         }
 
-        let block = Block::default()
+        let mut block = Block::default()
             .borders(Borders::ALL)
             .border_set(border::ROUNDED)
             .border_style(app.config.as_border_style())
             .title_alignment(ratatui::prelude::Alignment::Center)
             .title("Song info");
+        if !self.edited_songs.is_empty() {
+            block = block.title_bottom(format!(
+                " Rename edits the {} marked song(s) instead of this one ",
+                self.edited_songs.len()
+            ));
+        }
 
         let (key_col_width, val_col_width) = (30, 70);
         let margin = Margin { horizontal: 1, vertical: 0 };
@@ -202,6 +216,15 @@ impl Modal for SongInfoModal {
                 CommonAction::Close => {
                     pop_modal!(context);
                 }
+                CommonAction::Rename => {
+                    let songs = if self.edited_songs.is_empty() {
+                        vec![self.song.clone()]
+                    } else {
+                        std::mem::take(&mut self.edited_songs)
+                    };
+                    pop_modal!(context);
+                    modal!(context, TagEditorModal::new(songs, context));
+                }
                 _ => {}
             }
         };
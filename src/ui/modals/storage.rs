@@ -0,0 +1,378 @@
+use anyhow::Result;
+use itertools::Itertools;
+use ratatui::{
+    layout::{Constraint, Margin, Rect},
+    style::Style,
+    symbols::border,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+};
+
+use super::{Modal, RectExt, confirm_modal::ConfirmModal, input_modal::InputModal, refresh_after};
+use crate::{
+    MpdQueryResult,
+    config::keys::CommonAction,
+    context::AppContext,
+    mpd::{
+        client::Client,
+        commands::{list_mounts::Mount, list_neighbors::Neighbor},
+        mpd_client::MpdClient,
+    },
+    shared::{
+        key_event::KeyEvent,
+        macros::{modal, pop_modal, status_info},
+        mouse_event::{MouseEvent, MouseEventKind},
+    },
+    ui::dirstack::DirState,
+};
+
+const REFRESH_STORAGE: &str = "refresh_storage";
+
+#[derive(Debug)]
+pub struct StorageModal {
+    scrolling_state: DirState<TableState>,
+    table_area: Rect,
+    mounts: Vec<Mount>,
+    neighbors: Vec<Neighbor>,
+}
+
+/// Row of the storage table, mounts are listed first followed by the neighbors
+#[derive(Debug, Clone, Copy)]
+enum StorageRow<'a> {
+    Mount(&'a Mount),
+    Neighbor(&'a Neighbor),
+}
+
+impl StorageModal {
+    pub fn new(mounts: Vec<Mount>, neighbors: Vec<Neighbor>) -> Self {
+        let mut result = Self {
+            mounts,
+            neighbors,
+            scrolling_state: DirState::default(),
+            table_area: Rect::default(),
+        };
+        result.scrolling_state.set_content_len(Some(result.rows().len()));
+        result.scrolling_state.first();
+
+        result
+    }
+
+    /// Fetches current mounts and neighbors. Neighbors are optional because
+    /// MPD responds with an error when no neighbor plugin is configured.
+    // Is used as a query callback and thus needs the -> Result
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn fetch_storage(client: &mut Client<'_>) -> Result<MpdQueryResult> {
+        // Mounts are not supported by every database plugin
+        let mounts = client.list_mounts().map_or_else(
+            |err| {
+                log::warn!(err:?; "Failed to list mounts");
+                Vec::new()
+            },
+            |mounts| mounts.0,
+        );
+        let neighbors = client.list_neighbors().map(|neighbors| neighbors.0).unwrap_or_default();
+        Ok(MpdQueryResult::Storage { mounts, neighbors })
+    }
+
+    fn rows(&self) -> Vec<StorageRow<'_>> {
+        self.mounts
+            .iter()
+            .map(StorageRow::Mount)
+            .chain(self.neighbors.iter().map(StorageRow::Neighbor))
+            .collect_vec()
+    }
+
+    fn selected_row(&self) -> Option<StorageRow<'_>> {
+        self.scrolling_state.get_selected().and_then(|idx| self.rows().get(idx).copied())
+    }
+
+    fn refresh_storage(
+        context: &AppContext,
+        callback: impl FnOnce(&mut Client<'_>) -> Result<()> + Send + 'static,
+    ) {
+        refresh_after(context, REFRESH_STORAGE, callback, Self::fetch_storage);
+    }
+
+    fn mount(context: &AppContext, name: String, uri: String) {
+        Self::refresh_storage(context, move |client| {
+            client.mount(&name, &uri)?;
+            status_info!("Mounted '{uri}' at '{name}'");
+            Ok(())
+        });
+    }
+
+    /// Asks for the mount point of the selected neighbor and mounts it
+    fn confirm_selected(&mut self, context: &AppContext) -> Result<()> {
+        let Some(StorageRow::Neighbor(neighbor)) = self.selected_row() else {
+            return Ok(());
+        };
+
+        let uri = neighbor.neighbor.clone();
+        modal!(
+            context,
+            InputModal::new(context)
+                .title("Mount storage")
+                .confirm_label("Mount")
+                .input_label("Mount point:")
+                .initial_value(default_mount_name(&neighbor.name))
+                .on_confirm(move |context, name| {
+                    Self::mount(context, name.to_owned(), uri.clone());
+                    Ok(())
+                })
+        );
+        Ok(())
+    }
+
+    /// Asks for an arbitrary storage URI, for example `nfs://host/path`, and
+    /// mounts it under a mount point derived from the URI
+    fn add_storage(context: &AppContext) -> Result<()> {
+        modal!(
+            context,
+            InputModal::new(context)
+                .title("Mount storage")
+                .confirm_label("Mount")
+                .input_label("Storage URI:")
+                .on_confirm(move |context, uri| {
+                    Self::mount(context, default_mount_name(uri), uri.to_owned());
+                    Ok(())
+                })
+        );
+        Ok(())
+    }
+
+    fn unmount_selected(&mut self, context: &AppContext) -> Result<()> {
+        let Some(StorageRow::Mount(mount)) = self.selected_row() else {
+            return Ok(());
+        };
+        // The root mount is the music directory itself and cannot be unmounted
+        if mount.mount.is_empty() {
+            return Ok(());
+        }
+
+        let name = mount.mount.clone();
+        modal!(
+            context,
+            ConfirmModal::new(context)
+                .message("Are you sure you want to unmount this storage?")
+                .on_confirm(move |context| {
+                    let name = name.clone();
+                    Self::refresh_storage(context, move |client| {
+                        client.unmount(&name)?;
+                        status_info!("Storage '{name}' unmounted");
+                        Ok(())
+                    });
+                    Ok(())
+                })
+                .confirm_label("Unmount")
+                .size(45, 5)
+        );
+        Ok(())
+    }
+}
+
+/// Turns a neighbor name or storage URI into a single path segment usable as a
+/// mount point, for example `smb://nas/Music Share` becomes `Music_Share`
+fn default_mount_name(value: &str) -> String {
+    let value = value.split_once("://").map_or(value, |(_, rest)| rest);
+    let last_segment = value.trim_end_matches('/').rsplit('/').next().unwrap_or_default();
+
+    let name = last_segment
+        .split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '.'))
+        .filter(|part| !part.is_empty())
+        .join("_");
+
+    if name.is_empty() { "storage".to_owned() } else { name }
+}
+
+impl Modal for StorageModal {
+    fn render(&mut self, frame: &mut ratatui::Frame, app: &mut AppContext) -> anyhow::Result<()> {
+        // Rows, header and borders
+        let height = u16::try_from(self.rows().len()).unwrap_or(u16::MAX).saturating_add(4);
+        let popup_area = frame.area().centered_exact(80, height.max(10));
+        frame.render_widget(Clear, popup_area);
+        if let Some(bg_color) = app.config.theme.modal_background_color {
+            frame.render_widget(Block::default().style(Style::default().bg(bg_color)), popup_area);
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(app.config.as_border_style())
+            .title_alignment(ratatui::prelude::Alignment::Center)
+            .title("Storage");
+
+        let table_area = popup_area.inner(Margin { horizontal: 1, vertical: 1 });
+
+        let rows: Vec<Row<'static>> = self
+            .rows()
+            .into_iter()
+            .map(|row| match row {
+                StorageRow::Mount(mount) => Row::new([
+                    Cell::from("mount"),
+                    Cell::from(if mount.mount.is_empty() {
+                        "/".to_owned()
+                    } else {
+                        mount.mount.clone()
+                    }),
+                    Cell::from(mount.storage.clone()),
+                ]),
+                StorageRow::Neighbor(neighbor) => {
+                    let mounted = self.mounts.iter().any(|m| m.storage == neighbor.neighbor);
+                    Row::new([
+                        Cell::from("neighbor"),
+                        Cell::from(if mounted {
+                            format!("{} (mounted)", neighbor.name)
+                        } else {
+                            neighbor.name.clone()
+                        }),
+                        Cell::from(neighbor.neighbor.clone()),
+                    ])
+                }
+            })
+            .collect();
+
+        self.scrolling_state.set_content_len(Some(rows.len()));
+        self.scrolling_state.set_viewport_len(Some(table_area.height.into()));
+
+        let table = Table::new(rows, [
+            Constraint::Length(10),
+            Constraint::Percentage(40),
+            Constraint::Percentage(60),
+        ])
+        .column_spacing(0)
+        .style(app.config.as_text_style())
+        .header(Row::new(["Type", "Name", "Storage"]))
+        .row_highlight_style(app.config.theme.current_item_style);
+
+        let table_area = table_area.inner(Margin { horizontal: 1, vertical: 0 });
+        self.table_area = table_area;
+
+        frame.render_widget(block, popup_area);
+        frame.render_stateful_widget(table, table_area, self.scrolling_state.as_render_state_ref());
+        frame.render_stateful_widget(
+            app.config.as_styled_scrollbar(),
+            popup_area.inner(Margin { horizontal: 0, vertical: 1 }),
+            self.scrolling_state.as_scrollbar_state_ref(),
+        );
+
+        Ok(())
+    }
+
+    fn on_query_finished(
+        &mut self,
+        id: &'static str,
+        data: &mut MpdQueryResult,
+        context: &AppContext,
+    ) -> Result<()> {
+        match (id, data) {
+            (REFRESH_STORAGE, MpdQueryResult::Storage { mounts, neighbors }) => {
+                self.mounts = std::mem::take(mounts);
+                self.neighbors = std::mem::take(neighbors);
+                self.scrolling_state.set_content_len(Some(self.rows().len()));
+                context.render()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        if let Some(action) = key.as_common_action(context) {
+            match action {
+                CommonAction::DownHalf => {
+                    self.scrolling_state.next_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::UpHalf => {
+                    self.scrolling_state.prev_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::Up => {
+                    self.scrolling_state
+                        .prev(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Down => {
+                    self.scrolling_state
+                        .next(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Bottom => {
+                    self.scrolling_state.last();
+
+                    context.render()?;
+                }
+                CommonAction::Top => {
+                    self.scrolling_state.first();
+
+                    context.render()?;
+                }
+                CommonAction::Confirm => {
+                    self.confirm_selected(context)?;
+                }
+                CommonAction::Add => {
+                    Self::add_storage(context)?;
+                }
+                CommonAction::Delete => {
+                    self.unmount_selected(context)?;
+                }
+                CommonAction::Close => {
+                    pop_modal!(context);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &mut AppContext) -> Result<()> {
+        match event.kind {
+            MouseEventKind::LeftClick if self.table_area.contains(event.into()) => {
+                let y: usize = event.y.saturating_sub(self.table_area.y).into();
+                let y = y.saturating_sub(1); // Subtract one to account for table header
+                if let Some(idx) = self.scrolling_state.get_at_rendered_row(y) {
+                    self.scrolling_state.select(Some(idx), context.config.scrolloff);
+                    context.render()?;
+                }
+            }
+            MouseEventKind::DoubleClick if self.table_area.contains(event.into()) => {
+                self.confirm_selected(context)?;
+            }
+            MouseEventKind::MiddleClick => {}
+            MouseEventKind::RightClick => {}
+            MouseEventKind::ScrollDown if self.table_area.contains(event.into()) => {
+                self.scrolling_state.next(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::ScrollUp if self.table_area.contains(event.into()) => {
+                self.scrolling_state.prev(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::LeftClick => {}
+            MouseEventKind::DoubleClick => {}
+            MouseEventKind::ScrollDown => {}
+            MouseEventKind::ScrollUp => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::default_mount_name;
+
+    #[test_case("smb://nas/Music Share", "Music_Share"; "last uri segment")]
+    #[test_case("nfs://host/export/music/", "music"; "trailing slash")]
+    #[test_case("smb://nas", "nas"; "host only")]
+    #[test_case("nas (SMB)", "nas_SMB"; "neighbor name")]
+    #[test_case("smb://", "storage"; "empty")]
+    fn derives_mount_name(input: &str, expected: &str) {
+        assert_eq!(default_mount_name(input), expected);
+    }
+}
@@ -0,0 +1,546 @@
+use std::path::Path;
+
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use itertools::Itertools;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Margin, Rect},
+    style::{Style, Stylize},
+    symbols::{self, border},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+};
+
+use super::{Modal, RectExt};
+use crate::{
+    config::keys::CommonAction,
+    context::AppContext,
+    mpd::{commands::Song, mpd_client::MpdClient},
+    shared::{
+        events::WorkRequest,
+        key_event::KeyEvent,
+        macros::{pop_modal, status_error},
+        mouse_event::{MouseEvent, MouseEventKind},
+        tag_writer::{EDITABLE_TAGS, FILE_NAME_KEY, is_editable},
+    },
+    ui::{
+        dirstack::DirState,
+        widgets::button::{Button, ButtonGroup, ButtonGroupState},
+    },
+};
+
+const BUTTON_GROUP_SYMBOLS: symbols::border::Set = symbols::border::Set {
+    top_right: symbols::line::NORMAL.vertical_left,
+    top_left: symbols::line::NORMAL.vertical_right,
+    ..symbols::border::ROUNDED
+};
+
+#[derive(Debug)]
+struct TagRow {
+    key: String,
+    /// Value shared by all of the edited songs, `None` if they differ
+    original: Option<String>,
+    value: String,
+    changed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Editing {
+    Key,
+    Value,
+}
+
+/// Edits tags of one or more songs and renames their files. When more than one
+/// song is edited, values can contain placeholders expanded separately for
+/// every song, see [`expand_pattern`](crate::shared::tag_writer::expand_pattern).
+#[derive(Debug)]
+pub struct TagEditorModal<'a> {
+    songs: Vec<Song>,
+    rows: Vec<TagRow>,
+    scrolling_state: DirState<TableState>,
+    table_area: Rect,
+    editing: Option<Editing>,
+    /// Value of the edited row before the editing started
+    backup: String,
+    table_focused: bool,
+    button_group_state: ButtonGroupState,
+    button_group: ButtonGroup<'a>,
+}
+
+impl TagEditorModal<'_> {
+    pub fn new(songs: Vec<Song>, context: &AppContext) -> Self {
+        let mut scrolling_state = DirState::default();
+        scrolling_state.select(Some(0), 0);
+
+        let mut button_group_state = ButtonGroupState::default();
+        let buttons = vec![Button::default().label("Save"), Button::default().label("Cancel")];
+        button_group_state.set_button_count(buttons.len());
+        let button_group = ButtonGroup::default()
+            .buttons(buttons)
+            .inactive_style(context.config.as_text_style())
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_set(BUTTON_GROUP_SYMBOLS)
+                    .border_style(context.config.as_border_style()),
+            );
+
+        Self {
+            rows: Self::rows(&songs),
+            songs,
+            scrolling_state,
+            table_area: Rect::default(),
+            editing: None,
+            backup: String::new(),
+            table_focused: true,
+            button_group_state,
+            button_group,
+        }
+    }
+
+    fn rows(songs: &[Song]) -> Vec<TagRow> {
+        let other_keys = songs
+            .iter()
+            .flat_map(|song| song.metadata.keys())
+            .filter(|key| is_editable(key) && !EDITABLE_TAGS.contains(&key.as_str()))
+            .unique()
+            .sorted()
+            .cloned()
+            .collect_vec();
+
+        std::iter::once(FILE_NAME_KEY)
+            .chain(EDITABLE_TAGS)
+            .map(|key| key.to_owned())
+            .chain(other_keys)
+            .map(|key| {
+                let original = songs
+                    .iter()
+                    .map(|song| {
+                        if key == FILE_NAME_KEY {
+                            Path::new(&song.file)
+                                .file_stem()
+                                .and_then(|stem| stem.to_str())
+                                .unwrap_or_default()
+                        } else {
+                            song.metadata.get(&key).map_or("", String::as_str)
+                        }
+                    })
+                    .all_equal_value()
+                    .ok()
+                    .map(str::to_owned);
+                TagRow {
+                    value: original.clone().unwrap_or_default(),
+                    key,
+                    original,
+                    changed: false,
+                }
+            })
+            .collect_vec()
+    }
+
+    fn selected_row(&mut self) -> Option<&mut TagRow> {
+        self.scrolling_state.get_selected().and_then(|idx| self.rows.get_mut(idx))
+    }
+
+    fn start_editing(&mut self, context: &AppContext) -> Result<()> {
+        if let Some(row) = self.selected_row() {
+            let value = row.value.clone();
+            self.backup = value;
+            self.editing = Some(Editing::Value);
+            context.render()?;
+        }
+        Ok(())
+    }
+
+    fn add_row(&mut self, context: &AppContext) -> Result<()> {
+        self.rows.push(TagRow {
+            key: String::new(),
+            original: None,
+            value: String::new(),
+            changed: true,
+        });
+        self.scrolling_state.set_content_len(Some(self.rows.len()));
+        self.scrolling_state.last();
+        self.editing = Some(Editing::Key);
+        context.render()?;
+        Ok(())
+    }
+
+    fn remove_selected_row(&mut self) {
+        if let Some(idx) = self.scrolling_state.get_selected() {
+            self.rows.remove(idx);
+            self.scrolling_state.set_content_len(Some(self.rows.len()));
+            self.scrolling_state.select(Some(idx.saturating_sub(1)), 0);
+        }
+    }
+
+    fn handle_editing_key(
+        &mut self,
+        editing: Editing,
+        key: &mut KeyEvent,
+        context: &AppContext,
+    ) -> Result<()> {
+        let action = key.as_common_action(context);
+        match (action, editing) {
+            (Some(CommonAction::Close), Editing::Key) => {
+                self.remove_selected_row();
+                self.editing = None;
+            }
+            (Some(CommonAction::Close), Editing::Value) => {
+                let backup = std::mem::take(&mut self.backup);
+                if let Some(row) = self.selected_row() {
+                    row.value = backup;
+                }
+                self.editing = None;
+            }
+            (Some(CommonAction::Confirm), Editing::Key) => {
+                let is_valid = self.selected_row().is_some_and(|row| {
+                    row.key = row.key.trim().to_lowercase();
+                    !row.key.is_empty() && is_editable(&row.key)
+                });
+                let idx = self.scrolling_state.get_selected();
+                let is_duplicate = self
+                    .rows
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| Some(*i) != idx)
+                    .any(|(_, row)| idx.is_some_and(|idx| self.rows[idx].key == row.key));
+                if is_valid && !is_duplicate {
+                    self.editing = Some(Editing::Value);
+                } else {
+                    self.remove_selected_row();
+                    self.editing = None;
+                }
+            }
+            (Some(CommonAction::Confirm), Editing::Value) => {
+                let backup = std::mem::take(&mut self.backup);
+                if let Some(row) = self.selected_row() {
+                    row.changed |= row.value != backup;
+                }
+                self.editing = None;
+            }
+            _ => {
+                let Some(row) = self.selected_row() else {
+                    return Ok(());
+                };
+                let text = match editing {
+                    Editing::Key => &mut row.key,
+                    Editing::Value => &mut row.value,
+                };
+                match key.code() {
+                    KeyCode::Char(c) => text.push(c),
+                    KeyCode::Backspace => {
+                        text.pop();
+                    }
+                    _ => return Ok(()),
+                }
+            }
+        }
+
+        context.render()?;
+        Ok(())
+    }
+
+    /// Requests the changed tags to be written to all of the songs
+    fn save(&mut self, context: &AppContext) {
+        let tags = self
+            .rows
+            .iter()
+            .filter(|row| row.changed)
+            .map(|row| (row.key.clone(), row.value.clone()))
+            .collect_vec();
+        if tags.is_empty() {
+            return;
+        }
+
+        let songs = std::mem::take(&mut self.songs);
+        let command = context.config.tag_editor;
+        let work_sender = context.work_sender.clone();
+        context.command(move |client| {
+            let Some(music_directory) = client.config().map(|c| c.music_directory.clone()) else {
+                status_error!("Editing tags requires socket connection to MPD");
+                return Ok(());
+            };
+
+            // the tags are written by the worker to not block other requests
+            // to MPD while the external programs run
+            work_sender.send(WorkRequest::WriteTags { command, music_directory, songs, tags })?;
+            Ok(())
+        });
+    }
+
+    fn confirm_button(&mut self, context: &AppContext) -> Result<()> {
+        if self.button_group_state.selected == 0 {
+            self.save(context);
+        }
+        pop_modal!(context);
+        Ok(())
+    }
+}
+
+impl Modal for TagEditorModal<'_> {
+    fn render(&mut self, frame: &mut Frame, app: &mut AppContext) -> Result<()> {
+        let popup_area = frame.area().centered(80, 80);
+        frame.render_widget(Clear, popup_area);
+        if let Some(bg_color) = app.config.theme.modal_background_color {
+            frame.render_widget(Block::default().style(Style::default().bg(bg_color)), popup_area);
+        }
+
+        let title = if self.songs.len() > 1 {
+            format!("Edit tags of {} songs", self.songs.len())
+        } else {
+            "Edit tags".to_owned()
+        };
+        let block = Block::default()
+            .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+            .border_set(border::ROUNDED)
+            .border_style(app.config.as_border_style())
+            .title_alignment(ratatui::prelude::Alignment::Center)
+            .title(title);
+
+        let [body_area, buttons_area] =
+            Layout::vertical([Constraint::Percentage(100), Constraint::Length(3)])
+                .areas(popup_area);
+        let margin = Margin { horizontal: 1, vertical: 0 };
+        let [files_area, header_area, table_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Percentage(100),
+        ])
+        .areas(block.inner(body_area));
+        let files_area = files_area.inner(margin);
+        let header_area = header_area.inner(margin);
+        let table_area = table_area.inner(margin);
+        let files = Line::from(format!(
+            "Editing: {}",
+            self.songs.iter().map(|song| song.file.as_str()).join(", ")
+        ));
+
+        let widths = [Constraint::Percentage(30), Constraint::Percentage(70)];
+        let editing_style = app.config.theme.highlight_border_style;
+        let selected = self.scrolling_state.get_selected();
+        let rows = self.rows.iter().enumerate().map(|(idx, row)| {
+            let editing = self.editing.filter(|_| selected == Some(idx));
+            let key = Cell::from(row.key.as_str());
+            let value = if row.original.is_none() && !row.changed && editing.is_none() {
+                Cell::from(Line::from("<multiple values>").dim())
+            } else {
+                Cell::from(row.value.as_str())
+            };
+            let changed_marker =
+                if row.changed { Style::default().bold() } else { Style::default() };
+            match editing {
+                Some(Editing::Key) => Row::new([key.style(editing_style), value]),
+                Some(Editing::Value) => Row::new([key, value.style(editing_style)]),
+                None => Row::new([key, value]).style(changed_marker),
+            }
+        });
+
+        self.scrolling_state.set_content_len(Some(self.rows.len()));
+        self.scrolling_state.set_viewport_len(Some(table_area.height.into()));
+
+        let header_table =
+            Table::new(vec![Row::new([Cell::from("Tag"), Cell::from("Value")])], widths)
+                .column_spacing(1)
+                .block(
+                    Block::default()
+                        .borders(Borders::BOTTOM)
+                        .border_style(app.config.as_border_style()),
+                );
+        let table = Table::new(rows, widths)
+            .column_spacing(1)
+            .style(app.config.as_text_style())
+            .row_highlight_style(if self.table_focused {
+                app.config.theme.current_item_style
+            } else {
+                Style::default()
+            });
+
+        self.table_area = table_area;
+        self.button_group.set_active_style(if self.table_focused {
+            Style::default()
+        } else {
+            app.config.theme.current_item_style
+        });
+
+        frame.render_widget(block, body_area);
+        frame.render_widget(files.style(app.config.as_text_style()), files_area);
+        frame.render_widget(header_table, header_area);
+        frame.render_stateful_widget(table, table_area, self.scrolling_state.as_render_state_ref());
+        frame.render_stateful_widget(
+            &mut self.button_group,
+            buttons_area,
+            &mut self.button_group_state,
+        );
+
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        if let Some(editing) = self.editing {
+            return self.handle_editing_key(editing, key, context);
+        }
+
+        let Some(action) = key.as_common_action(context) else {
+            return Ok(());
+        };
+
+        if !self.table_focused {
+            match action {
+                CommonAction::Down | CommonAction::Up => self.button_group_state.next(),
+                CommonAction::Close => {
+                    pop_modal!(context);
+                    return Ok(());
+                }
+                CommonAction::Confirm => return self.confirm_button(context),
+                CommonAction::FocusInput => self.table_focused = true,
+                _ => return Ok(()),
+            }
+            context.render()?;
+            return Ok(());
+        }
+
+        let config = context.config;
+        match action {
+            CommonAction::Down => {
+                self.scrolling_state.next(config.scrolloff, config.wrap_navigation);
+            }
+            CommonAction::Up => self.scrolling_state.prev(config.scrolloff, config.wrap_navigation),
+            CommonAction::DownHalf => self.scrolling_state.next_half_viewport(config.scrolloff),
+            CommonAction::UpHalf => self.scrolling_state.prev_half_viewport(config.scrolloff),
+            CommonAction::Bottom => self.scrolling_state.last(),
+            CommonAction::Top => self.scrolling_state.first(),
+            CommonAction::Confirm => return self.start_editing(context),
+            CommonAction::Add => return self.add_row(context),
+            CommonAction::Delete => {
+                if let Some(row) = self.selected_row() {
+                    row.value.clear();
+                    row.changed = true;
+                }
+            }
+            CommonAction::Close => self.table_focused = false,
+            _ => return Ok(()),
+        }
+
+        context.render()?;
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &mut AppContext) -> Result<()> {
+        if self.editing.is_some() {
+            return Ok(());
+        }
+
+        if let Some(idx) = self.button_group.get_button_idx_at(event.into()) {
+            match event.kind {
+                MouseEventKind::LeftClick => {
+                    self.button_group_state.select(idx);
+                    self.table_focused = false;
+                    context.render()?;
+                }
+                MouseEventKind::DoubleClick => {
+                    self.button_group_state.select(idx);
+                    return self.confirm_button(context);
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        if !self.table_area.contains(event.into()) {
+            return Ok(());
+        }
+
+        match event.kind {
+            MouseEventKind::LeftClick | MouseEventKind::DoubleClick => {
+                let y: usize = event.y.saturating_sub(self.table_area.y).into();
+                if let Some(idx) = self.scrolling_state.get_at_rendered_row(y) {
+                    self.table_focused = true;
+                    self.scrolling_state.select(Some(idx), context.config.scrolloff);
+                    if matches!(event.kind, MouseEventKind::DoubleClick) {
+                        return self.start_editing(context);
+                    }
+                    context.render()?;
+                }
+            }
+            MouseEventKind::ScrollDown => {
+                self.scrolling_state.next(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::ScrollUp => {
+                self.scrolling_state.prev(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::MiddleClick | MouseEventKind::RightClick => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crossbeam::channel::{Receiver, Sender};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use rstest::rstest;
+
+    use super::TagEditorModal;
+    use crate::{
+        mpd::commands::Song,
+        shared::events::{ClientRequest, WorkRequest},
+        tests::fixtures::{app_context, client_request_channel, work_request_channel},
+        ui::modals::Modal,
+    };
+
+    fn song(tags: &[(&str, &str)]) -> Song {
+        Song {
+            metadata: tags.iter().map(|(k, v)| ((*k).to_owned(), (*v).to_owned())).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rows_contain_values_shared_by_all_songs() {
+        let songs = [
+            song(&[("title", "a"), ("album", "album"), ("comment", "x"), ("duration", "1")]),
+            song(&[("title", "b"), ("album", "album")]),
+        ];
+
+        let rows = TagEditorModal::rows(&songs);
+        let rows: HashMap<_, _> =
+            rows.iter().map(|row| (row.key.as_str(), row.original.as_deref())).collect();
+
+        assert_eq!(rows.len(), 8);
+        assert_eq!(rows["filename"], Some(""));
+        assert_eq!(rows["title"], None);
+        assert_eq!(rows["album"], Some("album"));
+        assert_eq!(rows["genre"], Some(""));
+        assert_eq!(rows["comment"], None);
+        assert!(!rows.contains_key("duration"));
+    }
+
+    #[rstest]
+    fn confirming_unchanged_multiple_values_keeps_them(
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+    ) {
+        let mut context = app_context(work_request_channel, client_request_channel);
+        let songs = vec![song(&[("title", "a")]), song(&[("title", "b")])];
+        let mut modal = TagEditorModal::new(songs, &context);
+        modal.scrolling_state.set_content_len(Some(modal.rows.len()));
+        let enter = || KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE).into();
+
+        // Title is the second row after the file name, enter starts and then
+        // finishes editing it
+        modal.scrolling_state.select(Some(1), 0);
+        modal.handle_key(&mut enter(), &mut context).unwrap();
+        modal.handle_key(&mut enter(), &mut context).unwrap();
+
+        assert_eq!(modal.rows[1].key, "title");
+        assert!(!modal.rows[1].changed);
+    }
+}
@@ -6,8 +6,7 @@ use crate::{
     MpdQueryResult,
     config::tabs::PaneType,
     context::AppContext,
-    mpd::mpd_client::MpdClient,
-    shared::{image::ImageProtocol, key_event::KeyEvent},
+    shared::{album_art::find_album_art, image::ImageProtocol, key_event::KeyEvent},
     ui::{UiEvent, image::facade::AlbumArtFacade},
 };
 
@@ -44,11 +43,15 @@ impl AlbumArtPane {
             return None;
         }
 
-        let song_uri = song_uri.to_owned();
+        let song = current_song.clone();
+        let config = &context.config.album_art;
+        let cache_dir = context.config.cache_dir;
+        let mpd_supported = context.supported_commands.contains("albumart")
+            && context.supported_commands.contains("readpicture");
         context.query().id(ALBUM_ART).replace_id(ALBUM_ART).target(PaneType::AlbumArt).query(move |client| {
             let start = std::time::Instant::now();
-            log::debug!(file = song_uri.as_str(); "Searching for album art");
-            let result = client.find_album_art(&song_uri)?;
+            log::debug!(file = song.file.as_str(); "Searching for album art");
+            let result = find_album_art(client, &song, config, cache_dir, mpd_supported)?;
             log::debug!(elapsed:? = start.elapsed(), size = result.as_ref().map(|v|v.len()); "Found album art");
 
             Ok(MpdQueryResult::AlbumArt(result))
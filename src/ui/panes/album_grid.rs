@@ -0,0 +1,403 @@
+use anyhow::{Context, Result};
+use ratatui::{Frame, layout::Rect, text::Text};
+
+use super::{CommonAction, Pane};
+use crate::{
+    MpdQueryResult,
+    config::tabs::PaneType,
+    context::AppContext,
+    mpd::mpd_client::{Filter, MpdClient, Tag},
+    shared::{
+        ext::mpd_client::MpdClientExt,
+        key_event::KeyEvent,
+        macros::status_info,
+        mouse_event::{MouseEvent, MouseEventKind},
+    },
+    ui::{
+        UiEvent,
+        image::{
+            album_cover::{ALBUM_COVER, AlbumCover},
+            facade::AlbumArtFacade,
+            worker::ImageWorker,
+        },
+    },
+};
+
+/// Width of a single cell of the grid in columns
+const CELL_WIDTH: u16 = 20;
+/// Height of a single cell of the grid in rows, including the album name
+const CELL_HEIGHT: u16 = 11;
+
+const INIT: &str = "init";
+
+/// Browses albums as a grid of their covers
+#[derive(Debug)]
+pub struct AlbumGridPane {
+    albums: Vec<GridAlbum>,
+    selected: usize,
+    /// First visible row of the grid
+    offset: usize,
+    /// Covers of the visible cells, empty if the image method cannot display
+    /// more than one image at once
+    covers: Vec<AlbumCover>,
+    /// Encodes and displays the covers of all cells, [`None`] if covers are
+    /// not shown
+    worker: Option<ImageWorker>,
+    initialized: bool,
+    area: Rect,
+}
+
+/// Albums of different artists can share the same name, so they are told
+/// apart by their album artist
+#[derive(Debug, Clone)]
+struct GridAlbum {
+    name: String,
+    album_artist: String,
+}
+
+impl GridAlbum {
+    fn filter(&self) -> [Filter<'_>; 2] {
+        [Filter::new(Tag::Album, &self.name), Filter::new(Tag::AlbumArtist, &self.album_artist)]
+    }
+
+    /// Identifies the album of a cover
+    fn cover_key(&self) -> String {
+        format!("{}\0{}", self.album_artist, self.name)
+    }
+}
+
+impl AlbumGridPane {
+    pub fn new(context: &AppContext) -> Self {
+        Self {
+            albums: Vec::new(),
+            selected: 0,
+            offset: 0,
+            covers: Vec::new(),
+            worker: AlbumArtFacade::supports_multiple_images(context.config).then(ImageWorker::new),
+            initialized: false,
+            area: Rect::default(),
+        }
+    }
+
+    fn columns(&self) -> usize {
+        usize::from((self.area.width / CELL_WIDTH).max(1))
+    }
+
+    fn visible_rows(&self) -> usize {
+        usize::from((self.area.height / CELL_HEIGHT).max(1))
+    }
+
+    /// Area of the visible cell at the given position
+    // columns and rows are bounded by the size of the area
+    #[allow(clippy::cast_possible_truncation)]
+    fn cell_area(&self, row: usize, col: usize) -> Rect {
+        let width = self.area.width / self.columns() as u16;
+        Rect {
+            x: self.area.x + col as u16 * width,
+            y: self.area.y + row as u16 * CELL_HEIGHT,
+            width,
+            height: CELL_HEIGHT.min(self.area.height),
+        }
+    }
+
+    /// Index of the album at the given position on the screen
+    #[allow(clippy::cast_possible_truncation)]
+    fn album_at(&self, x: u16, y: u16) -> Option<usize> {
+        let col = usize::from((x - self.area.x) / (self.area.width / self.columns() as u16));
+        let row = usize::from((y - self.area.y) / CELL_HEIGHT);
+        if col >= self.columns() || row >= self.visible_rows() {
+            return None;
+        }
+
+        let idx = (self.offset + row) * self.columns() + col;
+        (idx < self.albums.len()).then_some(idx)
+    }
+
+    fn select(&mut self, idx: usize, context: &AppContext) -> Result<()> {
+        self.selected = idx.min(self.albums.len().saturating_sub(1));
+
+        let row = self.selected / self.columns();
+        if row < self.offset {
+            self.offset = row;
+        } else if row >= self.offset + self.visible_rows() {
+            self.offset = row + 1 - self.visible_rows();
+        }
+
+        self.request_covers(context)?;
+        context.render()?;
+        Ok(())
+    }
+
+    /// Requests the covers of the visible albums. Covers of cells without an
+    /// album are cleared.
+    fn request_covers(&mut self, context: &AppContext) -> Result<()> {
+        let Some(worker) = &self.worker else {
+            return Ok(());
+        };
+        let visible_cells = self.columns() * self.visible_rows();
+        if self.covers.len() < visible_cells {
+            self.covers
+                .resize_with(visible_cells, || AlbumCover::with_worker(context, worker.clone()));
+        }
+
+        let first = self.offset * self.columns();
+        for (cell, cover) in self.covers.iter_mut().enumerate() {
+            let album = self.albums.get(first + cell).filter(|_| cell < visible_cells).cloned();
+            let key = album.as_ref().map(GridAlbum::cover_key);
+            cover.request(key.as_deref(), PaneType::AlbumGrid, context, move |client| {
+                let Some(album) = album else {
+                    return Ok(None);
+                };
+                Ok(client.find(&album.filter())?.into_iter().next())
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn add_selected(&self, autoplay: bool, context: &AppContext) {
+        let Some(album) = self.albums.get(self.selected).cloned() else {
+            return;
+        };
+        let queue_len = context.queue.len();
+        context.command(move |client| {
+            client.find_add(&album.filter())?;
+            if autoplay {
+                client.play_last(queue_len)?;
+            } else {
+                status_info!("Album '{}' added to queue", album.name);
+            }
+            Ok(())
+        });
+    }
+
+    fn hide_covers(&mut self) -> Result<()> {
+        self.covers.iter_mut().try_for_each(AlbumCover::hide)
+    }
+
+    fn show_covers(&mut self) {
+        self.covers.iter_mut().for_each(AlbumCover::show);
+    }
+
+    fn init(context: &AppContext) {
+        context.query().id(INIT).replace_id(INIT).target(PaneType::AlbumGrid).query(|client| {
+            let mut albums = client
+                .list_tag_grouped(Tag::Album, Tag::AlbumArtist)
+                .context("Cannot list tags")?
+                .items
+                .into_iter()
+                .map(|(album_artist, name)| GridAlbum { name, album_artist })
+                .collect::<Vec<_>>();
+            albums.sort_by(|a, b| (&a.name, &a.album_artist).cmp(&(&b.name, &b.album_artist)));
+            Ok(MpdQueryResult::Any(Box::new(albums)))
+        });
+    }
+}
+
+impl Pane for AlbumGridPane {
+    fn render(&mut self, frame: &mut Frame, _area: Rect, context: &AppContext) -> Result<()> {
+        let visible_cells = self.columns() * self.visible_rows();
+        for cell in 0..self.covers.len().max(visible_cells) {
+            let (row, col) = (cell / self.columns(), cell % self.columns());
+            let idx = (self.offset + row) * self.columns() + col;
+            let album = self.albums.get(idx).filter(|_| cell < visible_cells);
+            let cell_area = self.cell_area(row, col);
+
+            if let Some(cover) = self.covers.get_mut(cell) {
+                let image_area = Rect {
+                    x: cell_area.x + 1,
+                    width: cell_area.width.saturating_sub(2),
+                    height: cell_area.height.saturating_sub(1),
+                    ..cell_area
+                };
+                cover.render(image_area, context)?;
+            }
+
+            let Some(album) = album else {
+                continue;
+            };
+            let style = if idx == self.selected {
+                context.config.theme.current_item_style
+            } else {
+                context.config.as_text_style()
+            };
+            let name_area = Rect {
+                y: cell_area.bottom().saturating_sub(1),
+                height: 1.min(cell_area.height),
+                ..cell_area
+            };
+            frame.render_widget(Text::from(album.name.as_str()).centered().style(style), name_area);
+        }
+
+        Ok(())
+    }
+
+    fn calculate_areas(&mut self, area: Rect, context: &AppContext) -> Result<()> {
+        self.area = area;
+        self.select(self.selected, context)
+    }
+
+    fn before_show(&mut self, context: &AppContext) -> Result<()> {
+        if !self.initialized {
+            Self::init(context);
+            self.initialized = true;
+        }
+        self.show_covers();
+
+        Ok(())
+    }
+
+    fn on_hide(&mut self, _context: &AppContext) -> Result<()> {
+        self.hide_covers()
+    }
+
+    fn on_event(
+        &mut self,
+        event: &mut UiEvent,
+        is_visible: bool,
+        context: &AppContext,
+    ) -> Result<()> {
+        match event {
+            UiEvent::Database => Self::init(context),
+            UiEvent::Reconnected => {
+                self.initialized = false;
+                self.before_show(context)?;
+            }
+            UiEvent::ModalOpened if is_visible => self.hide_covers()?,
+            UiEvent::ModalClosed if is_visible => {
+                self.show_covers();
+                context.render()?;
+            }
+            UiEvent::Exit => {
+                self.covers.iter_mut().try_for_each(AlbumCover::cleanup)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn on_query_finished(
+        &mut self,
+        id: &'static str,
+        data: MpdQueryResult,
+        _is_visible: bool,
+        context: &AppContext,
+    ) -> Result<()> {
+        match (id, data) {
+            (INIT, MpdQueryResult::Any(data)) => {
+                if let Ok(albums) = data.downcast::<Vec<GridAlbum>>() {
+                    self.albums = *albums;
+                    self.select(self.selected, context)?;
+                }
+            }
+            (ALBUM_COVER, MpdQueryResult::AlbumCover { album, data }) => {
+                if let Some(cover) =
+                    self.covers.iter_mut().find(|cover| cover.album() == Some(album.as_str()))
+                {
+                    cover.receive(&album, data, context)?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        let Some(action) = event.as_common_action(context) else {
+            return Ok(());
+        };
+
+        let columns = self.columns();
+        let page = columns * self.visible_rows();
+        match action {
+            CommonAction::Up => self.select(self.selected.saturating_sub(columns), context)?,
+            CommonAction::Down => self.select(self.selected + columns, context)?,
+            CommonAction::Left => self.select(self.selected.saturating_sub(1), context)?,
+            CommonAction::Right => self.select(self.selected + 1, context)?,
+            CommonAction::UpHalf | CommonAction::PageUp => {
+                self.select(self.selected.saturating_sub(page), context)?;
+            }
+            CommonAction::DownHalf | CommonAction::PageDown => {
+                self.select(self.selected + page, context)?;
+            }
+            CommonAction::Top => self.select(0, context)?,
+            CommonAction::Bottom => self.select(self.albums.len(), context)?,
+            CommonAction::Confirm => self.add_selected(true, context),
+            CommonAction::Add => self.add_selected(false, context),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &AppContext) -> Result<()> {
+        if !self.area.contains(event.into()) {
+            return Ok(());
+        }
+
+        let columns = self.columns();
+        match event.kind {
+            MouseEventKind::LeftClick => {
+                if let Some(idx) = self.album_at(event.x, event.y) {
+                    self.select(idx, context)?;
+                }
+            }
+            MouseEventKind::DoubleClick => {
+                if let Some(idx) = self.album_at(event.x, event.y) {
+                    self.select(idx, context)?;
+                    self.add_selected(true, context);
+                }
+            }
+            MouseEventKind::ScrollUp => {
+                self.select(self.selected.saturating_sub(columns), context)?;
+            }
+            MouseEventKind::ScrollDown => self.select(self.selected + columns, context)?,
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crossbeam::channel::{Receiver, Sender};
+    use ratatui::layout::Rect;
+    use rstest::rstest;
+
+    use super::{AlbumGridPane, GridAlbum};
+    use crate::{
+        shared::events::{ClientRequest, WorkRequest},
+        tests::fixtures::{app_context, client_request_channel, work_request_channel},
+    };
+
+    #[rstest]
+    fn keeps_selected_album_visible(
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+    ) {
+        let context = app_context(work_request_channel, client_request_channel);
+        let mut pane = AlbumGridPane::new(&context);
+        pane.albums = (0..50)
+            .map(|i| GridAlbum { name: format!("album {i}"), album_artist: String::new() })
+            .collect();
+        // 3 columns and 2 rows
+        pane.area = Rect::new(0, 0, 60, 22);
+
+        pane.select(14, &context).unwrap();
+        assert_eq!(pane.offset, 3);
+        assert_eq!(pane.album_at(25, 12), Some(13));
+
+        pane.select(100, &context).unwrap();
+        assert_eq!(pane.selected, 49);
+        assert_eq!(pane.offset, 15);
+
+        pane.select(2, &context).unwrap();
+        assert_eq!(pane.offset, 0);
+        assert_eq!(pane.album_at(59, 0), Some(2));
+        assert_eq!(pane.album_at(0, 21), Some(3));
+    }
+}
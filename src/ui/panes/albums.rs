@@ -24,6 +24,7 @@ use crate::{
         UiEvent,
         browser::BrowserPane,
         dirstack::{DirStack, DirStackItem},
+        image::album_cover::{ALBUM_COVER, AlbumCover},
         widgets::browser::Browser,
     },
 };
@@ -34,9 +35,11 @@ pub struct AlbumsPane {
     filter_input_mode: bool,
     browser: Browser<DirOrSong>,
     initialized: bool,
+    cover: Option<AlbumCover>,
 }
 
 const INIT: &str = "init";
+const REINIT: &str = "reinit";
 const OPEN_OR_PLAY: &str = "open_or_play";
 const PREVIEW: &str = "preview";
 
@@ -47,6 +50,46 @@ impl AlbumsPane {
             filter_input_mode: false,
             browser: Browser::new(context.config),
             initialized: false,
+            cover: AlbumCover::is_preview_enabled(context.config)
+                .then(|| AlbumCover::preview(context)),
+        }
+    }
+
+    fn request_cover(&mut self, context: &AppContext) -> Result<()> {
+        let Some(cover) = &mut self.cover else {
+            return Ok(());
+        };
+        let Some(current) = self.stack.current().selected() else {
+            return cover.clear();
+        };
+
+        match (self.stack.path(), current) {
+            ([album], DirOrSong::Song(song)) => {
+                let song = song.clone();
+                cover.request(Some(album), PaneType::Albums, context, move |_| Ok(Some(song)))
+            }
+            ([], current) => cover.request(Some(current.as_path()), PaneType::Albums, context, {
+                let album = current.as_path().to_owned();
+                move |client| {
+                    // albums of different artists can share the same name, the
+                    // cover of the first album artist is shown like in the
+                    // album grid
+                    let album_artist = client
+                        .list_tag(Tag::AlbumArtist, Some(&[Filter::new(Tag::Album, &album)]))?
+                        .0
+                        .into_iter()
+                        .next()
+                        .unwrap_or_default();
+                    Ok(client
+                        .find(&[
+                            Filter::new(Tag::Album, &album),
+                            Filter::new(Tag::AlbumArtist, &album_artist),
+                        ])?
+                        .into_iter()
+                        .next())
+                }
+            }),
+            _ => cover.clear(),
         }
     }
 
@@ -94,12 +137,15 @@ impl AlbumsPane {
 }
 
 impl Pane for AlbumsPane {
-    fn render(&mut self, frame: &mut Frame, area: Rect, _context: &AppContext) -> Result<()> {
-        self.browser.set_filter_input_active(self.filter_input_mode).render(
-            area,
-            frame.buffer_mut(),
-            &mut self.stack,
-        );
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
+        self.browser
+            .set_filter_input_active(self.filter_input_mode)
+            .set_show_preview_image(self.cover.as_ref().is_some_and(AlbumCover::has_image))
+            .render(area, frame.buffer_mut(), &mut self.stack);
+
+        if let Some(cover) = &mut self.cover {
+            cover.render(self.browser.preview_image_area, context)?;
+        }
 
         Ok(())
     }
@@ -114,23 +160,43 @@ impl Pane for AlbumsPane {
             );
             self.initialized = true;
         }
+        if let Some(cover) = &mut self.cover {
+            cover.show();
+        }
+
+        Ok(())
+    }
 
+    fn on_hide(&mut self, _context: &AppContext) -> Result<()> {
+        if let Some(cover) = &mut self.cover {
+            cover.hide()?;
+        }
         Ok(())
     }
 
     fn on_event(
         &mut self,
         event: &mut UiEvent,
-        _is_visible: bool,
+        is_visible: bool,
         context: &AppContext,
     ) -> Result<()> {
         match event {
             UiEvent::Database => {
-                context.query().id(INIT).replace_id(INIT).target(PaneType::Albums).query(
+                let path = self.stack.path().to_vec();
+                context.query().id(REINIT).replace_id(REINIT).target(PaneType::Albums).query(
                     move |client| {
-                        let result =
-                            client.list_tag(Tag::Album, None).context("Cannot list tags")?;
-                        Ok(MpdQueryResult::LsInfo { data: result.0, origin_path: None })
+                        let albums = client
+                            .list_tag(Tag::Album, None)
+                            .context("Cannot list tags")?
+                            .0
+                            .into_iter()
+                            .map(|v| DirOrSong::Dir { full_path: String::new(), name: v })
+                            .collect_vec();
+                        let mut levels = vec![albums];
+                        if let [album] = path.as_slice() {
+                            levels.push(list_titles(client, album)?.collect());
+                        }
+                        Ok(MpdQueryResult::Any(Box::new((path, levels))))
                     },
                 );
             }
@@ -138,6 +204,22 @@ impl Pane for AlbumsPane {
                 self.initialized = false;
                 self.before_show(context)?;
             }
+            UiEvent::ModalOpened if is_visible => {
+                if let Some(cover) = &mut self.cover {
+                    cover.hide()?;
+                }
+            }
+            UiEvent::ModalClosed if is_visible => {
+                if let Some(cover) = &mut self.cover {
+                    cover.show();
+                    context.render()?;
+                }
+            }
+            UiEvent::Exit => {
+                if let Some(cover) = &mut self.cover {
+                    cover.cleanup()?;
+                }
+            }
             _ => {}
         }
         Ok(())
@@ -172,6 +254,11 @@ impl Pane for AlbumsPane {
                 self.stack_mut().set_preview(data);
                 context.render()?;
             }
+            (ALBUM_COVER, MpdQueryResult::AlbumCover { album, data }) => {
+                if let Some(cover) = &mut self.cover {
+                    cover.receive(&album, data, context)?;
+                }
+            }
             (INIT, MpdQueryResult::LsInfo { data, origin_path: _ }) => {
                 self.stack = DirStack::new(
                     data.into_iter()
@@ -180,6 +267,15 @@ impl Pane for AlbumsPane {
                 );
                 self.prepare_preview(context)?;
             }
+            (REINIT, MpdQueryResult::Any(data)) => {
+                let Ok(data) = data.downcast::<(Vec<String>, Vec<Vec<DirOrSong>>)>() else {
+                    return Ok(());
+                };
+                let (origin_path, levels) = *data;
+                self.stack.replace_levels(&origin_path, levels, context.config.scrolloff);
+                self.prepare_preview(context)?;
+                context.render()?;
+            }
             (OPEN_OR_PLAY, MpdQueryResult::DirOrSong { data, origin_path }) => {
                 if let Some(origin_path) = origin_path {
                     if origin_path != self.stack().path() {
@@ -308,6 +404,7 @@ impl BrowserPane<DirOrSong> for AlbumsPane {
         let config = context.config;
         let origin_path = Some(self.stack().path().to_vec());
 
+        self.request_cover(context)?;
         self.stack_mut().clear_preview();
         match self.stack.path() {
             [album] => {
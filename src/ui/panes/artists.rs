@@ -28,6 +28,7 @@ use crate::{
         UiEvent,
         browser::BrowserPane,
         dirstack::{DirStack, DirStackItem},
+        image::album_cover::{ALBUM_COVER, AlbumCover},
         widgets::browser::Browser,
     },
 };
@@ -45,9 +46,11 @@ pub struct ArtistsPane {
     browser: Browser<DirOrSong>,
     initialized: bool,
     cache: ArtistsCache,
+    cover: Option<AlbumCover>,
 }
 
 const INIT: &str = "init";
+const REINIT: &str = "reinit";
 const OPEN_OR_PLAY: &str = "open_or_play";
 const PREVIEW: &str = "preview";
 
@@ -73,9 +76,51 @@ impl ArtistsPane {
             browser: Browser::new(context.config),
             initialized: false,
             cache: ArtistsCache::default(),
+            cover: AlbumCover::is_preview_enabled(context.config)
+                .then(|| AlbumCover::preview(context)),
         }
     }
 
+    fn request_cover(&mut self, context: &AppContext) -> Result<()> {
+        let target = self.target_pane();
+        let Some(cover) = &mut self.cover else {
+            return Ok(());
+        };
+        let Some(current) = self.stack.current().selected().map(DirStackItem::as_path) else {
+            return cover.clear();
+        };
+
+        let find_album = |artist: &String, name: &str| {
+            self.cache
+                .0
+                .get(artist)
+                .and_then(|albums| albums.0.iter().find(|album| album.name == name))
+        };
+        let (artist, album, song) = match self.stack.path() {
+            [artist, album] => {
+                let song = find_album(artist, album)
+                    .and_then(|album| album.songs.iter().find(|song| song.file == current))
+                    .cloned();
+                (artist, album.as_str(), song)
+            }
+            [artist] => {
+                let song =
+                    find_album(artist, current).and_then(|album| album.songs.first()).cloned();
+                (artist, current, song)
+            }
+            _ => return cover.clear(),
+        };
+        // The songs of the artist are not cached yet, the cover is requested
+        // again once they are
+        let Some(song) = song else {
+            return cover.clear();
+        };
+
+        // albums of different artists can share the same name
+        let key = format!("{artist}\0{album}");
+        cover.request(Some(&key), target, context, move |_| Ok(Some(song)))
+    }
+
     fn artist_tag(&self) -> Tag {
         match self.mode {
             ArtistsPaneMode::AlbumArtist => Tag::AlbumArtist,
@@ -219,12 +264,15 @@ impl ArtistsPane {
 }
 
 impl Pane for ArtistsPane {
-    fn render(&mut self, frame: &mut Frame, area: Rect, _context: &AppContext) -> Result<()> {
-        self.browser.set_filter_input_active(self.filter_input_mode).render(
-            area,
-            frame.buffer_mut(),
-            &mut self.stack,
-        );
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
+        self.browser
+            .set_filter_input_active(self.filter_input_mode)
+            .set_show_preview_image(self.cover.as_ref().is_some_and(AlbumCover::has_image))
+            .render(area, frame.buffer_mut(), &mut self.stack);
+
+        if let Some(cover) = &mut self.cover {
+            cover.render(self.browser.preview_image_area, context)?;
+        }
 
         Ok(())
     }
@@ -240,31 +288,62 @@ impl Pane for ArtistsPane {
 
             self.initialized = true;
         }
+        if let Some(cover) = &mut self.cover {
+            cover.show();
+        }
 
         Ok(())
     }
 
+    fn on_hide(&mut self, _context: &AppContext) -> Result<()> {
+        if let Some(cover) = &mut self.cover {
+            cover.hide()?;
+        }
+        Ok(())
+    }
+
     fn on_event(
         &mut self,
         event: &mut UiEvent,
-        _is_visible: bool,
+        is_visible: bool,
         context: &AppContext,
     ) -> Result<()> {
         match event {
             UiEvent::Database => {
                 let target = self.target_pane();
                 let artist_tag = self.artist_tag();
+                let path = self.stack.path().to_vec();
                 self.cache = ArtistsCache::default();
-                context.query().id(INIT).replace_id(INIT).target(target).query(move |client| {
-                    let result =
-                        client.list_tag(artist_tag, None).context("Cannot list artists")?;
-                    Ok(MpdQueryResult::LsInfo { data: result.0, origin_path: None })
+                context.query().id(REINIT).replace_id(REINIT).target(target).query(move |client| {
+                    let artists =
+                        client.list_tag(artist_tag, None).context("Cannot list artists")?.0;
+                    let songs = match path.first() {
+                        Some(artist) => Some(client.find(&[Filter::new(artist_tag, artist)])?),
+                        None => None,
+                    };
+                    Ok(MpdQueryResult::Any(Box::new((path, artists, songs))))
                 });
             }
             UiEvent::Reconnected => {
                 self.initialized = false;
                 self.before_show(context)?;
             }
+            UiEvent::ModalOpened if is_visible => {
+                if let Some(cover) = &mut self.cover {
+                    cover.hide()?;
+                }
+            }
+            UiEvent::ModalClosed if is_visible => {
+                if let Some(cover) = &mut self.cover {
+                    cover.show();
+                    context.render()?;
+                }
+            }
+            UiEvent::Exit => {
+                if let Some(cover) = &mut self.cover {
+                    cover.cleanup()?;
+                }
+            }
             _ => {}
         }
         Ok(())
@@ -289,6 +368,11 @@ impl Pane for ArtistsPane {
         context: &AppContext,
     ) -> Result<()> {
         match (id, data) {
+            (ALBUM_COVER, MpdQueryResult::AlbumCover { album, data }) => {
+                if let Some(cover) = &mut self.cover {
+                    cover.receive(&album, data, context)?;
+                }
+            }
             (PREVIEW, MpdQueryResult::SongsList { data, origin_path }) => {
                 let Some(artist) = origin_path.and_then(|mut v| v.first_mut().map(std::mem::take))
                 else {
@@ -357,6 +441,45 @@ impl Pane for ArtistsPane {
                 self.prepare_preview(context)?;
                 context.render()?;
             }
+            (REINIT, MpdQueryResult::Any(data)) => {
+                let Ok(data) = data.downcast::<(Vec<String>, Vec<String>, Option<Vec<Song>>)>()
+                else {
+                    return Ok(());
+                };
+                let (origin_path, artists, songs) = *data;
+
+                let mut levels = vec![artists.into_iter().map(DirOrSong::name_only).collect_vec()];
+                if let (Some(artist), Some(songs)) = (origin_path.first(), songs) {
+                    let cached_artist = self.process_songs(artist.clone(), songs, context);
+                    levels.push(
+                        cached_artist
+                            .0
+                            .iter()
+                            .map(|CachedAlbum { name, .. }| DirOrSong::name_only(name.to_owned()))
+                            .collect(),
+                    );
+                    if let Some(album) = origin_path.get(1) {
+                        levels.push(
+                            cached_artist
+                                .0
+                                .iter()
+                                .find(|cached_album| &cached_album.name == album)
+                                .map(|cached_album| {
+                                    cached_album
+                                        .songs
+                                        .iter()
+                                        .cloned()
+                                        .map(DirOrSong::Song)
+                                        .collect()
+                                })
+                                .unwrap_or_default(),
+                        );
+                    }
+                }
+                self.stack.replace_levels(&origin_path, levels, context.config.scrolloff);
+                self.prepare_preview(context)?;
+                context.render()?;
+            }
             (INIT, MpdQueryResult::LsInfo { data, origin_path: _ }) => {
                 self.stack =
                     DirStack::new(data.into_iter().map(DirOrSong::name_only).collect_vec());
@@ -544,6 +667,7 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
         };
         let current = current.to_owned();
 
+        self.request_cover(context)?;
         self.stack_mut().clear_preview();
         match self.stack.path() {
             [artist, album] => {
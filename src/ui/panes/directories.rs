@@ -5,11 +5,11 @@ use ratatui::{Frame, prelude::Rect, widgets::StatefulWidget};
 use super::{Pane, browser::DirOrSong};
 use crate::{
     MpdQueryResult,
-    config::tabs::PaneType,
+    config::{keys::DirectoriesActions, tabs::PaneType},
     context::AppContext,
     mpd::{
         client::Client,
-        commands::{Song, lsinfo::LsInfoEntry},
+        commands::Song,
         mpd_client::{Filter, FilterKind, MpdClient, Tag},
     },
     shared::{
@@ -36,6 +36,7 @@ pub struct DirectoriesPane {
 }
 
 const INIT: &str = "init";
+const REINIT: &str = "reinit";
 const OPEN_OR_PLAY: &str = "open_or_play";
 const PREVIEW: &str = "preview";
 
@@ -67,18 +68,7 @@ impl DirectoriesPane {
                     .replace_id(OPEN_OR_PLAY)
                     .target(PaneType::Directories)
                     .query(move |client| {
-                        let new_current = client.lsinfo(Some(&next_path.join("/").to_string()))?;
-                        let res = new_current
-                            .into_iter()
-                            .filter_map(|v| match v {
-                                LsInfoEntry::Dir(d) => {
-                                    Some(DirOrSong::Dir { name: d.path, full_path: d.full_path })
-                                }
-                                LsInfoEntry::File(s) => Some(DirOrSong::Song(s)),
-                                LsInfoEntry::Playlist(_) => None,
-                            })
-                            .sorted()
-                            .collect();
+                        let res = dir_entries(client, Some(&next_path.join("/").to_string()))?;
 
                         Ok(MpdQueryResult::DirOrSong { data: res, origin_path: Some(next_path) })
                     });
@@ -99,6 +89,34 @@ impl DirectoriesPane {
     }
 }
 
+/// Lists the directory, the database root if `None`, together with the
+/// storages mounted directly inside of it. A freshly mounted storage is not
+/// part of the database until MPD finishes updating it, so it is added as an
+/// empty directory in the meantime.
+fn dir_entries(client: &mut Client<'_>, dir: Option<&str>) -> Result<Vec<DirOrSong>> {
+    let mut result =
+        client.lsinfo(dir)?.into_iter().filter_map(Into::<Option<DirOrSong>>::into).collect_vec();
+
+    // Mounts are not supported by every database plugin
+    let mounts = client.list_mounts().map(|mounts| mounts.0).unwrap_or_default();
+    for mount in mounts {
+        // MPD only allows mounting inside of an existing directory
+        let (parent, name) = mount.mount.rsplit_once('/').unwrap_or(("", &mount.mount));
+        if name.is_empty() || parent != dir.unwrap_or_default() {
+            continue;
+        }
+        let exists = result.iter().any(
+            |entry| matches!(entry, DirOrSong::Dir { full_path, .. } if *full_path == mount.mount),
+        );
+        if !exists {
+            result.push(DirOrSong::Dir { name: name.to_owned(), full_path: mount.mount.clone() });
+        }
+    }
+
+    result.sort();
+    Ok(result)
+}
+
 impl Pane for DirectoriesPane {
     fn render(
         &mut self,
@@ -119,12 +137,7 @@ impl Pane for DirectoriesPane {
         if !self.initialized {
             context.query().id(INIT).replace_id(INIT).target(PaneType::Directories).query(
                 move |client| {
-                    let result = client
-                        .lsinfo(None)?
-                        .into_iter()
-                        .filter_map(Into::<Option<DirOrSong>>::into)
-                        .sorted()
-                        .collect::<Vec<_>>();
+                    let result = dir_entries(client, None)?;
                     Ok(MpdQueryResult::DirOrSong { data: result, origin_path: None })
                 },
             );
@@ -141,16 +154,21 @@ impl Pane for DirectoriesPane {
         context: &AppContext,
     ) -> Result<()> {
         match event {
-            UiEvent::Database => {
-                context.query().id(INIT).replace_id(INIT).target(PaneType::Directories).query(
+            UiEvent::Database | UiEvent::Mount => {
+                let path = self.stack.path().to_vec();
+                context.query().id(REINIT).replace_id(REINIT).target(PaneType::Directories).query(
                     move |client| {
-                        let result = client
-                            .lsinfo(None)?
-                            .into_iter()
-                            .filter_map(Into::<Option<DirOrSong>>::into)
-                            .sorted()
-                            .collect::<Vec<_>>();
-                        Ok(MpdQueryResult::DirOrSong { data: result, origin_path: None })
+                        let mut levels = Vec::with_capacity(path.len() + 1);
+                        levels.push(dir_entries(client, None)?);
+                        for depth in 1..=path.len() {
+                            let dir = path[..depth].join("/");
+                            // Opened directory might have been removed by the update
+                            let Ok(entries) = dir_entries(client, Some(&dir)) else {
+                                break;
+                            };
+                            levels.push(entries);
+                        }
+                        Ok(MpdQueryResult::Any(Box::new((path, levels))))
                     },
                 );
             }
@@ -169,6 +187,20 @@ impl Pane for DirectoriesPane {
 
     fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
         self.handle_filter_input(event, context)?;
+        if let Some(action) = event.as_directories_action(context) {
+            let rescan = match action {
+                DirectoriesActions::Update => false,
+                DirectoriesActions::Rescan => true,
+            };
+            let path = match self.stack.current().selected() {
+                Some(DirOrSong::Dir { full_path, .. }) => full_path.clone(),
+                Some(DirOrSong::Song(song)) => song.file.clone(),
+                None => self.stack.path().join("/"),
+            };
+            context.command(move |client| {
+                Ok(client.start_db_update((!path.is_empty()).then_some(path.as_str()), rescan)?)
+            });
+        }
         self.handle_common_action(event, context)?;
         self.handle_global_action(event, context)?;
         Ok(())
@@ -197,6 +229,15 @@ impl Pane for DirectoriesPane {
                 self.prepare_preview(context)?;
                 context.render()?;
             }
+            (REINIT, MpdQueryResult::Any(data)) => {
+                let Ok(data) = data.downcast::<(Vec<String>, Vec<Vec<DirOrSong>>)>() else {
+                    return Ok(());
+                };
+                let (origin_path, levels) = *data;
+                self.stack.replace_levels(&origin_path, levels, context.config.scrolloff);
+                self.prepare_preview(context)?;
+                context.render()?;
+            }
             (OPEN_OR_PLAY, MpdQueryResult::DirOrSong { data, origin_path }) => {
                 if let Some(origin_path) = origin_path {
                     if origin_path != self.stack().path() {
@@ -318,7 +359,7 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
                     .replace_id("directories_preview")
                     .target(PaneType::Directories)
                     .query(move |client| {
-                        let data: Vec<_> = match client.lsinfo(Some(&next_path)) {
+                        let data: Vec<_> = match dir_entries(client, Some(&next_path)) {
                             Ok(val) => val,
                             Err(err) => {
                                 log::error!(error:? = err; "Failed to get lsinfo for dir",);
@@ -328,16 +369,7 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
                                 });
                             }
                         }
-                        .0
                         .into_iter()
-                        .filter_map(|v| match v {
-                            LsInfoEntry::Dir(dir) => {
-                                Some(DirOrSong::Dir { name: dir.path, full_path: dir.full_path })
-                            }
-                            LsInfoEntry::File(song) => Some(DirOrSong::Song(song)),
-                            LsInfoEntry::Playlist(_) => None,
-                        })
-                        .sorted()
                         .map(|v| v.to_list_item_simple(config))
                         .collect();
 
@@ -1,77 +1,554 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
 use anyhow::Result;
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
-    style::Style,
-    text::Text,
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
 };
 
 use super::Pane;
 use crate::{
+    config::{
+        keys::{CommonAction, LyricsActions},
+        tabs::PaneType,
+    },
     context::AppContext,
-    shared::{key_event::KeyEvent, lrc::Lrc, macros::status_error, mpd_query::run_status_update},
-    ui::UiEvent,
+    mpd::mpd_client::MpdClient,
+    shared::{
+        events::WorkRequest,
+        key_event::KeyEvent,
+        lrc::{
+            Lrc,
+            LrcLine,
+            Lyrics,
+            adjust_offset,
+            find_translations,
+            get_lrc_path,
+            get_translation_path,
+            synced_lrc,
+        },
+        macros::{modal, status_error, status_info, status_warn},
+        mouse_event::{MouseEvent, MouseEventKind},
+        mpd_query::{MpdQueryResult, run_status_update},
+    },
+    ui::{UiEvent, modals::confirm_modal::ConfirmModal},
 };
 
+const SYNC_LINE: &str = "sync_line";
+const OFFSET_STEP_MS: i64 = 100;
+
 #[derive(Debug)]
 pub struct LyricsPane {
-    current_lyrics: Option<Lrc>,
+    current_lyrics: Option<Lyrics>,
     initialized: bool,
-    last_requested_line_idx: usize,
+    /// Time of the next line or word change which has already been scheduled
+    last_requested_time: Option<Duration>,
+    /// Index of the first displayed line of unsynchronized lyrics
+    scroll: usize,
+    /// Line of synchronized lyrics selected by the user, the current line
+    /// follows playback when none is selected
+    cursor: Option<usize>,
+    /// Tap to sync state, lines are stamped one by one with the elapsed time
+    sync: Option<LyricsSync>,
+    /// Translation shown under the lines of synchronized lyrics
+    translation: Option<Lrc>,
+    /// Language of the translation selected by the user, kept between songs
+    translation_lang: Option<String>,
+    /// Modification time of the lrc file the lyrics were loaded from, the
+    /// lyrics are reloaded when the file is indexed again after a change
+    loaded_mtime: Option<SystemTime>,
+    area: Rect,
+}
+
+#[derive(Debug)]
+struct LyricsSync {
+    lines: Vec<String>,
+    times: Vec<Duration>,
 }
 
 impl LyricsPane {
     pub fn new(_context: &AppContext) -> Self {
-        Self { current_lyrics: None, initialized: false, last_requested_line_idx: 0 }
+        Self {
+            current_lyrics: None,
+            initialized: false,
+            last_requested_time: None,
+            scroll: 0,
+            cursor: None,
+            sync: None,
+            translation: None,
+            translation_lang: None,
+            loaded_mtime: None,
+            area: Rect::default(),
+        }
     }
-}
 
-impl Pane for LyricsPane {
-    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
-        let Some(lrc) = &self.current_lyrics else { return Ok(()) };
+    fn load_lyrics(&mut self, context: &AppContext) {
+        match context.find_lyrics() {
+            Ok(lyrics) => {
+                self.current_lyrics = lyrics;
+            }
+            Err(err) => {
+                status_error!("Failed to load lyrics file: '{err}'");
+                self.current_lyrics = None;
+            }
+        }
+        self.last_requested_time = None;
+        self.scroll = 0;
+        self.cursor = None;
+        self.loaded_mtime = context.find_lrc_path().ok().flatten().and_then(|path| mtime(&path));
+        self.load_translation(context);
+    }
 
-        let elapsed = context.status.elapsed;
-        let current_line_idx = lrc
+    /// Lrc path of the current song next to which the translations are
+    /// searched for
+    fn translation_base_path(context: &AppContext) -> Option<PathBuf> {
+        let lyrics_dir = context.config.lyrics_dir?;
+        let (_, song) = context.find_current_song_in_queue()?;
+        get_lrc_path(lyrics_dir, &song.file).ok()
+    }
+
+    fn load_translation(&mut self, context: &AppContext) {
+        self.translation = None;
+        let (Some(lang), Some(base_path)) =
+            (&self.translation_lang, Self::translation_base_path(context))
+        else {
+            return;
+        };
+
+        let path = get_translation_path(&base_path, lang);
+        match std::fs::read_to_string(&path).map(|text| text.parse()) {
+            Ok(Ok(lrc)) => self.translation = Some(lrc),
+            Ok(Err(err)) => status_error!("Failed to parse lyrics translation: '{err}'"),
+            Err(err) if matches!(err.kind(), std::io::ErrorKind::NotFound) => {
+                log::trace!(path:?; "Lyrics translation not found");
+            }
+            Err(err) => status_error!("Failed to read lyrics translation: '{err}'"),
+        }
+    }
+
+    /// Switches to the next available translation language, hiding the
+    /// translation after the last one
+    fn cycle_translation(&mut self, context: &AppContext) -> Result<()> {
+        let languages = match Self::translation_base_path(context) {
+            Some(base_path) => find_translations(&base_path)?,
+            None => Vec::new(),
+        };
+        if languages.is_empty() {
+            status_info!("No lyrics translations found for the current song");
+            return Ok(());
+        }
+
+        let current_idx = self
+            .translation_lang
+            .as_ref()
+            .and_then(|lang| languages.iter().position(|l| l == lang));
+        self.translation_lang = match current_idx {
+            Some(idx) => languages.get(idx + 1).cloned(),
+            None => languages.first().cloned(),
+        };
+        if let Some(lang) = &self.translation_lang {
+            status_info!("Showing '{lang}' lyrics translation");
+        } else {
+            status_info!("Lyrics translation hidden");
+        }
+
+        self.load_translation(context);
+        context.render()?;
+
+        Ok(())
+    }
+
+    /// Translation of the line, matched by its timestamp or by its position if
+    /// the timestamps differ
+    fn translated_line(&self, idx: usize, line: &LrcLine) -> Option<&str> {
+        let translation = self.translation.as_ref()?;
+        translation
             .lines
+            .iter()
+            .find(|translated| translated.time == line.time)
+            .or_else(|| translation.lines.get(idx))
+            .map(|translated| translated.content.as_str())
+    }
+
+    /// Number of rows taken by a single line of synchronized lyrics
+    fn line_height(&self) -> u16 {
+        if self.translation.is_some() { 2 } else { 1 }
+    }
+
+    /// Index of the line which is being sung at the given time
+    fn current_line_idx(lrc: &Lrc, elapsed: Duration) -> usize {
+        lrc.lines
             .iter()
             .enumerate()
             .filter(|line| elapsed >= line.1.time)
             .min_by(|a, b| a.1.time.abs_diff(elapsed).cmp(&b.1.time.abs_diff(elapsed)))
             .map(|result| result.0)
-            .unwrap_or_default();
+            .unwrap_or_default()
+    }
 
-        let rows = area.height;
-        let areas = Layout::vertical((0..rows).map(|_| Constraint::Length(1))).split(area);
+    fn render_synced(&mut self, lrc: &Lrc, frame: &mut Frame, area: Rect, context: &AppContext) {
+        let elapsed = context.status.elapsed;
+        let current_line_idx = Self::current_line_idx(lrc, elapsed);
+        let center_line_idx = self.cursor.unwrap_or(current_line_idx);
+
+        let line_height = self.line_height();
+        let rows = area.height / line_height;
+        let areas =
+            Layout::vertical((0..rows).map(|_| Constraint::Length(line_height))).split(area);
         let middle_row = rows / 2;
+        let text_style = Style::default().fg(context.config.theme.text_color.unwrap_or_default());
 
         for i in 0..rows {
             let i = i as usize;
-            let Some(idx) = (current_line_idx + i).checked_sub(middle_row as usize) else {
+            let Some(idx) = (center_line_idx + i).checked_sub(middle_row as usize) else {
                 continue;
             };
             let Some(line) = lrc.lines.get(idx) else {
                 continue;
             };
+            let line_area = Rect { height: 1, ..areas[i] };
 
-            let darken = (middle_row as usize).abs_diff(i) > 0;
-
-            let p = Text::from(line.content.clone()).centered().style(if darken {
-                Style::default().fg(context.config.theme.text_color.unwrap_or_default())
+            if self.cursor == Some(idx) {
+                let p = Text::from(line.content.clone())
+                    .centered()
+                    .style(context.config.theme.current_item_style);
+                frame.render_widget(p, line_area);
+            } else if idx == current_line_idx {
+                let line = Self::current_line(line, elapsed, context);
+                frame.render_widget(Text::from(line).centered(), line_area);
             } else {
+                let p = Text::from(line.content.clone()).centered().style(text_style);
+                frame.render_widget(p, line_area);
+            }
+
+            if let Some(translated) = self.translated_line(idx, line) {
+                let p = Text::from(translated.to_owned())
+                    .centered()
+                    .style(text_style.add_modifier(Modifier::DIM));
+                frame.render_widget(p, Rect { y: line_area.y + 1, ..line_area });
+            }
+        }
+
+        // Try to schedule the next line or word to be displayed on time
+        let next_time = lrc
+            .lines
+            .get(current_line_idx)
+            .and_then(|line| line.words.iter().find(|word| word.time > elapsed))
+            .map(|word| word.time)
+            .or_else(|| lrc.lines.get(current_line_idx + 1).map(|line| line.time));
+        if let Some(next_time) = next_time {
+            if self.last_requested_time != Some(next_time) {
+                self.last_requested_time = Some(next_time);
+                context.scheduler.schedule(next_time.saturating_sub(elapsed), run_status_update);
+            }
+        }
+    }
+
+    /// Highlights the currently sung word if the line has word timings
+    fn current_line(line: &LrcLine, elapsed: Duration, context: &AppContext) -> Line<'static> {
+        let line_style = context.config.theme.highlighted_item_style;
+        let Some(current_word_idx) = line.current_word_idx(elapsed) else {
+            return Line::styled(line.content.clone(), line_style);
+        };
+
+        let prefix_len =
+            line.content.len() - line.words.iter().map(|w| w.content.len()).sum::<usize>();
+        std::iter::once(Span::styled(line.content[..prefix_len].to_owned(), line_style))
+            .chain(line.words.iter().enumerate().map(|(idx, word)| {
+                Span::styled(
+                    word.content.clone(),
+                    if idx == current_word_idx {
+                        context.config.theme.lyrics_current_word_style
+                    } else {
+                        line_style
+                    },
+                )
+            }))
+            .collect()
+    }
+
+    fn render_unsynced(
+        &self,
+        lines: &[String],
+        frame: &mut Frame,
+        area: Rect,
+        context: &AppContext,
+    ) {
+        let areas = Layout::vertical((0..area.height).map(|_| Constraint::Length(1))).split(area);
+
+        for (line, line_area) in lines.iter().skip(self.scroll).zip(areas.iter()) {
+            let p = Text::from(line.as_str()).centered().style(context.config.as_text_style());
+            frame.render_widget(p, *line_area);
+        }
+    }
+
+    fn render_sync(&self, sync: &LyricsSync, frame: &mut Frame, area: Rect, context: &AppContext) {
+        let rows = area.height;
+        let areas = Layout::vertical((0..rows).map(|_| Constraint::Length(1))).split(area);
+        let middle_row = rows as usize / 2;
+        let next_line_idx = sync.times.len();
+
+        for i in 0..rows as usize {
+            let Some(idx) = (next_line_idx + i).checked_sub(middle_row) else {
+                continue;
+            };
+            let Some(line) = sync.lines.get(idx) else {
+                continue;
+            };
+
+            let p = Text::from(line.as_str()).centered().style(if idx == next_line_idx {
                 context.config.theme.highlighted_item_style
+            } else {
+                Style::default().fg(context.config.theme.text_color.unwrap_or_default())
             });
-
             frame.render_widget(p, areas[i]);
         }
+    }
+
+    /// Shifts the lyrics and saves the new offset to the lrc file right away
+    fn adjust_offset(&mut self, delta: i64, context: &AppContext) -> Result<()> {
+        if !matches!(self.current_lyrics, Some(Lyrics::Synced(_))) {
+            status_warn!("No synchronized lyrics found for the current song");
+            return Ok(());
+        }
+        let Some(path) = context.find_lrc_path()? else {
+            status_warn!("No lrc file found for the current song");
+            return Ok(());
+        };
+
+        let (text, offset) = adjust_offset(&std::fs::read_to_string(&path)?, delta)?;
+        std::fs::write(&path, &text)?;
+        self.loaded_mtime = mtime(&path);
+        self.current_lyrics = Some(Lyrics::parse(&text));
+        self.last_requested_time = None;
+        status_info!("Lyrics offset set to {offset}ms");
+        context.render()?;
+
+        Ok(())
+    }
+
+    fn toggle_sync(&mut self, context: &AppContext) -> Result<()> {
+        if let Some(sync) = self.sync.take() {
+            self.save_sync(sync, context)?;
+            context.render()?;
+            return Ok(());
+        }
+
+        let lines: Vec<String> = match &self.current_lyrics {
+            Some(Lyrics::Synced(lrc)) => {
+                lrc.lines.iter().map(|line| line.content.clone()).collect()
+            }
+            Some(Lyrics::Unsynced(lines)) => lines.clone(),
+            None => Vec::new(),
+        }
+        .into_iter()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+
+        if lines.is_empty() {
+            status_warn!("No lyrics to sync for the current song");
+            return Ok(());
+        }
+
+        self.sync = Some(LyricsSync { lines, times: Vec::new() });
+        status_info!("Tap to sync started");
+        context.render()?;
+
+        Ok(())
+    }
+
+    fn sync_line(&mut self, elapsed: Duration, context: &AppContext) -> Result<()> {
+        let Some(sync) = &mut self.sync else {
+            return Ok(());
+        };
+
+        sync.times.push(elapsed);
+        if sync.times.len() >= sync.lines.len() {
+            if let Some(sync) = self.sync.take() {
+                self.save_sync(sync, context)?;
+            }
+        }
+
+        context.render()?;
+
+        Ok(())
+    }
+
+    /// Writes the stamped lines as the current song's lrc file in the
+    /// `lyrics_dir`
+    fn save_sync(&mut self, sync: LyricsSync, context: &AppContext) -> Result<()> {
+        if sync.times.is_empty() {
+            status_info!("Tap to sync cancelled");
+            return Ok(());
+        }
+
+        let Some(lyrics_dir) = context.config.lyrics_dir else {
+            status_error!("Cannot save synced lyrics, lyrics_dir is not configured");
+            return Ok(());
+        };
+        let Some((_, song)) = context.find_current_song_in_queue() else {
+            return Ok(());
+        };
+
+        let path = get_lrc_path(lyrics_dir, &song.file)?;
+        // Lines after the last tap have no timestamp and are dropped
+        let is_complete = sync.times.len() >= sync.lines.len();
+        let lines: Vec<_> = sync.times.into_iter().zip(sync.lines).collect();
+        let lrc = synced_lrc(song, &lines);
+
+        let (message, confirm_label, rows) = match (is_complete, path.exists()) {
+            (true, false) => {
+                write_synced_lrc(&path, &lrc)?;
+                self.load_lyrics(context);
+                return Ok(());
+            }
+            (true, true) => (
+                "Lyrics file for this song already exists. Do you want to overwrite it?",
+                "Overwrite",
+                6,
+            ),
+            (false, false) => (
+                "Not all lines were synced and the rest will be dropped. Do you want to save the synced lines?",
+                "Save",
+                7,
+            ),
+            (false, true) => (
+                "Not all lines were synced and the rest will be dropped. Lyrics file for this song already exists. Do you want to overwrite it?",
+                "Overwrite",
+                8,
+            ),
+        };
+
+        modal!(
+            context,
+            ConfirmModal::new(context)
+                .message(message)
+                .on_confirm(move |context| {
+                    write_synced_lrc(&path, &lrc)?;
+                    context.work_sender.send(WorkRequest::IndexSingleLrc { path: path.clone() })?;
+                    Ok(())
+                })
+                .confirm_label(confirm_label)
+                .size(45, rows)
+        );
+        Ok(())
+    }
+
+    /// Moves the cursor of synchronized lyrics or scrolls unsynchronized ones
+    fn navigate(&mut self, action: CommonAction, context: &AppContext) -> Result<()> {
+        match &self.current_lyrics {
+            Some(Lyrics::Synced(lrc)) => {
+                let len = lrc.lines.len();
+                self.move_cursor(
+                    action,
+                    len,
+                    Self::current_line_idx(lrc, context.status.elapsed),
+                    context,
+                )
+            }
+            Some(Lyrics::Unsynced(lines)) => self.scroll(action, lines.len(), context),
+            None => Ok(()),
+        }
+    }
+
+    fn move_cursor(
+        &mut self,
+        action: CommonAction,
+        len: usize,
+        current_line_idx: usize,
+        context: &AppContext,
+    ) -> Result<()> {
+        let viewport = (self.area.height / self.line_height()) as usize;
+        let cursor = self.cursor.unwrap_or(current_line_idx);
+        let last = len.saturating_sub(1);
+        self.cursor = match action {
+            CommonAction::Down => Some(cursor + 1),
+            CommonAction::Up => Some(cursor.saturating_sub(1)),
+            CommonAction::DownHalf => Some(cursor + viewport / 2),
+            CommonAction::UpHalf => Some(cursor.saturating_sub(viewport / 2)),
+            CommonAction::PageDown => Some(cursor + viewport),
+            CommonAction::PageUp => Some(cursor.saturating_sub(viewport)),
+            CommonAction::Top => Some(0),
+            CommonAction::Bottom => Some(last),
+            CommonAction::Confirm if self.cursor.is_some() => {
+                self.seek_to_line(cursor, context);
+                None
+            }
+            CommonAction::Close => None,
+            _ => return Ok(()),
+        }
+        .map(|cursor| cursor.min(last));
+
+        context.render()?;
+
+        Ok(())
+    }
+
+    /// Seeks the current song to the start of the synchronized lyrics line
+    fn seek_to_line(&self, idx: usize, context: &AppContext) {
+        let Some(Lyrics::Synced(lrc)) = &self.current_lyrics else {
+            return;
+        };
+        let Some(line) = lrc.lines.get(idx) else {
+            return;
+        };
+
+        let position = line.time;
+        context.command(move |client| {
+            client.seek_current_to(position)?;
+            Ok(())
+        });
+    }
+
+    /// Scrolls unsynchronized lyrics
+    fn scroll(&mut self, action: CommonAction, len: usize, context: &AppContext) -> Result<()> {
+        let viewport = self.area.height as usize;
+        let max_scroll = len.saturating_sub(viewport);
+        let scroll = match action {
+            CommonAction::Down => self.scroll + 1,
+            CommonAction::Up => self.scroll.saturating_sub(1),
+            CommonAction::DownHalf => self.scroll + viewport / 2,
+            CommonAction::UpHalf => self.scroll.saturating_sub(viewport / 2),
+            CommonAction::PageDown => self.scroll + viewport,
+            CommonAction::PageUp => self.scroll.saturating_sub(viewport),
+            CommonAction::Top => 0,
+            CommonAction::Bottom => max_scroll,
+            _ => return Ok(()),
+        }
+        .min(max_scroll);
+
+        if scroll != self.scroll {
+            self.scroll = scroll;
+            context.render()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Pane for LyricsPane {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
+        self.area = area;
+        if let Some(sync) = &self.sync {
+            self.render_sync(sync, frame, area, context);
+            return Ok(());
+        }
 
-        // Try to schedule the next line to be displayed on time
-        if self.last_requested_line_idx != current_line_idx + 1 {
-            if let Some(line) = lrc.lines.get(current_line_idx + 1) {
-                self.last_requested_line_idx = current_line_idx + 1;
-                context
-                    .scheduler
-                    .schedule(line.time.saturating_sub(context.status.elapsed), run_status_update);
+        match self.current_lyrics.take() {
+            Some(Lyrics::Synced(lrc)) => {
+                self.render_synced(&lrc, frame, area, context);
+                self.current_lyrics = Some(Lyrics::Synced(lrc));
             }
+            Some(Lyrics::Unsynced(lines)) => {
+                self.render_unsynced(&lines, frame, area, context);
+                self.current_lyrics = Some(Lyrics::Unsynced(lines));
+            }
+            None => {}
         }
 
         Ok(())
@@ -79,16 +556,7 @@ impl Pane for LyricsPane {
 
     fn before_show(&mut self, context: &AppContext) -> Result<()> {
         if !self.initialized {
-            match context.find_lrc() {
-                Ok(lrc) => {
-                    self.current_lyrics = lrc;
-                }
-                Err(err) => {
-                    status_error!("Failed to load lyrics file: '{err}'");
-                    self.current_lyrics = None;
-                }
-            }
-            self.last_requested_line_idx = 0;
+            self.load_lyrics(context);
             self.initialized = true;
         }
 
@@ -103,37 +571,148 @@ impl Pane for LyricsPane {
     ) -> Result<()> {
         match event {
             UiEvent::SongChanged | UiEvent::Reconnected => {
-                match context.find_lrc() {
-                    Ok(lrc) => {
-                        self.current_lyrics = lrc;
-                        context.render()?;
-                    }
-                    Err(err) => {
-                        self.current_lyrics = None;
-                        status_error!("Failed to load lyrics file: '{err}'");
-                    }
-                }
-                self.last_requested_line_idx = 0;
-            }
-            UiEvent::LyricsIndexed if self.current_lyrics.is_none() => {
-                match context.find_lrc() {
-                    Ok(lrc) => {
-                        self.current_lyrics = lrc;
-                        context.render()?;
-                    }
-                    Err(err) => {
-                        self.current_lyrics = None;
-                        status_error!("Failed to load lyrics file: '{err}'");
-                    }
+                if self.sync.take().is_some() {
+                    status_warn!("Tap to sync cancelled because the song changed");
                 }
-                self.last_requested_line_idx = 0;
+                self.load_lyrics(context);
+                context.render()?;
+            }
+            UiEvent::CycleLyricsTranslation => self.cycle_translation(context)?,
+            UiEvent::LyricsIndexed
+                if !matches!(self.current_lyrics, Some(Lyrics::Synced(_)))
+                    || context.find_lrc_path().ok().flatten().and_then(|path| mtime(&path))
+                        != self.loaded_mtime =>
+            {
+                self.load_lyrics(context);
+                context.render()?;
             }
             _ => {}
         }
         Ok(())
     }
 
-    fn handle_action(&mut self, _event: &mut KeyEvent, _context: &mut AppContext) -> Result<()> {
+    fn on_query_finished(
+        &mut self,
+        id: &'static str,
+        data: MpdQueryResult,
+        _is_visible: bool,
+        context: &AppContext,
+    ) -> Result<()> {
+        if let (SYNC_LINE, MpdQueryResult::Status(status)) = (id, data) {
+            self.sync_line(status.elapsed, context)?;
+        }
+
         Ok(())
     }
+
+    fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        if let Some(action) = event.as_lyrics_action(context) {
+            match action {
+                LyricsActions::IncreaseOffset => self.adjust_offset(OFFSET_STEP_MS, context)?,
+                LyricsActions::DecreaseOffset => self.adjust_offset(-OFFSET_STEP_MS, context)?,
+                LyricsActions::ToggleSync => self.toggle_sync(context)?,
+                LyricsActions::SyncLine if self.sync.is_some() => {
+                    // Status is fetched right away because the periodically
+                    // updated elapsed time is not precise enough
+                    context
+                        .query()
+                        .id(SYNC_LINE)
+                        .target(PaneType::Lyrics)
+                        .query(|client| Ok(MpdQueryResult::Status(client.get_status()?)));
+                }
+                LyricsActions::SyncLine => {}
+            }
+        } else if let Some(action) = event.as_common_action(context) {
+            self.navigate(action, context)?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &AppContext) -> Result<()> {
+        if !self.area.contains(event.into()) {
+            return Ok(());
+        }
+
+        match event.kind {
+            MouseEventKind::LeftClick | MouseEventKind::DoubleClick => {
+                let Some(Lyrics::Synced(lrc)) = &self.current_lyrics else {
+                    return Ok(());
+                };
+                let center_line_idx = self
+                    .cursor
+                    .unwrap_or_else(|| Self::current_line_idx(lrc, context.status.elapsed));
+                let line_height = self.line_height();
+                let row = usize::from((event.y - self.area.y) / line_height);
+                let middle_row = usize::from(self.area.height / line_height / 2);
+                if let Some(idx) = (center_line_idx + row).checked_sub(middle_row) {
+                    self.seek_to_line(idx, context);
+                    self.cursor = None;
+                    context.render()?;
+                }
+            }
+            MouseEventKind::ScrollDown => self.navigate(CommonAction::Down, context)?,
+            MouseEventKind::ScrollUp => self.navigate(CommonAction::Up, context)?,
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+fn write_synced_lrc(path: &Path, content: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, content)?;
+    status_info!("Synced lyrics saved to '{}'", path.display());
+    Ok(())
+}
+
+fn mtime(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::tests::fixtures::app_context;
+
+    fn pane(context: &AppContext) -> LyricsPane {
+        let mut pane = LyricsPane::new(context);
+        pane.current_lyrics =
+            Some(Lyrics::Synced("[00:01.00]one\n[00:02.00]two\n[00:03.00]three".parse().unwrap()));
+        pane.area = Rect::new(0, 0, 10, 5);
+        pane
+    }
+
+    #[rstest]
+    fn cursor_starts_at_current_line(mut app_context: AppContext) {
+        app_context.status.elapsed = Duration::from_millis(2500);
+        let mut pane = pane(&app_context);
+
+        pane.navigate(CommonAction::Down, &app_context).unwrap();
+        assert_eq!(pane.cursor, Some(2));
+        pane.navigate(CommonAction::Down, &app_context).unwrap();
+        assert_eq!(pane.cursor, Some(2));
+        pane.navigate(CommonAction::Top, &app_context).unwrap();
+        assert_eq!(pane.cursor, Some(0));
+        pane.navigate(CommonAction::Close, &app_context).unwrap();
+        assert_eq!(pane.cursor, None);
+    }
+
+    #[rstest]
+    fn confirm_resets_cursor_to_follow_playback(app_context: AppContext) {
+        let mut pane = pane(&app_context);
+
+        pane.navigate(CommonAction::Confirm, &app_context).unwrap();
+        assert_eq!(pane.cursor, None);
+        pane.navigate(CommonAction::Bottom, &app_context).unwrap();
+        assert_eq!(pane.cursor, Some(2));
+        pane.navigate(CommonAction::Confirm, &app_context).unwrap();
+        assert_eq!(pane.cursor, None);
+    }
 }
@@ -1,6 +1,10 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 use album_art::AlbumArtPane;
+use album_grid::AlbumGridPane;
 use albums::AlbumsPane;
 use anyhow::Result;
 use artists::{ArtistsPane, ArtistsPaneMode};
@@ -20,6 +24,7 @@ use ratatui::{
     widgets::Block,
 };
 use search::SearchPane;
+use stats::StatsPane;
 use strum::Display;
 use tabs::TabsPane;
 
@@ -49,6 +54,7 @@ use crate::{
 };
 
 pub mod album_art;
+pub mod album_grid;
 pub mod albums;
 pub mod artists;
 pub mod directories;
@@ -63,6 +69,7 @@ pub mod progress_bar;
 pub mod property;
 pub mod queue;
 pub mod search;
+pub mod stats;
 pub mod tabs;
 
 #[derive(Debug, Display, strum::EnumDiscriminants)]
@@ -74,10 +81,12 @@ pub enum Panes<'pane_ref, 'pane> {
     Artists(&'pane_ref mut ArtistsPane),
     AlbumArtists(&'pane_ref mut ArtistsPane),
     Albums(&'pane_ref mut AlbumsPane),
+    AlbumGrid(&'pane_ref mut AlbumGridPane),
     Playlists(&'pane_ref mut PlaylistsPane),
     Search(&'pane_ref mut SearchPane),
     AlbumArt(&'pane_ref mut AlbumArtPane),
     Lyrics(&'pane_ref mut LyricsPane),
+    Stats(&'pane_ref mut StatsPane),
     ProgressBar(&'pane_ref mut ProgressBarPane),
     Header(&'pane_ref mut HeaderPane),
     Tabs(&'pane_ref mut TabsPane<'pane>),
@@ -94,12 +103,14 @@ pub struct PaneContainer<'panes> {
     pub logs: LogsPane,
     pub directories: DirectoriesPane,
     pub albums: AlbumsPane,
+    pub album_grid: AlbumGridPane,
     pub artists: ArtistsPane,
     pub album_artists: ArtistsPane,
     pub playlists: PlaylistsPane,
     pub search: SearchPane,
     pub album_art: AlbumArtPane,
     pub lyrics: LyricsPane,
+    pub stats: StatsPane,
     pub progress_bar: ProgressBarPane,
     pub header: HeaderPane,
     pub tabs: TabsPane<'panes>,
@@ -115,12 +126,14 @@ impl<'panes> PaneContainer<'panes> {
             logs: LogsPane::new(),
             directories: DirectoriesPane::new(context),
             albums: AlbumsPane::new(context),
+            album_grid: AlbumGridPane::new(context),
             artists: ArtistsPane::new(ArtistsPaneMode::Artist, context),
             album_artists: ArtistsPane::new(ArtistsPaneMode::AlbumArtist, context),
             playlists: PlaylistsPane::new(context),
             search: SearchPane::new(context),
             album_art: AlbumArtPane::new(context),
             lyrics: LyricsPane::new(context),
+            stats: StatsPane::new(context),
             progress_bar: ProgressBarPane::new(),
             header: HeaderPane::new(),
             tabs: TabsPane::new(context)?,
@@ -143,10 +156,12 @@ impl<'panes> PaneContainer<'panes> {
                 Some(Panes::AlbumArtists(&mut self.album_artists))
             }
             PaneTypeDiscriminants::Albums => Some(Panes::Albums(&mut self.albums)),
+            PaneTypeDiscriminants::AlbumGrid => Some(Panes::AlbumGrid(&mut self.album_grid)),
             PaneTypeDiscriminants::Playlists => Some(Panes::Playlists(&mut self.playlists)),
             PaneTypeDiscriminants::Search => Some(Panes::Search(&mut self.search)),
             PaneTypeDiscriminants::AlbumArt => Some(Panes::AlbumArt(&mut self.album_art)),
             PaneTypeDiscriminants::Lyrics => Some(Panes::Lyrics(&mut self.lyrics)),
+            PaneTypeDiscriminants::Stats => Some(Panes::Stats(&mut self.stats)),
             PaneTypeDiscriminants::ProgressBar => Some(Panes::ProgressBar(&mut self.progress_bar)),
             PaneTypeDiscriminants::Header => Some(Panes::Header(&mut self.header)),
             PaneTypeDiscriminants::Tabs => Some(Panes::Tabs(&mut self.tabs)),
@@ -170,10 +185,12 @@ impl<'panes> PaneContainer<'panes> {
             PaneType::Artists => Panes::Artists(&mut self.artists),
             PaneType::AlbumArtists => Panes::AlbumArtists(&mut self.album_artists),
             PaneType::Albums => Panes::Albums(&mut self.albums),
+            PaneType::AlbumGrid => Panes::AlbumGrid(&mut self.album_grid),
             PaneType::Playlists => Panes::Playlists(&mut self.playlists),
             PaneType::Search => Panes::Search(&mut self.search),
             PaneType::AlbumArt => Panes::AlbumArt(&mut self.album_art),
             PaneType::Lyrics => Panes::Lyrics(&mut self.lyrics),
+            PaneType::Stats => Panes::Stats(&mut self.stats),
             PaneType::ProgressBar => Panes::ProgressBar(&mut self.progress_bar),
             PaneType::Header => Panes::Header(&mut self.header),
             PaneType::Tabs => Panes::Tabs(&mut self.tabs),
@@ -197,10 +214,12 @@ macro_rules! pane_call {
             Panes::Artists(ref mut s) => s.$fn($($param),+),
             Panes::AlbumArtists(ref mut s) => s.$fn($($param),+),
             Panes::Albums(ref mut s) => s.$fn($($param),+),
+            Panes::AlbumGrid(ref mut s) => s.$fn($($param),+),
             Panes::Playlists(ref mut s) => s.$fn($($param),+),
             Panes::Search(ref mut s) => s.$fn($($param),+),
             Panes::AlbumArt(ref mut s) => s.$fn($($param),+),
             Panes::Lyrics(ref mut s) => s.$fn($($param),+),
+            Panes::Stats(ref mut s) => s.$fn($($param),+),
             Panes::ProgressBar(ref mut s) => s.$fn($($param),+),
             Panes::Header(ref mut s) => s.$fn($($param),+),
             Panes::Tabs(ref mut s) => s.$fn($($param),+),
@@ -705,6 +724,21 @@ impl Property<'static, SongProperty> {
     }
 }
 
+/// Frames of the spinner shown while the database is being updated
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Current frame of the spinner, advanced by the scheduled job which rerenders
+/// the UI while the database update is running
+static SPINNER_FRAME: AtomicUsize = AtomicUsize::new(0);
+
+pub fn advance_spinner() {
+    SPINNER_FRAME.fetch_add(1, Ordering::Relaxed);
+}
+
+fn spinner_frame() -> &'static str {
+    SPINNER_FRAMES[SPINNER_FRAME.load(Ordering::Relaxed) % SPINNER_FRAMES.len()]
+}
+
 impl Property<'static, PropertyKind> {
     fn default_as_span<'song: 's, 's>(
         &self,
@@ -714,6 +748,15 @@ impl Property<'static, PropertyKind> {
         self.default.and_then(|p| p.as_span(song, status))
     }
 
+    /// Same as [`Self::as_span`] but without any styling, used to format
+    /// output of the CLI
+    pub fn as_string(&self, song: Option<&Song>, status: &Status) -> Option<String> {
+        self.as_span(song, status).map(|spans| match spans {
+            Either::Left(span) => span.content.into_owned(),
+            Either::Right(spans) => spans.into_iter().map(|span| span.content).collect(),
+        })
+    }
+
     pub fn as_span<'song: 's, 's>(
         &'s self,
         song: Option<&'song Song>,
@@ -831,6 +874,15 @@ impl Property<'static, PropertyKind> {
                     || self.default_as_span(song, status),
                     |v| Some(Either::Left(Span::styled(v.to_string(), style))),
                 ),
+                StatusProperty::UpdatingDb => status.updating_db.map_or_else(
+                    || self.default_as_span(song, status),
+                    |_| {
+                        Some(Either::Left(Span::styled(
+                            format!("{} Updating database…", spinner_frame()),
+                            style,
+                        )))
+                    },
+                ),
             },
             PropertyKindOrText::Property(PropertyKind::Widget(w)) => match w {
                 WidgetProperty::Volume => {
@@ -863,7 +915,7 @@ impl Property<'static, PropertyKind> {
                     match format.as_span(song, status) {
                         Some(Either::Left(span)) => buf.push(span),
                         Some(Either::Right(spans)) => buf.extend(spans),
-                        None => return None,
+                        None => return self.default_as_span(song, status),
                     }
                 }
                 return Some(Either::Right(buf));
@@ -1084,6 +1136,64 @@ mod format_tests {
             );
         }
 
+        #[test_case(Some(3), Some("Updating database…"))]
+        #[test_case(None, None)]
+        fn updating_db_shows_spinner_only_during_update(
+            updating_db: Option<u32>,
+            expected: Option<&str>,
+        ) {
+            let format = Property::<'static, PropertyKind> {
+                kind: PropertyKindOrText::Property(PropertyKind::Status(
+                    StatusProperty::UpdatingDb,
+                )),
+                style: None,
+                default: None,
+            };
+            let status = Status { updating_db, ..Default::default() };
+
+            let result = format.as_string(None, &status);
+
+            assert_eq!(
+                result.as_deref().and_then(|value| value.split_once(' ')).map(|(_, label)| label),
+                expected
+            );
+        }
+
+        #[test_case(Some(320), "320 kbps")]
+        #[test_case(None, "no bitrate")]
+        fn group_falls_back_to_default_when_property_is_missing(
+            bitrate: Option<u32>,
+            expected: &str,
+        ) {
+            let format = Property::<'static, PropertyKind> {
+                kind: PropertyKindOrText::Group(&[
+                    &Property {
+                        kind: PropertyKindOrText::Property(PropertyKind::Status(
+                            StatusProperty::Bitrate,
+                        )),
+                        style: None,
+                        default: None,
+                    },
+                    &Property {
+                        kind: PropertyKindOrText::Text(" kbps"),
+                        style: None,
+                        default: None,
+                    },
+                ]),
+                style: None,
+                default: Some(&Property {
+                    kind: PropertyKindOrText::Text("no bitrate"),
+                    style: None,
+                    default: None,
+                }),
+            };
+            let status = Status { bitrate, ..Default::default() };
+
+            let result = format.as_string(None, &status);
+
+            assert_eq!(result.as_deref(), Some(expected));
+        }
+
         #[test_case("otherplay", "otherstopped", "otherpaused", State::Play, "otherplay")]
         #[test_case("otherplay", "otherstopped", "otherpaused", State::Pause, "otherpaused")]
         #[test_case("otherplay", "otherstopped", "otherpaused", State::Stop, "otherstopped")]
@@ -1505,4 +1615,47 @@ mod format_tests {
             assert_eq!(result, Some("innerfallbackouter".to_owned()));
         }
     }
+
+    mod template {
+        use std::collections::HashMap;
+
+        use crate::{
+            config::theme::template::Template,
+            mpd::commands::{Song, Status},
+        };
+
+        fn format(template: &str, song: &Song, status: &Status) -> String {
+            template
+                .parse::<Template>()
+                .and_then(Template::into_properties)
+                .expect("template to be valid")
+                .iter()
+                .filter_map(|prop| prop.as_string(Some(song), status))
+                .collect()
+        }
+
+        #[test]
+        fn group_falls_back_to_default() {
+            let song = Song {
+                metadata: HashMap::from([("title".to_string(), "title".to_owned())]),
+                ..Default::default()
+            };
+
+            let result = format("[{Artist} - ]|[unknown - ]{Title}", &song, &Status::default());
+
+            assert_eq!(result, "unknown - title");
+        }
+
+        #[test]
+        fn missing_properties_are_skipped() {
+            let song = Song {
+                metadata: HashMap::from([("artist".to_string(), "artist".to_owned())]),
+                ..Default::default()
+            };
+
+            let result = format("{Artist}{Title} {Volume}", &song, &Status::default());
+
+            assert_eq!(result, "artist 0");
+        }
+    }
 }
@@ -139,30 +139,25 @@ impl Pane for PlaylistsPane {
         _is_visible: bool,
         context: &AppContext,
     ) -> Result<()> {
-        let id = match event {
-            UiEvent::Database => Some(INIT),
-            UiEvent::StoredPlaylist => Some(REINIT),
-            _ => None,
-        };
         match event {
+            // Songs of the playlists might have changed by the database update
+            // as well, both reinitialize while keeping the position
             UiEvent::Database | UiEvent::StoredPlaylist => {
-                if let Some(id) = id {
-                    context.query().id(id).replace_id(id).target(PaneType::Playlists).query(
-                        move |client| {
-                            let result: Vec<_> = client
-                                .list_playlists()
-                                .context("Cannot list playlists")?
-                                .into_iter()
-                                .map(|playlist| DirOrSong::Dir {
-                                    name: playlist.name,
-                                    full_path: String::new(),
-                                })
-                                .sorted()
-                                .collect();
-                            Ok(MpdQueryResult::DirOrSong { data: result, origin_path: None })
-                        },
-                    );
-                }
+                context.query().id(REINIT).replace_id(REINIT).target(PaneType::Playlists).query(
+                    move |client| {
+                        let result: Vec<_> = client
+                            .list_playlists()
+                            .context("Cannot list playlists")?
+                            .into_iter()
+                            .map(|playlist| DirOrSong::Dir {
+                                name: playlist.name,
+                                full_path: String::new(),
+                            })
+                            .sorted()
+                            .collect();
+                        Ok(MpdQueryResult::DirOrSong { data: result, origin_path: None })
+                    },
+                );
             }
             UiEvent::Reconnected => {
                 self.initialized = false;
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use ratatui::{
@@ -19,18 +19,17 @@ use crate::{
         key_event::KeyEvent,
         mouse_event::{MouseEvent, MouseEventKind},
     },
-    ui::{StatusMessage, UiEvent},
+    ui::UiEvent,
 };
 
 #[derive(Debug)]
 pub struct ProgressBarPane {
     area: Rect,
-    status_message: Option<StatusMessage>,
 }
 
 impl ProgressBarPane {
     pub fn new() -> Self {
-        Self { area: Rect::default(), status_message: None }
+        Self { area: Rect::default() }
     }
 }
 
@@ -42,18 +41,16 @@ impl Pane for ProgressBarPane {
         context: &AppContext,
     ) -> anyhow::Result<()> {
         self.area = area;
-        if self
-            .status_message
-            .as_ref()
-            .is_some_and(|m| m.created.elapsed() > std::time::Duration::from_secs(5))
-        {
-            self.status_message = None;
-        }
+        let now = Instant::now();
 
-        if let Some(StatusMessage { message, level, .. }) = &self.status_message {
-            let status_bar = Paragraph::new(message.to_owned())
+        if let Some(notification) = context.notifications.current(now) {
+            let message = match context.notifications.pending(now) {
+                0 => notification.message.clone(),
+                pending => format!("{} (+{pending} more)", notification.message),
+            };
+            let status_bar = Paragraph::new(message)
                 .alignment(ratatui::prelude::Alignment::Center)
-                .style(Style::default().fg(level.into()).bg(Color::Black));
+                .style(Style::default().fg((&notification.level).into()).bg(Color::Black));
             frame.render_widget(status_bar, self.area);
         } else {
             let elapsed_bar = context.config.as_styled_progress_bar();
@@ -75,20 +72,10 @@ impl Pane for ProgressBarPane {
 
     fn on_event(
         &mut self,
-        event: &mut UiEvent,
+        _event: &mut UiEvent,
         _is_visible: bool,
         _context: &AppContext,
     ) -> Result<()> {
-        match event {
-            UiEvent::Status(message, level) => {
-                self.status_message = Some(StatusMessage {
-                    message: std::mem::take(message),
-                    level: *level,
-                    created: std::time::Instant::now(),
-                });
-            }
-            _ => {}
-        }
         Ok(())
     }
 
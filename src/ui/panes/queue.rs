@@ -85,6 +85,12 @@ impl QueuePane {
             },
         }
     }
+
+    /// Keeps the selected song and scroll position of the previous instance of
+    /// the pane, used when the config is reloaded
+    pub fn restore_position(&mut self, previous: QueuePane) {
+        self.scrolling_state = previous.scrolling_state;
+    }
 }
 
 impl Pane for QueuePane {
@@ -522,7 +528,16 @@ impl Pane for QueuePane {
                     if let Some(selected_song) =
                         self.scrolling_state.get_selected().and_then(|idx| context.queue.get(idx))
                     {
-                        modal!(context, SongInfoModal::new(selected_song.clone()));
+                        let marked = self
+                            .scrolling_state
+                            .get_marked()
+                            .iter()
+                            .filter_map(|idx| context.queue.get(*idx).cloned())
+                            .collect_vec();
+                        modal!(
+                            context,
+                            SongInfoModal::new(selected_song.clone()).edited_songs(marked)
+                        );
                     } else {
                         status_error!("No song selected");
                     }
@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::{collections::BTreeMap, rc::Rc};
 
 use anyhow::{Context, Result};
 use crossterm::event::KeyCode;
@@ -9,11 +9,12 @@ use ratatui::{
     text::Span,
     widgets::{Block, Borders, List, ListItem, Padding},
 };
+use serde::{Serialize, de::DeserializeOwned};
 
 use super::{CommonAction, Pane};
 use crate::{
     MpdQueryResult,
-    config::{Config, Search, keys::GlobalAction, tabs::PaneType},
+    config::{Config, SavedSearch, Search, SearchQuery, keys::GlobalAction, tabs::PaneType},
     context::AppContext,
     core::command::{create_env, run_external},
     mpd::{
@@ -23,29 +24,43 @@ use crate::{
     shared::{
         ext::mpd_client::MpdClientExt,
         key_event::KeyEvent,
-        macros::{status_info, status_warn},
+        macros::{modal, status_error, status_info, status_warn},
         mouse_event::{MouseEvent, MouseEventKind},
         mpd_query::PreviewGroup,
     },
     ui::{
         UiEvent,
         dirstack::{Dir, DirStackItem},
+        modals::{input_modal::InputModal, select_modal::SelectModal},
         widgets::{button::Button, input::Input},
     },
 };
 
 #[derive(Debug)]
 pub struct SearchPane {
-    inputs: InputGroups<2, 1>,
+    inputs: InputGroups<2, 3>,
     phase: Phase,
     preview: Option<Vec<PreviewGroup>>,
     songs_dir: Dir<Song>,
     input_areas: Rc<[Rect]>,
     column_areas: [Rect; 3],
+    /// Previous searches, the most recent first
+    history: Vec<SearchQuery>,
+    /// Index of the search recalled from the history and the inputs' values
+    /// from before the history was entered
+    history_idx: Option<(usize, SearchQuery)>,
+    saved: Vec<SavedSearch>,
+    /// Cache directory the history and saved searches were read from
+    cache_dir: Option<&'static str>,
 }
 
 const PREVIEW: &str = "preview";
 const SEARCH: &str = "search";
+const LOAD_SAVED_SEARCH: &str = "load_saved_search";
+const SAVE_SEARCH: &str = "save_search";
+
+const HISTORY_FILE: &str = "search_history.json";
+const SAVED_SEARCHES_FILE: &str = "saved_searches.json";
 
 impl SearchPane {
     pub fn new(context: &AppContext) -> Self {
@@ -68,10 +83,24 @@ impl SearchPane {
                         },
                     },
                 ],
-                [ButtonInput { label: " Reset", variant: ButtonInputVariant::Reset }],
+                [
+                    ButtonInput { label: " Reset", variant: ButtonInputVariant::Reset },
+                    ButtonInput {
+                        label: " Saved searches",
+                        variant: ButtonInputVariant::SavedSearches,
+                    },
+                    ButtonInput { label: " Save search", variant: ButtonInputVariant::SaveSearch },
+                ],
             ),
             input_areas: Rc::default(),
             column_areas: [Rect::default(); 3],
+            history: read_cache(config, HISTORY_FILE),
+            history_idx: None,
+            saved: merge_saved_searches(
+                config.search.saved,
+                read_cache(config, SAVED_SEARCHES_FILE),
+            ),
+            cache_dir: config.cache_dir,
         }
     }
 
@@ -283,7 +312,9 @@ impl SearchPane {
 
         for input in &self.inputs.button_inputs {
             let mut button = match input.variant {
-                ButtonInputVariant::Reset => {
+                ButtonInputVariant::Reset
+                | ButtonInputVariant::SavedSearches
+                | ButtonInputVariant::SaveSearch => {
                     Button::default().label(input.label).label_alignment(Alignment::Left)
                 }
             };
@@ -297,6 +328,7 @@ impl SearchPane {
                 button = button.style(config.as_text_style());
             }
             frame.render_widget(button, input_areas[idx]);
+            idx += 1;
         }
     }
 
@@ -384,31 +416,184 @@ impl SearchPane {
     }
 
     fn reset(&mut self, search_config: &Search) {
-        for val in &mut self.inputs.textbox_inputs {
-            let Textbox { value, .. } = val;
-            value.clear();
+        self.load_query(&SearchQuery {
+            tags: BTreeMap::new(),
+            mode: search_config.mode,
+            case_sensitive: search_config.case_sensitive,
+        });
+    }
+
+    fn current_query(&self) -> SearchQuery {
+        let (mode, case_sensitive) = self.filter_type();
+        SearchQuery {
+            tags: self
+                .inputs
+                .textbox_inputs
+                .iter()
+                .filter(|input| !input.value.is_empty())
+                .map(|input| (input.filter_key.to_owned(), input.value.clone()))
+                .collect(),
+            mode,
+            case_sensitive,
+        }
+    }
+
+    /// Fills the inputs with values from the query. Tags which are not
+    /// configured as searchable are ignored.
+    fn load_query(&mut self, query: &SearchQuery) {
+        for Textbox { value, filter_key, .. } in &mut self.inputs.textbox_inputs {
+            *value = query.tags.get(*filter_key).cloned().unwrap_or_default();
         }
         for val in &mut self.inputs.filter_inputs {
             match val.variant {
                 FilterInputVariant::SelectFilterKind { ref mut value } => {
-                    *value = search_config.mode;
+                    *value = query.mode;
                 }
                 FilterInputVariant::SelectFilterCaseSensitive { ref mut value } => {
-                    *value = search_config.case_sensitive;
+                    *value = query.case_sensitive;
                 }
             }
         }
     }
 
-    fn activate_input(&mut self, context: &AppContext) {
+    /// Records an explicitly confirmed search. Searches triggered by changing
+    /// the filters or leaving the inputs are not recorded.
+    fn push_history(&mut self, query: SearchQuery, config: &Config) {
+        self.history_idx = None;
+        if query.tags.is_empty() {
+            return;
+        }
+        if self.history.first() == Some(&query) {
+            return;
+        }
+
+        self.history.retain(|item| item != &query);
+        self.history.insert(0, query);
+        self.history.truncate(config.search.history_size);
+        write_cache(config, HISTORY_FILE, &self.history);
+    }
+
+    /// Loads an older or a newer search from the history into the inputs.
+    /// Going past the most recent search restores the values from before the
+    /// history was entered.
+    fn recall_history(&mut self, older: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+
+        match self.history_idx.take() {
+            None if older => {
+                self.history_idx = Some((0, self.current_query()));
+            }
+            None => {}
+            Some((0, draft)) if !older => {
+                self.load_query(&draft);
+                return;
+            }
+            Some((idx, draft)) if older => {
+                self.history_idx = Some(((idx + 1).min(self.history.len() - 1), draft));
+            }
+            Some((idx, draft)) => {
+                self.history_idx = Some((idx - 1, draft));
+            }
+        }
+
+        if let Some((idx, _)) = self.history_idx {
+            let query = self.history[idx].clone();
+            self.load_query(&query);
+        }
+    }
+
+    fn open_saved_searches(&self, context: &AppContext) -> Result<()> {
+        if self.saved.is_empty() {
+            status_info!("There are no saved searches");
+            return Ok(());
+        }
+
+        modal!(
+            context,
+            SelectModal::new(context)
+                .options(self.saved.iter().map(|saved| saved.name.clone()).collect_vec())
+                .confirm_label("Load")
+                .title("Saved searches")
+                .on_confirm(|context, name: &String, _idx| {
+                    let name = name.clone();
+                    context
+                        .query()
+                        .id(LOAD_SAVED_SEARCH)
+                        .replace_id(LOAD_SAVED_SEARCH)
+                        .target(PaneType::Search)
+                        .query(move |_| Ok(MpdQueryResult::Any(Box::new(name))));
+                    Ok(())
+                })
+        );
+        Ok(())
+    }
+
+    fn open_save_search(&self, context: &AppContext) -> Result<()> {
+        if self.current_query().tags.is_empty() {
+            status_info!("Nothing to save, all of the search inputs are empty");
+            return Ok(());
+        }
+
+        modal!(
+            context,
+            InputModal::new(context)
+                .title("Save search")
+                .confirm_label("Save")
+                .input_label("Search name:")
+                .on_confirm(|context, name| {
+                    let name = name.trim().to_owned();
+                    context
+                        .query()
+                        .id(SAVE_SEARCH)
+                        .replace_id(SAVE_SEARCH)
+                        .target(PaneType::Search)
+                        .query(move |_| Ok(MpdQueryResult::Any(Box::new(name))));
+                    Ok(())
+                })
+        );
+        Ok(())
+    }
+
+    fn save_search(&mut self, name: String, config: &Config) {
+        if name.is_empty() {
+            status_warn!("Search was not saved because its name was empty");
+            return;
+        }
+
+        let saved = SavedSearch { name, query: self.current_query() };
+        status_info!("Search '{}' saved", saved.name);
+        self.saved = merge_saved_searches(&self.saved, vec![saved]);
+
+        // searches defined in the config do not need to be cached
+        let to_cache =
+            self.saved.iter().filter(|saved| !config.search.saved.contains(saved)).collect_vec();
+        write_cache(config, SAVED_SEARCHES_FILE, &to_cache);
+    }
+
+    fn activate_input(&mut self, context: &AppContext) -> Result<()> {
         match self.inputs.focused_mut() {
             FocusedInputGroup::Textboxes(_) => self.phase = Phase::SearchTextboxInput,
-            FocusedInputGroup::Buttons(_) => {
-                // Reset is the only button in this group at the moment
+            FocusedInputGroup::Buttons(ButtonInput {
+                variant: ButtonInputVariant::Reset, ..
+            }) => {
                 self.reset(&context.config.search);
                 self.songs_dir = Dir::default();
                 self.prepare_preview(context);
             }
+            FocusedInputGroup::Buttons(ButtonInput {
+                variant: ButtonInputVariant::SavedSearches,
+                ..
+            }) => {
+                self.open_saved_searches(context)?;
+            }
+            FocusedInputGroup::Buttons(ButtonInput {
+                variant: ButtonInputVariant::SaveSearch,
+                ..
+            }) => {
+                self.open_save_search(context)?;
+            }
             FocusedInputGroup::Filters(FilterInput {
                 variant: FilterInputVariant::SelectFilterKind { ref mut value },
                 ..
@@ -424,6 +609,8 @@ impl SearchPane {
                 self.search(context);
             }
         };
+
+        Ok(())
     }
 
     fn get_clicked_input(&self, event: MouseEvent) -> Option<FocusedInput> {
@@ -559,6 +746,18 @@ impl Pane for SearchPane {
                 self.phase = Phase::Search;
                 self.preview = None;
                 self.songs_dir = Dir::default();
+
+                // Switching to another profile can change the cache directory
+                let config = context.config;
+                if config.cache_dir != self.cache_dir {
+                    self.history = read_cache(config, HISTORY_FILE);
+                    self.history_idx = None;
+                    self.saved = merge_saved_searches(
+                        config.search.saved,
+                        read_cache(config, SAVED_SEARCHES_FILE),
+                    );
+                    self.cache_dir = config.cache_dir;
+                }
             }
             _ => {}
         }
@@ -595,6 +794,26 @@ impl Pane for SearchPane {
                 )]);
                 context.render()?;
             }
+            (LOAD_SAVED_SEARCH, MpdQueryResult::Any(name)) => {
+                let Ok(name) = name.downcast::<String>() else {
+                    return Ok(());
+                };
+                let Some(saved) = self.saved.iter().find(|saved| saved.name == *name) else {
+                    return Ok(());
+                };
+                let query = saved.query.clone();
+                self.load_query(&query);
+                self.phase = Phase::Search;
+                self.search(context);
+                context.render()?;
+            }
+            (SAVE_SEARCH, MpdQueryResult::Any(name)) => {
+                let Ok(name) = name.downcast::<String>() else {
+                    return Ok(());
+                };
+                self.save_search(*name, context.config);
+                context.render()?;
+            }
             _ => {}
         };
         Ok(())
@@ -671,7 +890,7 @@ impl Pane for SearchPane {
             MouseEventKind::DoubleClick => match self.phase {
                 Phase::SearchTextboxInput | Phase::Search => {
                     if self.get_clicked_input(event).is_some() {
-                        self.activate_input(context);
+                        self.activate_input(context)?;
 
                         context.render()?;
                     }
@@ -757,6 +976,7 @@ impl Pane for SearchPane {
                 }
                 Some(CommonAction::Confirm) => {
                     self.phase = Phase::Search;
+                    self.push_history(self.current_query(), context.config);
                     self.search(context);
 
                     context.render()?;
@@ -780,6 +1000,16 @@ impl Pane for SearchPane {
                             }
                             FocusedInputGroup::Filters(_) | FocusedInputGroup::Buttons(_) => {}
                         },
+                        KeyCode::Up => {
+                            self.recall_history(true);
+
+                            context.render()?;
+                        }
+                        KeyCode::Down => {
+                            self.recall_history(false);
+
+                            context.render()?;
+                        }
                         _ => {}
                     }
                 }
@@ -845,7 +1075,7 @@ impl Pane for SearchPane {
                         CommonAction::Rename => {}
                         CommonAction::Close => {}
                         CommonAction::Confirm => {
-                            self.activate_input(context);
+                            self.activate_input(context)?;
                             context.render()?;
                         }
                         CommonAction::FocusInput
@@ -1246,4 +1476,119 @@ struct ButtonInput {
 #[derive(Debug, PartialEq)]
 enum ButtonInputVariant {
     Reset,
+    SavedSearches,
+    SaveSearch,
+}
+
+/// Adds the searches to the already existing ones, replacing those with the
+/// same name
+fn merge_saved_searches(existing: &[SavedSearch], new: Vec<SavedSearch>) -> Vec<SavedSearch> {
+    let mut result = existing.to_vec();
+    for saved in new {
+        if let Some(item) = result.iter_mut().find(|item| item.name == saved.name) {
+            *item = saved;
+        } else {
+            result.push(saved);
+        }
+    }
+    result
+}
+
+fn read_cache<T: DeserializeOwned + Default>(config: &Config, file: &str) -> T {
+    let Some(cache_dir) = config.cache_dir else {
+        return T::default();
+    };
+
+    let path = format!("{cache_dir}{file}");
+    match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|err| {
+            log::error!(err:?, path = path.as_str(); "Failed to parse cached file");
+            T::default()
+        }),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => T::default(),
+        Err(err) => {
+            log::error!(err:?, path = path.as_str(); "Failed to read cached file");
+            T::default()
+        }
+    }
+}
+
+fn write_cache(config: &Config, file: &str, value: &impl Serialize) {
+    let Some(cache_dir) = config.cache_dir else {
+        return;
+    };
+
+    let result = std::fs::create_dir_all(cache_dir).map_err(anyhow::Error::from).and_then(|()| {
+        Ok(std::fs::write(format!("{cache_dir}{file}"), serde_json::to_vec(value)?)?)
+    });
+    if let Err(err) = result {
+        status_error!(err:?; "Failed to write '{}' to the cache directory", file);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::tests::fixtures::app_context;
+
+    fn query(artist: &str) -> SearchQuery {
+        SearchQuery {
+            tags: BTreeMap::from([("artist".to_owned(), artist.to_owned())]),
+            mode: FilterKind::Contains,
+            case_sensitive: false,
+        }
+    }
+
+    #[rstest]
+    fn history_moves_repeated_search_to_front(app_context: AppContext) {
+        let mut pane = SearchPane::new(&app_context);
+
+        pane.push_history(query("a"), app_context.config);
+        pane.push_history(query("b"), app_context.config);
+        pane.push_history(query("a"), app_context.config);
+
+        assert_eq!(pane.history, vec![query("a"), query("b")]);
+    }
+
+    #[rstest]
+    fn search_without_confirm_is_not_recorded(app_context: AppContext) {
+        let mut pane = SearchPane::new(&app_context);
+        pane.load_query(&query("a"));
+
+        pane.search(&app_context);
+
+        assert!(pane.history.is_empty());
+    }
+
+    #[rstest]
+    fn recalls_history_and_restores_previous_input(app_context: AppContext) {
+        let mut pane = SearchPane::new(&app_context);
+        pane.push_history(query("a"), app_context.config);
+        pane.push_history(query("b"), app_context.config);
+        pane.load_query(&query("draft"));
+
+        pane.recall_history(true);
+        assert_eq!(pane.current_query(), query("b"));
+        pane.recall_history(true);
+        assert_eq!(pane.current_query(), query("a"));
+        pane.recall_history(true);
+        assert_eq!(pane.current_query(), query("a"));
+        pane.recall_history(false);
+        assert_eq!(pane.current_query(), query("b"));
+        pane.recall_history(false);
+        assert_eq!(pane.current_query(), query("draft"));
+    }
+
+    #[test]
+    fn saved_searches_replace_those_with_same_name() {
+        let saved =
+            |name: &str, artist: &str| SavedSearch { name: name.to_owned(), query: query(artist) };
+
+        let result =
+            merge_saved_searches(&[saved("x", "a"), saved("y", "b")], vec![saved("x", "c")]);
+
+        assert_eq!(result, vec![saved("x", "c"), saved("y", "b")]);
+    }
 }
@@ -0,0 +1,336 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use anyhow::Result;
+use itertools::Itertools;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+};
+
+use super::{CommonAction, Pane};
+use crate::{
+    MpdQueryResult,
+    config::tabs::PaneType,
+    context::AppContext,
+    mpd::{
+        commands::{Stats, count::GroupedCount},
+        mpd_client::{MpdClient, Tag},
+    },
+    shared::{
+        key_event::KeyEvent,
+        mouse_event::{MouseEvent, MouseEventKind},
+    },
+    ui::{UiEvent, dirstack::DirState},
+};
+
+const STATS: &str = "stats";
+/// Number of genres shown in the top genres table
+const TOP_GENRES: usize = 20;
+
+/// Displays MPD's database statistics alongside aggregates computed from the
+/// songs in the database
+#[derive(Debug)]
+pub struct StatsPane {
+    stats: Option<Stats>,
+    library: LibraryStats,
+    /// Scroll state of the duration per artist table
+    artists_state: DirState<TableState>,
+    artists_area: Rect,
+    initialized: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct LibraryStats {
+    /// Number of songs per genre, most common first
+    genres: Vec<(String, usize)>,
+    /// Number of songs per decade, oldest first
+    decades: Vec<(u16, usize)>,
+    /// Total duration of the songs of every artist, longest first
+    artists: Vec<(String, Duration)>,
+}
+
+impl LibraryStats {
+    fn new(genres: GroupedCount, dates: GroupedCount, artists: GroupedCount) -> Self {
+        let mut decades: BTreeMap<u16, usize> = BTreeMap::new();
+        for count in dates.0 {
+            if let Some(year) = count.group.get(..4).and_then(|year| year.parse::<u16>().ok()) {
+                *decades.entry(year / 10 * 10).or_default() += count.songs as usize;
+            }
+        }
+
+        // Songs without the tag are grouped under an empty value
+        Self {
+            genres: genres
+                .0
+                .into_iter()
+                .filter(|count| !count.group.is_empty())
+                .map(|count| (count.group, count.songs as usize))
+                .sorted_by(|(a_name, a), (b_name, b)| b.cmp(a).then_with(|| a_name.cmp(b_name)))
+                .collect(),
+            decades: decades.into_iter().collect(),
+            artists: artists
+                .0
+                .into_iter()
+                .filter(|count| !count.group.is_empty())
+                .map(|count| (count.group, count.playtime))
+                .sorted_by(|(_, a), (_, b)| b.cmp(a))
+                .collect(),
+        }
+    }
+}
+
+impl StatsPane {
+    pub fn new(_context: &AppContext) -> Self {
+        Self {
+            stats: None,
+            library: LibraryStats::default(),
+            artists_state: DirState::default(),
+            artists_area: Rect::default(),
+            initialized: false,
+        }
+    }
+
+    fn fetch(context: &AppContext) {
+        context.query().id(STATS).replace_id(STATS).target(PaneType::Stats).query(|client| {
+            let stats = client.stats()?;
+            let library = LibraryStats::new(
+                client.count_grouped(Tag::Genre)?,
+                client.count_grouped(Tag::Custom("Date"))?,
+                client.count_grouped(Tag::Artist)?,
+            );
+
+            Ok(MpdQueryResult::Any(Box::new((stats, library))))
+        });
+    }
+
+    fn block<'a>(title: &'a str, context: &AppContext) -> Block<'a> {
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(context.config.as_border_style())
+            .title(title)
+    }
+
+    fn table<'a>(
+        rows: impl IntoIterator<Item = [String; 2]>,
+        title: &'a str,
+        context: &AppContext,
+    ) -> Table<'a> {
+        Table::new(rows.into_iter().map(Row::new), [Constraint::Fill(1), Constraint::Length(12)])
+            .style(context.config.as_text_style())
+            .row_highlight_style(context.config.theme.current_item_style)
+            .block(Self::block(title, context))
+    }
+}
+
+/// Formats long durations in days, hours and minutes, for example `2d 3h 15m`
+fn format_long_duration(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+fn format_timestamp(timestamp: u64) -> String {
+    i64::try_from(timestamp)
+        .ok()
+        .and_then(|timestamp| chrono::DateTime::from_timestamp(timestamp, 0))
+        .map(|date| date.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+impl Pane for StatsPane {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
+        let [db_area, aggregates_area] =
+            Layout::vertical([Constraint::Length(9), Constraint::Fill(1)]).areas(area);
+        let [genres_area, decades_area, artists_area] =
+            Layout::horizontal([Constraint::Ratio(1, 3); 3]).areas(aggregates_area);
+
+        let db_block = Self::block("Database", context);
+        let db_lines = self.stats.as_ref().map_or_else(
+            || vec![Line::from("Loading...")],
+            |stats| {
+                [
+                    ("Artists", stats.artists.to_string()),
+                    ("Albums", stats.albums.to_string()),
+                    ("Songs", stats.songs.to_string()),
+                    ("Total duration", format_long_duration(stats.db_playtime)),
+                    ("Last update", format_timestamp(stats.db_update)),
+                    ("Uptime", format_long_duration(stats.uptime)),
+                    ("Play time", format_long_duration(stats.playtime)),
+                ]
+                .into_iter()
+                .map(|(key, value)| Line::from(format!("{key:<16}{value}")))
+                .collect_vec()
+            },
+        );
+        frame.render_widget(
+            Paragraph::new(db_lines).style(context.config.as_text_style()).block(db_block),
+            db_area,
+        );
+
+        let genres = self
+            .library
+            .genres
+            .iter()
+            .take(TOP_GENRES)
+            .map(|(genre, count)| [genre.clone(), count.to_string()]);
+        frame.render_widget(Self::table(genres, "Top genres", context), genres_area);
+
+        let decades = self
+            .library
+            .decades
+            .iter()
+            .map(|(decade, count)| [format!("{decade}s"), count.to_string()]);
+        frame.render_widget(Self::table(decades, "Songs per decade", context), decades_area);
+
+        let artists = self
+            .library
+            .artists
+            .iter()
+            .map(|(artist, duration)| [artist.clone(), format_long_duration(*duration)]);
+        self.artists_area = artists_area;
+        self.artists_state.set_content_len(Some(self.library.artists.len()));
+        self.artists_state.set_viewport_len(Some(artists_area.height.saturating_sub(2).into()));
+        frame.render_stateful_widget(
+            Self::table(artists, "Duration per artist", context),
+            artists_area,
+            self.artists_state.as_render_state_ref(),
+        );
+
+        Ok(())
+    }
+
+    fn before_show(&mut self, context: &AppContext) -> Result<()> {
+        if !self.initialized {
+            Self::fetch(context);
+            self.initialized = true;
+        }
+
+        Ok(())
+    }
+
+    fn on_event(
+        &mut self,
+        event: &mut UiEvent,
+        is_visible: bool,
+        context: &AppContext,
+    ) -> Result<()> {
+        match event {
+            UiEvent::Database | UiEvent::Reconnected if is_visible => Self::fetch(context),
+            UiEvent::Database | UiEvent::Reconnected => self.initialized = false,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn on_query_finished(
+        &mut self,
+        id: &'static str,
+        data: MpdQueryResult,
+        _is_visible: bool,
+        context: &AppContext,
+    ) -> Result<()> {
+        if let (STATS, MpdQueryResult::Any(data)) = (id, data) {
+            let Ok(data) = data.downcast::<(Stats, LibraryStats)>() else {
+                return Ok(());
+            };
+            let (stats, library) = *data;
+            self.stats = Some(stats);
+            self.library = library;
+            context.render()?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        let Some(action) = event.as_common_action(context) else {
+            return Ok(());
+        };
+
+        let config = context.config;
+        match action {
+            CommonAction::Down => self.artists_state.next(config.scrolloff, config.wrap_navigation),
+            CommonAction::Up => self.artists_state.prev(config.scrolloff, config.wrap_navigation),
+            CommonAction::DownHalf => self.artists_state.next_half_viewport(config.scrolloff),
+            CommonAction::UpHalf => self.artists_state.prev_half_viewport(config.scrolloff),
+            CommonAction::PageDown => self.artists_state.next_viewport(config.scrolloff),
+            CommonAction::PageUp => self.artists_state.prev_viewport(config.scrolloff),
+            CommonAction::Top => self.artists_state.first(),
+            CommonAction::Bottom => self.artists_state.last(),
+            _ => return Ok(()),
+        }
+
+        context.render()?;
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &AppContext) -> Result<()> {
+        if !self.artists_area.contains(event.into()) {
+            return Ok(());
+        }
+
+        match event.kind {
+            MouseEventKind::ScrollDown => self.artists_state.next(context.config.scrolloff, false),
+            MouseEventKind::ScrollUp => self.artists_state.prev(context.config.scrolloff, false),
+            _ => return Ok(()),
+        }
+
+        context.render()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{LibraryStats, format_long_duration};
+    use crate::mpd::commands::count::{GroupedCount, SongCount};
+
+    fn counts(counts: &[(&str, u32, u64)]) -> GroupedCount {
+        GroupedCount(
+            counts
+                .iter()
+                .map(|(group, songs, secs)| SongCount {
+                    group: (*group).to_owned(),
+                    songs: *songs,
+                    playtime: Duration::from_secs(*secs),
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn aggregates_counts() {
+        let stats = LibraryStats::new(
+            counts(&[("", 5, 500), ("Jazz", 1, 10), ("Rock", 3, 1120)]),
+            counts(&[("1969", 1, 10), ("1975-01-01", 1, 100), ("1979", 2, 60), ("unknown", 1, 5)]),
+            counts(&[("", 1, 1000), ("a", 2, 110), ("b", 2, 120)]),
+        );
+
+        assert_eq!(stats, LibraryStats {
+            genres: vec![("Rock".to_owned(), 3), ("Jazz".to_owned(), 1)],
+            decades: vec![(1960, 1), (1970, 3)],
+            artists: vec![
+                ("b".to_owned(), Duration::from_secs(120)),
+                ("a".to_owned(), Duration::from_secs(110)),
+            ],
+        });
+    }
+
+    #[test]
+    fn formats_long_duration() {
+        assert_eq!(format_long_duration(Duration::from_secs(59)), "0m");
+        assert_eq!(format_long_duration(Duration::from_secs(3 * 3600 + 120)), "3h 2m");
+        assert_eq!(format_long_duration(Duration::from_secs(2 * 86400 + 3600)), "2d 1h 0m");
+    }
+}
@@ -16,6 +16,9 @@ pub struct Browser<T: std::fmt::Debug + DirStackItem + Clone + Send> {
     config: &'static Config,
     border_style: Style,
     pub areas: [Rect; 3],
+    /// Area at the top of the preview column reserved for an image
+    pub preview_image_area: Rect,
+    show_preview_image: bool,
     filter_input_active: bool,
 }
 
@@ -27,6 +30,8 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> Browser<T> {
             config,
             border_style: config.as_border_style(),
             areas: [Rect::default(); 3],
+            preview_image_area: Rect::default(),
+            show_preview_image: false,
             filter_input_active: false,
         }
     }
@@ -35,6 +40,13 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> Browser<T> {
         self.filter_input_active = value;
         self
     }
+
+    /// Reserves roughly square area at the top of the preview column for an
+    /// image, the preview list is rendered below it
+    pub fn set_show_preview_image(&mut self, value: bool) -> &mut Self {
+        self.show_preview_image = value;
+        self
+    }
 }
 const MIDDLE_COLUMN_SYMBOLS: symbols::border::Set = symbols::border::Set {
     top_right: symbols::line::NORMAL.horizontal_down,
@@ -80,6 +92,16 @@ where
         };
 
         if self.widths[2] > 0 {
+            // terminal cells are about twice as tall as they are wide
+            let image_height = if self.show_preview_image {
+                (preview_area.width / 2).min(preview_area.height / 2)
+            } else {
+                0
+            };
+            let [image_area, preview_area] =
+                Layout::vertical([Constraint::Length(image_height), Constraint::Min(0)])
+                    .areas(preview_area);
+            self.preview_image_area = image_area;
             self.areas[2] = preview_area;
 
             let mut result = Vec::new();